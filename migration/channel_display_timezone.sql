@@ -0,0 +1,4 @@
+USE reminders;
+
+# timezone used to display absolute reminder times in `look`. NULL uses Discord's per-viewer timestamps
+ALTER TABLE channels ADD COLUMN `display_timezone` VARCHAR(32) DEFAULT NULL;
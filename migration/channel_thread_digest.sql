@@ -0,0 +1,9 @@
+USE reminders;
+
+# post the channel's reminders into a thread made for each day, instead of into the channel itself
+ALTER TABLE channels ADD COLUMN `thread_digest` BOOL NOT NULL DEFAULT 0;
+# the thread for the current day and the day it was made for, so the sender can tell when to make the next
+ALTER TABLE channels ADD COLUMN `digest_thread_id` BIGINT UNSIGNED DEFAULT NULL;
+ALTER TABLE channels ADD COLUMN `digest_thread_date` DATE DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 9;
@@ -0,0 +1,15 @@
+USE reminders;
+
+# Discord permissions that also allow a command, alongside the roles in command_restrictions.
+# `permissions` is a permission bitfield; members need every bit in it
+CREATE TABLE command_permissions (
+    `guild_id` INT UNSIGNED NOT NULL,
+    `command` VARCHAR(16) NOT NULL,
+    `permissions` BIGINT UNSIGNED NOT NULL,
+
+    PRIMARY KEY (`guild_id`, `command`),
+
+    FOREIGN KEY (`guild_id`) REFERENCES guilds (`id`) ON DELETE CASCADE
+);
+
+UPDATE schema_version SET `version` = 6;
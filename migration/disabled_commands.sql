@@ -0,0 +1,11 @@
+USE reminders;
+
+# commands a guild has turned off for all of its members
+CREATE TABLE disabled_commands (
+    `guild_id` INT UNSIGNED NOT NULL,
+    `command` VARCHAR(16) NOT NULL,
+
+    PRIMARY KEY (`guild_id`, `command`),
+
+    FOREIGN KEY (`guild_id`) REFERENCES guilds (`id`) ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+USE reminders;
+
+# users who don't want reminders set by others sent to their DMs. Also set when Discord reports their DMs are closed
+ALTER TABLE users ADD COLUMN `dm_opt_out` BOOL NOT NULL DEFAULT 0;
+# guilds whose members shouldn't be sent DM reminders set from the guild
+ALTER TABLE guilds ADD COLUMN `dm_opt_out` BOOL NOT NULL DEFAULT 0;
@@ -0,0 +1,6 @@
+USE reminders;
+
+# record offset and snooze-all in the audit events alongside deletions
+ALTER TABLE events MODIFY COLUMN `event_name` ENUM('edit', 'enable', 'disable', 'delete', 'offset', 'snooze') NOT NULL;
+
+UPDATE schema_version SET `version` = 11;
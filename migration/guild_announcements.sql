@@ -0,0 +1,5 @@
+USE reminders;
+
+# whether operator announcements are sent to the guild, and where. Without a channel, the guild's system channel is used
+ALTER TABLE guilds ADD COLUMN `announcements` BOOL NOT NULL DEFAULT 1;
+ALTER TABLE guilds ADD COLUMN `announcement_channel` BIGINT UNSIGNED DEFAULT NULL;
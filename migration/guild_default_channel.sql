@@ -0,0 +1,4 @@
+USE reminders;
+
+# Discord ID of the channel reminders go to when no channel or user is given
+ALTER TABLE guilds ADD COLUMN `default_channel` BIGINT UNSIGNED DEFAULT NULL;
@@ -0,0 +1,4 @@
+USE reminders;
+
+# send reminders as the bot's own messages instead of through a channel webhook, for guilds that can't grant Manage Webhooks
+ALTER TABLE guilds ADD COLUMN `direct_delivery` BOOL NOT NULL DEFAULT 0;
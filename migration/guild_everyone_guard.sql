@@ -0,0 +1,6 @@
+USE reminders;
+
+# ask for confirmation before creating reminders that mention @everyone or @here
+ALTER TABLE guilds ADD COLUMN `confirm_everyone` BOOL NOT NULL DEFAULT 0;
+# additionally require the Mention Everyone permission to create such reminders
+ALTER TABLE guilds ADD COLUMN `restrict_everyone` BOOL NOT NULL DEFAULT 0;
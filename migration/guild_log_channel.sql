@@ -0,0 +1,6 @@
+USE reminders;
+
+# Discord ID of the channel that a copy of each reminder sent in the guild is posted to, for staff
+ALTER TABLE guilds ADD COLUMN `log_channel` BIGINT UNSIGNED DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 14;
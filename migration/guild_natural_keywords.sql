@@ -0,0 +1,8 @@
+USE reminders;
+
+# the guild's own words for `send`, `to` and `every` in `natural` commands, replacing the defaults
+ALTER TABLE guilds ADD COLUMN `natural_send` VARCHAR(16) DEFAULT NULL;
+ALTER TABLE guilds ADD COLUMN `natural_to` VARCHAR(16) DEFAULT NULL;
+ALTER TABLE guilds ADD COLUMN `natural_every` VARCHAR(16) DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 19;
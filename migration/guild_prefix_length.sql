@@ -0,0 +1,4 @@
+USE reminders;
+
+# prefixes are matched literally and may be up to MAX_PREFIX_LENGTH (at most 32) characters
+ALTER TABLE guilds MODIFY COLUMN `prefix` VARCHAR(32) NOT NULL DEFAULT '$';
@@ -0,0 +1,26 @@
+USE reminders;
+
+# whether the guild's keyword triggers respond to messages. Off by default, since they read every message
+ALTER TABLE guilds ADD COLUMN `keyword_triggers` BOOL NOT NULL DEFAULT 0;
+
+# messages sent when a word is said in a channel, rather than at a set time
+CREATE TABLE keyword_triggers (
+    `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+
+    `channel` BIGINT UNSIGNED NOT NULL,
+    `keyword` VARCHAR(32) NOT NULL,
+
+    `content` VARCHAR(2000) NOT NULL,
+
+    # when the trigger last fired, for the cooldown
+    `last_triggered` DATETIME DEFAULT NULL,
+
+    `set_at` TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    `set_by` BIGINT UNSIGNED NOT NULL,
+
+    PRIMARY KEY (id),
+    UNIQUE KEY (`channel`, `keyword`)
+)
+COLLATE utf8mb4_unicode_ci;
+
+UPDATE schema_version SET `version` = 10;
@@ -0,0 +1,14 @@
+USE reminders;
+
+# reminders turned off by `pause-mine`, so that resuming only turns back on the ones it turned off.
+# reminders with a resume_at are turned back on by the bot once it has passed
+CREATE TABLE paused_reminders (
+    `reminder_id` INT UNSIGNED NOT NULL,
+    `resume_at` DATETIME DEFAULT NULL,
+
+    PRIMARY KEY (`reminder_id`),
+
+    FOREIGN KEY (`reminder_id`) REFERENCES reminders (`id`) ON DELETE CASCADE
+);
+
+UPDATE schema_version SET `version` = 8;
@@ -0,0 +1,18 @@
+USE reminders;
+
+# reminders sent when a user next comes online, rather than at a set time
+CREATE TABLE presence_reminders (
+    `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+
+    `target_user` BIGINT UNSIGNED NOT NULL,
+    `channel` BIGINT UNSIGNED NOT NULL,
+
+    `content` VARCHAR(2000) NOT NULL,
+
+    `set_at` TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    `set_by` BIGINT UNSIGNED NOT NULL,
+
+    PRIMARY KEY (id),
+    INDEX (`target_user`)
+)
+COLLATE utf8mb4_unicode_ci;
@@ -0,0 +1,4 @@
+USE reminders;
+
+# repeat until acknowledged: each send carries a button with the custom ID `acknowledge:<uid>`, and pressing it deletes the reminder
+ALTER TABLE reminders ADD COLUMN `acknowledge` BOOL NOT NULL DEFAULT 0;
@@ -0,0 +1,7 @@
+USE reminders;
+
+# content over Discord's 2000 character message limit is stored as the embed description, which can hold up to
+# 4096 characters (MAX_CONTENT_LENGTH)
+ALTER TABLE reminders MODIFY COLUMN `embed_description` VARCHAR(4096) NOT NULL DEFAULT '';
+
+UPDATE schema_version SET `version` = 21;
@@ -0,0 +1,6 @@
+USE reminders;
+
+# publish the reminder after sending it, so servers following the announcement channel receive it too
+ALTER TABLE reminders ADD COLUMN `crosspost` BOOL NOT NULL DEFAULT 0;
+
+UPDATE schema_version SET `version` = 3;
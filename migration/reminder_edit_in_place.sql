@@ -0,0 +1,6 @@
+USE reminders;
+
+# when set, an interval reminder edits its previously delivered message instead of sending a new one
+ALTER TABLE reminders ADD COLUMN `edit_in_place` BOOL NOT NULL DEFAULT 0 AFTER `pin`;
+# id of the most recent message sent for this reminder, used to find the message to edit
+ALTER TABLE reminders ADD COLUMN `last_message_id` BIGINT UNSIGNED DEFAULT NULL AFTER `edit_in_place`;
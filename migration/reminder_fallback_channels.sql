@@ -0,0 +1,19 @@
+USE reminders;
+
+# channels set with --fallback, tried in order of position when the reminder can't be posted in its
+# own channel. The channel it was last delivered to is recorded by the sender in delivered_channel_id
+CREATE TABLE reminder_fallback_channels (
+    `reminder_id` INT UNSIGNED NOT NULL,
+    `channel_id` INT UNSIGNED NOT NULL,
+    `position` TINYINT UNSIGNED NOT NULL,
+
+    PRIMARY KEY (`reminder_id`, `position`),
+
+    FOREIGN KEY (`reminder_id`) REFERENCES reminders (`id`) ON DELETE CASCADE,
+    FOREIGN KEY (`channel_id`) REFERENCES channels (`id`) ON DELETE CASCADE
+);
+
+ALTER TABLE reminders ADD COLUMN `delivered_channel_id` INT UNSIGNED DEFAULT NULL;
+ALTER TABLE reminders ADD FOREIGN KEY (`delivered_channel_id`) REFERENCES channels(`id`) ON DELETE SET NULL;
+
+UPDATE schema_version SET `version` = 20;
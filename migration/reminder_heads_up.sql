@@ -0,0 +1,8 @@
+USE reminders;
+
+# heads-ups are earlier reminders set alongside a reminder with --before. The link is kept so
+# deleting the reminder can offer to delete its heads-ups too
+ALTER TABLE reminders ADD COLUMN `heads_up_for` INT UNSIGNED DEFAULT NULL;
+ALTER TABLE reminders ADD FOREIGN KEY (`heads_up_for`) REFERENCES reminders(`id`) ON DELETE SET NULL;
+
+UPDATE schema_version SET `version` = 13;
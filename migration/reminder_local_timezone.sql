@@ -0,0 +1,7 @@
+USE reminders;
+
+# the timezone a reminder's time was given as a local time in, so that the reminder can be moved to
+# the same local time when its creator changes timezone. NULL for times given relative to now
+ALTER TABLE reminders ADD COLUMN `local_timezone` VARCHAR(32) DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 16;
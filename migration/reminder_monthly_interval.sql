@@ -0,0 +1,8 @@
+USE reminders;
+
+# reminders set to repeat on the nth weekday of each month, e.g. the 2nd friday. The weekday counts from monday
+# as 0. When set, the next time is that weekday of the following month rather than `utc_time` + `interval`
+ALTER TABLE reminders ADD COLUMN `interval_weekday` TINYINT UNSIGNED DEFAULT NULL;
+ALTER TABLE reminders ADD COLUMN `interval_nth` TINYINT UNSIGNED DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 23;
@@ -0,0 +1,7 @@
+USE reminders;
+
+# until this time the reminder is still sent, but with its mentions suppressed and without a
+# notification. Cleared by the bot once it has passed
+ALTER TABLE reminders ADD COLUMN `muted_until` DATETIME DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 18;
@@ -0,0 +1,7 @@
+USE reminders;
+
+# a message in the reminder's channel to send the reminder as a reply to. Webhooks can't reply, so
+# it only applies to direct delivery, and a missing message means a standalone post instead
+ALTER TABLE reminders ADD COLUMN `reply_to` BIGINT UNSIGNED DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 7;
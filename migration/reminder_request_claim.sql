@@ -0,0 +1,7 @@
+USE reminders;
+
+# requests are claimed before their reminder is created, so the reminder is filled in afterwards. A claim without
+# a reminder is a request still being created
+ALTER TABLE reminder_requests MODIFY COLUMN reminder_id INT UNSIGNED DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 22;
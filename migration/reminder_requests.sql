@@ -0,0 +1,13 @@
+USE reminders;
+
+# the message that created each reminder, so a message delivered twice doesn't create the reminder twice. rows older than IDEMPOTENCY_WINDOW are removed as new reminders are created
+CREATE TABLE reminder_requests (
+    request VARCHAR(100) NOT NULL,
+    reminder_id INT UNSIGNED NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (request),
+    FOREIGN KEY (reminder_id) REFERENCES reminders(id) ON DELETE CASCADE
+);
+
+UPDATE schema_version SET `version` = 4;
@@ -0,0 +1,6 @@
+USE reminders;
+
+# send with the SUPPRESS_NOTIFICATIONS message flag, so the reminder arrives without a push notification
+ALTER TABLE reminders ADD COLUMN `silent` BOOL NOT NULL DEFAULT 0;
+
+UPDATE schema_version SET `version` = 2;
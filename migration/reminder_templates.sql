@@ -0,0 +1,21 @@
+USE reminders;
+
+CREATE TABLE reminder_templates (
+    `id` INT UNSIGNED NOT NULL AUTO_INCREMENT,
+    `guild_id` INT UNSIGNED NOT NULL,
+
+    `name` VARCHAR(24) NOT NULL,
+
+    `content` VARCHAR(2048) NOT NULL DEFAULT '',
+    `interval` INT UNSIGNED,
+
+    `embed_title` VARCHAR(256) NOT NULL DEFAULT '',
+    `embed_description` VARCHAR(2048) NOT NULL DEFAULT '',
+    `embed_color` INT UNSIGNED NOT NULL DEFAULT 0x0,
+
+    PRIMARY KEY (id),
+    UNIQUE KEY (`guild_id`, `name`),
+
+    FOREIGN KEY (`guild_id`) REFERENCES guilds (`id`) ON DELETE CASCADE
+)
+COLLATE utf8mb4_unicode_ci;
@@ -0,0 +1,6 @@
+USE reminders;
+
+# record restriction changes in the audit events
+ALTER TABLE events MODIFY COLUMN `event_name` ENUM('edit', 'enable', 'disable', 'delete', 'offset', 'snooze', 'restrict') NOT NULL;
+
+UPDATE schema_version SET `version` = 12;
@@ -0,0 +1,6 @@
+USE reminders;
+
+# temporary grants from `restrict <role> <commands> <duration>`. NULL grants are permanent
+ALTER TABLE command_restrictions ADD COLUMN `expires` DATETIME DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 5;
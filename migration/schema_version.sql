@@ -0,0 +1,9 @@
+USE reminders;
+
+# the version of the schema, checked by the bot at startup. Every migration after this one must bump it, and the
+# bot's SCHEMA_VERSION along with it
+CREATE TABLE schema_version (
+    `version` INT UNSIGNED NOT NULL
+);
+
+INSERT INTO schema_version (`version`) VALUES (1);
@@ -0,0 +1,5 @@
+USE reminders;
+
+# user who started the timer, so timers can be listed per user across servers. Unknown for existing guild timers
+ALTER TABLE timers ADD COLUMN `created_by` BIGINT UNSIGNED DEFAULT NULL AFTER `owner`;
+CREATE INDEX `timers_created_by` ON timers (`created_by`);
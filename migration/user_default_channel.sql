@@ -0,0 +1,7 @@
+USE reminders;
+
+# Discord ID of the channel a user's reminders go to when no channel or user is given. Only used
+# in the guild the channel belongs to, and takes priority over the guild's default channel
+ALTER TABLE users ADD COLUMN `default_channel` BIGINT UNSIGNED DEFAULT NULL;
+
+UPDATE schema_version SET `version` = 17;
@@ -0,0 +1,10 @@
+USE reminders;
+
+# times of day, in the user's timezone, that reminders sent to their DMs are held back or sent
+# silently. The window wraps past midnight when the end is earlier than the start
+ALTER TABLE users ADD COLUMN `quiet_start` TIME DEFAULT NULL;
+ALTER TABLE users ADD COLUMN `quiet_end` TIME DEFAULT NULL;
+# send during quiet hours without a notification, rather than waiting for them to end
+ALTER TABLE users ADD COLUMN `quiet_silent` BOOL NOT NULL DEFAULT 0;
+
+UPDATE schema_version SET `version` = 15;
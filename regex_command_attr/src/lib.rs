@@ -8,7 +8,7 @@ extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse::Error, parse_macro_input, spanned::Spanned, Lit};
+use syn::{parse::Error, parse_macro_input, spanned::Spanned, Lit, ReturnType};
 
 pub(crate) mod attributes;
 pub(crate) mod consts;
@@ -59,7 +59,8 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
         match_options!(name, values, options, span => [
             permission_level;
             supports_dm;
-            can_blacklist
+            can_blacklist;
+            can_disable
         ]);
     }
 
@@ -67,6 +68,7 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
         permission_level,
         supports_dm,
         can_blacklist,
+        can_disable,
     } = options;
 
     let visibility = fun.visibility;
@@ -82,6 +84,24 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
     populate_fut_lifetimes_on_refs(&mut fun.args);
     let args = fun.args;
 
+    let result_path = quote!(crate::framework::CommandResult);
+
+    // commands without a return type can't fail, so their body is wrapped to always succeed. The
+    // inner block keeps any early `return;` in the body valid
+    let future = match fun.ret {
+        ReturnType::Default => quote! {
+            async move {
+                async move { #(#body)* }.await;
+
+                Ok(())
+            }
+        },
+
+        ReturnType::Type(..) => quote! {
+            async move { #(#body)* }
+        },
+    };
+
     (quote! {
         #(#cooked)*
         pub static #n: #command_path = #command_path {
@@ -90,12 +110,13 @@ pub fn command(attr: TokenStream, input: TokenStream) -> TokenStream {
             required_perms: #permission_level,
             supports_dm: #supports_dm,
             can_blacklist: #can_blacklist,
+            can_disable: #can_disable,
         };
 
-        #visibility fn #name<'fut> (#(#args),*) -> ::serenity::futures::future::BoxFuture<'fut, ()> {
+        #visibility fn #name<'fut> (#(#args),*) -> ::serenity::futures::future::BoxFuture<'fut, #result_path> {
             use ::serenity::futures::future::FutureExt;
 
-            async move { #(#body)* }.boxed()
+            #future.boxed()
         }
     })
     .into()
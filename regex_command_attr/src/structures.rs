@@ -6,7 +6,7 @@ use syn::{
     braced,
     parse::{Error, Parse, ParseStream, Result},
     spanned::Spanned,
-    Attribute, Block, FnArg, Ident, Pat, Path, PathSegment, Stmt, Token, Visibility,
+    Attribute, Block, FnArg, Ident, Pat, Path, PathSegment, ReturnType, Stmt, Token, Visibility,
 };
 
 fn parse_argument(arg: FnArg) -> Result<Argument> {
@@ -91,6 +91,8 @@ pub struct CommandFun {
     pub visibility: Visibility,
     pub name: Ident,
     pub args: Vec<Argument>,
+    /// `-> CommandResult` for commands that report errors, or nothing for those that don't
+    pub ret: ReturnType,
     pub body: Vec<Stmt>,
 }
 
@@ -121,6 +123,9 @@ impl Parse for CommandFun {
         // (...)
         let Parenthesised(args) = input.parse::<Parenthesised<FnArg>>()?;
 
+        // -> ...
+        let ret = input.parse::<ReturnType>()?;
+
         // { ... }
         let bcont;
         braced!(bcont in input);
@@ -137,6 +142,7 @@ impl Parse for CommandFun {
             visibility,
             name,
             args,
+            ret,
             body,
         })
     }
@@ -150,12 +156,13 @@ impl ToTokens for CommandFun {
             visibility,
             name,
             args,
+            ret,
             body,
         } = self;
 
         stream.extend(quote! {
             #(#cooked)*
-            #visibility async fn #name (#(#args),*) -> () {
+            #visibility async fn #name (#(#args),*) #ret {
                 #(#body)*
             }
         });
@@ -216,6 +223,7 @@ pub struct Options {
     pub permission_level: PermissionLevel,
     pub supports_dm: bool,
     pub can_blacklist: bool,
+    pub can_disable: bool,
 }
 
 impl Options {
@@ -224,6 +232,7 @@ impl Options {
         let mut options = Self::default();
 
         options.can_blacklist = true;
+        options.can_disable = true;
         options.supports_dm = true;
 
         options
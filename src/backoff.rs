@@ -0,0 +1,72 @@
+use serenity::{
+    http::{HttpError, StatusCode},
+    Error, Result as SerenityResult,
+};
+
+use log::warn;
+
+use rand::Rng;
+
+use tokio::time::sleep;
+
+use std::{future::Future, time::Duration};
+
+use crate::consts::{DISCORD_RETRY_ATTEMPTS, DISCORD_RETRY_BASE_DELAY};
+
+/// Whether a failed request is worth trying again: Discord-side errors, rate limits and
+/// connection failures are, anything else (missing permissions, bad payloads) is not.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::Http(http_error) => match http_error.as_ref() {
+            HttpError::UnsuccessfulRequest(response) => {
+                response.status_code.is_server_error()
+                    || response.status_code == StatusCode::TOO_MANY_REQUESTS
+            }
+
+            HttpError::Request(_) => true,
+
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+/// Run a Discord request, retrying transient failures with jittered exponential backoff. Gives
+/// up after `DISCORD_RETRY_ATTEMPTS` attempts and returns the last error.
+pub async fn with_backoff<T, F, Fut>(description: &str, mut request: F) -> SerenityResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = SerenityResult<T>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match request().await {
+            Ok(result) => return Ok(result),
+
+            Err(e) if attempt < *DISCORD_RETRY_ATTEMPTS && is_transient(&e) => {
+                let ceiling = DISCORD_RETRY_BASE_DELAY.saturating_mul(1 << (attempt - 1).min(16));
+                let delay = rand::thread_rng().gen_range(ceiling / 2, ceiling + 1);
+
+                warn!(
+                    "{} failed (attempt {} of {}), retrying in {}ms: {:?}",
+                    description, attempt, *DISCORD_RETRY_ATTEMPTS, delay, e
+                );
+
+                sleep(Duration::from_millis(delay)).await;
+
+                attempt += 1;
+            }
+
+            Err(e) => {
+                warn!(
+                    "{} failed after {} attempt(s): {:?}",
+                    description, attempt, e
+                );
+
+                return Err(e);
+            }
+        }
+    }
+}
@@ -2,7 +2,8 @@ use regex_command_attr::command;
 
 use serenity::{builder::CreateEmbedFooter, client::Context, model::channel::Message};
 
-use chrono::offset::Utc;
+use chrono::{offset::Utc, TimeZone};
+use chrono_tz::Tz;
 
 use crate::{
     command_help,
@@ -10,7 +11,8 @@ use crate::{
     get_ctx_data,
     language_manager::LanguageManager,
     models::{user_data::UserData, CtxGuildData},
-    FrameworkCtx, THEME_COLOR,
+    time_parser::TimeParser,
+    FrameworkCtx, PopularTimezones, THEME_COLOR,
 };
 
 use std::{
@@ -50,6 +52,7 @@ async fn footer(ctx: &Context) -> impl FnOnce(&mut CreateEmbedFooter) -> &mut Cr
 
 #[command]
 #[can_blacklist(false)]
+#[can_disable(false)]
 async fn help(ctx: &Context, msg: &Message, args: String) {
     async fn default_help(
         ctx: &Context,
@@ -69,27 +72,27 @@ async fn help(ctx: &Context, msg: &Message, args: String) {
                         .description(desc)
                         .field(
                             lm.get(language, "help/setup_title"),
-                            "`lang` `timezone` `meridian`",
+                            "`lang` `timezone` `channeltz` `meridian` `dms` `quiet` `mychannel`",
                             true,
                         )
                         .field(
                             lm.get(language, "help/mod_title"),
-                            "`prefix` `blacklist` `restrict` `alias`",
+                            "`prefix` `blacklist` `restrict` `audit` `disable` `settings` `alias` `everyone` `defaultchannel` `logchannel` `naturalwords` `announcements` `serverdms` `delivery` `digest` `keyword` `webhook`",
                             true,
                         )
                         .field(
                             lm.get(language, "help/reminder_title"),
-                            "`remind` `interval` `natural` `look` `countdown`",
+                            "`remind` `interval` `nag` `natural` `look` `next` `agenda` `after` `count` `countdown` `online` `pause-mine` `resume-mine`",
                             true,
                         )
                         .field(
                             lm.get(language, "help/reminder_mod_title"),
-                            "`del` `offset` `pause` `nudge`",
+                            "`del` `del-uid` `mute` `bump` `offset` `snooze-all` `pause` `nudge` `template` `stats` `list-channels` `inspect` `preview`",
                             true,
                         )
                         .field(
                             lm.get(language, "help/info_title"),
-                            "`help` `info` `donate` `clock`",
+                            "`help` `info` `donate` `clock` `convert`",
                             true,
                         )
                         .field(
@@ -135,6 +138,7 @@ async fn help(ctx: &Context, msg: &Message, args: String) {
 }
 
 #[command]
+#[can_disable(false)]
 async fn info(ctx: &Context, msg: &Message, _args: String) {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
@@ -219,3 +223,124 @@ async fn clock(ctx: &Context, msg: &Message, _args: String) {
         )
         .await;
 }
+
+#[command("convert")]
+async fn convert(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let mut split = args.split_whitespace();
+
+    let (time, source) = match (split.next(), split.next()) {
+        (Some(time), Some(source)) => (time, source),
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "convert").await;
+
+            return;
+        }
+    };
+
+    let source_tz = match source.parse::<Tz>() {
+        Ok(tz) => tz,
+
+        Err(_) => {
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "convert/unknown_timezone")
+                        .replace("{timezone}", source),
+                )
+                .await;
+
+            return;
+        }
+    };
+
+    // a parsed timestamp can still be outside the range chrono can represent
+    let utc = match TimeParser::new(time, source_tz)
+        .timestamp()
+        .ok()
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+    {
+        Some(utc) => utc,
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "convert/invalid_time"))
+                .await;
+
+            return;
+        }
+    };
+
+    let mut targets = vec![];
+    let mut unknown = vec![];
+
+    for target in split {
+        match target.parse::<Tz>() {
+            Ok(tz) => targets.push(tz),
+
+            Err(_) => unknown.push(target),
+        }
+    }
+
+    let popular_timezones = ctx
+        .data
+        .read()
+        .await
+        .get::<PopularTimezones>()
+        .cloned()
+        .unwrap();
+
+    let mut zones = vec![source_tz];
+
+    // embeds can hold at most 25 fields, so the popular timezones fill whatever space is left
+    for tz in targets.iter().chain(popular_timezones.iter()) {
+        if zones.len() == 25 {
+            break;
+        }
+
+        if !zones.contains(tz) {
+            zones.push(*tz);
+        }
+    }
+
+    let fields = zones.iter().map(|tz| {
+        (
+            tz.to_string(),
+            format!(
+                "🕗 `{}`",
+                utc.with_timezone(tz).format("%Y-%m-%d %H:%M").to_string()
+            ),
+            true,
+        )
+    });
+
+    let mut description = format!("<t:{}:F>", utc.timestamp());
+
+    if !unknown.is_empty() {
+        description.push_str("\n\n");
+        description.push_str(
+            &lm.get(&language, "convert/unknown_timezone")
+                .replace("{timezone}", &unknown.join(", ")),
+        );
+    }
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                e.title(lm.get(&language, "convert/title"))
+                    .description(description)
+                    .fields(fields)
+                    .color(*THEME_COLOR)
+            })
+        })
+        .await;
+}
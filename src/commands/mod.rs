@@ -1,4 +1,5 @@
 pub mod info_cmds;
 pub mod moderation_cmds;
+pub mod owner_cmds;
 pub mod reminder_cmds;
 pub mod todo_cmds;
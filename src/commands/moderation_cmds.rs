@@ -4,31 +4,50 @@ use serenity::{
     builder::CreateActionRow,
     client::Context,
     framework::Framework,
+    http::AttachmentType,
     model::{
-        channel::Message,
+        channel::{Channel, ChannelType, Message},
+        guild::Role,
         id::{ChannelId, MessageId, RoleId},
-        interactions::message_component::ButtonStyle,
+        interactions::{message_component::ButtonStyle, InteractionResponseType},
+        misc::Mentionable,
+        permissions::Permissions,
     },
 };
 
 use chrono_tz::{Tz, TZ_VARIANTS};
 
-use chrono::offset::Utc;
+use chrono::{offset::Utc, NaiveTime};
 
 use inflector::Inflector;
 
 use levenshtein::levenshtein;
 
+use log::warn;
+
 use crate::{
     command_help,
-    consts::{REGEX_ALIAS, REGEX_CHANNEL, REGEX_COMMANDS, REGEX_ROLE, THEME_COLOR},
-    framework::SendIterator,
+    commands::reminder_cmds::await_confirmation,
+    consts::{
+        DEFAULT_NATURAL_EVERY, DEFAULT_NATURAL_SEND, DEFAULT_NATURAL_TO, MAX_PREFIX_LENGTH,
+        REGEX_ALIAS, REGEX_CHANNEL, REGEX_COMMANDS, REGEX_ROLE, RESERVED_NATURAL_WORDS,
+        THEME_COLOR,
+    },
+    framework::{CommandError, CommandResult, SendIterator},
     get_ctx_data,
-    models::{channel_data::ChannelData, guild_data::GuildData, user_data::UserData, CtxGuildData},
+    guild_context::{guild_context, guild_member},
+    language_manager::LanguageManager,
+    models::{
+        channel_data::ChannelData, event::Event, guild_data::GuildData,
+        guild_settings::GuildSettings, reminder::Reminder, user_data::UserData, CtxGuildData,
+    },
+    time_parser::TimeParser,
     FrameworkCtx, PopularTimezones,
 };
 
-use std::{collections::HashMap, iter};
+use sqlx::MySqlPool;
+
+use std::{collections::HashMap, iter, time::Duration};
 
 #[command]
 #[supports_dm(false)]
@@ -86,6 +105,7 @@ async fn blacklist(ctx: &Context, msg: &Message, args: String) {
 }
 
 #[command]
+#[can_disable(false)]
 async fn timezone(ctx: &Context, msg: &Message, args: String) {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
@@ -97,19 +117,52 @@ async fn timezone(ctx: &Context, msg: &Message, args: String) {
         1,
     );
 
+    // moves the user's reminders set for a local time to the same local time in the new timezone
+    let shift_reminders = args
+        .split_whitespace()
+        .any(|arg| arg == "--shift-reminders");
+    let args = args
+        .split_whitespace()
+        .filter(|arg| *arg != "--shift-reminders")
+        .collect::<Vec<&str>>()
+        .join(" ");
+
     if !args.is_empty() {
         match args.parse::<Tz>() {
-            Ok(_) => {
+            Ok(new_timezone) => {
+                let old_timezone = user_data.timezone();
+
                 user_data.timezone = args;
                 user_data.commit_changes(&pool).await;
 
                 let now = Utc::now().with_timezone(&user_data.timezone());
 
-                let content = lm
+                let mut content = lm
                     .get(&user_data.language, "timezone/set_p")
                     .replacen("{timezone}", &user_data.timezone, 1)
                     .replacen("{time}", &now.format("%H:%M").to_string(), 1);
 
+                if shift_reminders && old_timezone != new_timezone {
+                    let shifted =
+                        Reminder::shift_timezone(&pool, user_data.id, old_timezone, new_timezone)
+                            .await;
+
+                    content.push_str("\n\n");
+
+                    match shifted {
+                        Ok(shifted) => content.push_str(
+                            &lm.get(&user_data.language, "timezone/shifted")
+                                .replace("{count}", &shifted.to_string()),
+                        ),
+
+                        Err(e) => {
+                            warn!("Couldn't shift reminders for {}: {:?}", msg.author.id, e);
+
+                            content.push_str(lm.get(&user_data.language, "timezone/shift_failed"));
+                        }
+                    }
+                }
+
                 let _ =
                     msg.channel_id
                         .send_message(&ctx, |m| {
@@ -237,7 +290,367 @@ async fn timezone(ctx: &Context, msg: &Message, args: String) {
     }
 }
 
+#[command("channeltz")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn channel_timezone(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let mut channel = ChannelData::from_channel(msg.channel(&ctx).await.unwrap(), &pool)
+        .await
+        .unwrap();
+
+    if args.is_empty() {
+        let content = match &channel.display_timezone {
+            Some(timezone) => lm
+                .get(&language, "channeltz/current")
+                .replace("{timezone}", timezone),
+
+            None => lm.get(&language, "channeltz/none").to_string(),
+        };
+
+        let _ = msg.channel_id.say(&ctx, content).await;
+    } else if args.to_lowercase() == "off" {
+        channel.display_timezone = None;
+        channel.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "channeltz/removed"))
+            .await;
+    } else {
+        match args.parse::<Tz>() {
+            Ok(timezone) => {
+                channel.display_timezone = Some(timezone.to_string());
+                channel.commit_changes(&pool).await;
+
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(&language, "channeltz/set")
+                            .replace("{timezone}", &timezone.to_string()),
+                    )
+                    .await;
+            }
+
+            Err(_) => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "channeltz/invalid"))
+                    .await;
+            }
+        }
+    }
+}
+
+#[command("dms")]
+async fn dms(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let mut user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+
+    let mut split = args.splitn(2, char::is_whitespace);
+
+    match split.next().unwrap_or("").to_lowercase().as_str() {
+        // `dms server` is `serverdms`, so it needs the same permission
+        "server" | "guild" => {
+            let permitted = match msg.guild_id {
+                Some(guild_id) => match (
+                    guild_context(&ctx, guild_id).await,
+                    guild_member(&ctx, guild_id, msg.author.id).await,
+                ) {
+                    (Ok(guild), Ok(member)) => {
+                        DMS_GUILD_COMMAND
+                            .check_permissions(&ctx, &guild, &member)
+                            .await
+                    }
+
+                    _ => false,
+                },
+
+                None => false,
+            };
+
+            if permitted {
+                set_guild_dms(ctx, msg, split.next().unwrap_or("").trim()).await;
+            } else if msg.guild_id.is_some() {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&user_data.language, "no_perms_restricted"))
+                    .await;
+            } else {
+                let prefix = ctx.prefix(msg.guild_id).await;
+
+                command_help(ctx, msg, lm, &prefix, &user_data.language, "dms").await;
+            }
+        }
+
+        "off" => {
+            user_data.dm_opt_out = true;
+            user_data.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "dms/off"))
+                .await;
+        }
+
+        "on" => {
+            user_data.dm_opt_out = false;
+            user_data.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "dms/on"))
+                .await;
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &user_data.language, "dms").await;
+        }
+    }
+}
+
+/// `quiet <start> <end> [silent]`: hold back reminders sent to the user's DMs between two times
+/// of day, or send them without a notification with `silent`. `quiet off` removes the window
+#[command("quiet")]
+async fn quiet(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let mut user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+
+    let mut split = args.split_whitespace();
+
+    match (split.next(), split.next(), split.next()) {
+        (None, _, _) => {
+            let content = match (user_data.quiet_start, user_data.quiet_end) {
+                (Some(start), Some(end)) => {
+                    let key = if user_data.in_quiet_hours(Utc::now()) {
+                        "quiet/current_active"
+                    } else {
+                        "quiet/current"
+                    };
+
+                    lm.get(&user_data.language, key)
+                        .replace("{start}", &start.format("%H:%M").to_string())
+                        .replace("{end}", &end.format("%H:%M").to_string())
+                }
+
+                _ => lm.get(&user_data.language, "quiet/none").to_string(),
+            };
+
+            let _ = msg.channel_id.say(&ctx, content).await;
+        }
+
+        (Some(off), None, None) if off.eq_ignore_ascii_case("off") => {
+            user_data.quiet_start = None;
+            user_data.quiet_end = None;
+            user_data.quiet_silent = false;
+            user_data.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "quiet/off"))
+                .await;
+        }
+
+        (Some(start), Some(end), mode)
+            if mode.map_or(true, |mode| mode.eq_ignore_ascii_case("silent")) =>
+        {
+            let parse = |time: &str| NaiveTime::parse_from_str(time, "%H:%M").ok();
+
+            match (parse(start), parse(end)) {
+                (Some(start), Some(end)) if start != end => {
+                    user_data.quiet_start = Some(start);
+                    user_data.quiet_end = Some(end);
+                    user_data.quiet_silent = mode.is_some();
+                    user_data.commit_changes(&pool).await;
+
+                    let key = if user_data.quiet_silent {
+                        "quiet/set_silent"
+                    } else {
+                        "quiet/set"
+                    };
+
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&user_data.language, key)
+                                .replace("{start}", &start.format("%H:%M").to_string())
+                                .replace("{end}", &end.format("%H:%M").to_string()),
+                        )
+                        .await;
+                }
+
+                _ => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&user_data.language, "quiet/invalid_time"))
+                        .await;
+                }
+            }
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &user_data.language, "quiet").await;
+        }
+    }
+}
+
+/// `mychannel #channel|off`: the user's own default for reminders set in that channel's guild
+/// without a channel or user. Takes priority over the guild's `defaultchannel`
+#[command("mychannel")]
+async fn my_channel(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let mut user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+
+    if args.is_empty() {
+        let content = match user_data.default_channel {
+            Some(channel_id) => lm
+                .get(&user_data.language, "mychannel/current")
+                .replace("{channel}", &ChannelId(channel_id).mention().to_string()),
+
+            None => lm.get(&user_data.language, "mychannel/none").to_string(),
+        };
+
+        let _ = msg.channel_id.say(&ctx, content).await;
+    } else if args.to_lowercase() == "off" {
+        user_data.default_channel = None;
+        user_data.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&user_data.language, "mychannel/removed"))
+            .await;
+    } else if let Some(channel_id) = REGEX_CHANNEL
+        .captures(&args)
+        .map(|cap| cap.get(1))
+        .flatten()
+        .map(|c| c.as_str().parse::<u64>().ok())
+        .flatten()
+        .map(ChannelId)
+    {
+        // only channels in the guild the command is used in, so DMs can only clear the setting
+        let channel = channel_id
+            .to_channel_cached(&ctx)
+            .map(|channel| channel.guild())
+            .flatten()
+            .filter(|channel| Some(channel.guild_id) == msg.guild_id);
+
+        let direct_delivery = match msg.guild_id.map(|guild_id| ctx.guild_data(guild_id)) {
+            Some(guild_data) => match guild_data.await {
+                Ok(guild_data) => guild_data.read().await.direct_delivery,
+
+                Err(_) => false,
+            },
+
+            None => false,
+        };
+
+        // the user needs to be able to post there themselves, as well as the bot
+        let can_post = channel
+            .as_ref()
+            .map(|channel| {
+                let user_can_post = channel
+                    .permissions_for_user(&ctx, msg.author.id)
+                    .map(|perms| perms.send_messages())
+                    .unwrap_or(false);
+
+                let bot_can_post = channel
+                    .permissions_for_user(&ctx, ctx.cache.current_user_id())
+                    .map(|perms| {
+                        perms.send_messages() && (direct_delivery || perms.manage_webhooks())
+                    })
+                    .unwrap_or(false);
+
+                user_can_post && bot_can_post
+            })
+            .unwrap_or(false);
+
+        if channel.is_none() {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "mychannel/invalid"))
+                .await;
+        } else if !can_post {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "mychannel/no_perms"))
+                .await;
+        } else {
+            user_data.default_channel = Some(*channel_id.as_u64());
+            user_data.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&user_data.language, "mychannel/set")
+                        .replace("{channel}", &channel_id.mention().to_string()),
+                )
+                .await;
+        }
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &user_data.language, "mychannel").await;
+    }
+}
+
+#[command("serverdms")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn dms_guild(ctx: &Context, msg: &Message, args: String) {
+    set_guild_dms(ctx, msg, &args).await;
+}
+
+/// `serverdms on|off`, and `dms server on|off`: whether reminders can be set for the DMs of the
+/// guild's members
+async fn set_guild_dms(ctx: &Context, msg: &Message, args: &str) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let opt_out = match args.to_lowercase().as_str() {
+        "off" => Some(true),
+        "on" => Some(false),
+        _ => None,
+    };
+
+    match opt_out {
+        Some(opt_out) => {
+            guild_data.write().await.dm_opt_out = opt_out;
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let response = if opt_out {
+                "dms/server_off"
+            } else {
+                "dms/server_on"
+            };
+
+            let _ = msg.channel_id.say(&ctx, lm.get(&language, response)).await;
+        }
+
+        None => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "serverdms").await;
+        }
+    }
+}
+
 #[command("lang")]
+#[can_disable(false)]
 async fn language(ctx: &Context, msg: &Message, args: String) {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
@@ -354,16 +767,21 @@ async fn language(ctx: &Context, msg: &Message, args: String) {
 #[command]
 #[supports_dm(false)]
 #[permission_level(Restricted)]
+#[can_disable(false)]
 async fn prefix(ctx: &Context, msg: &Message, args: String) {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
     let language = UserData::language_of(&msg.author, &pool).await;
 
-    if args.len() > 5 {
+    if args.chars().count() > *MAX_PREFIX_LENGTH {
         let _ = msg
             .channel_id
-            .say(&ctx, lm.get(&language, "prefix/too_long"))
+            .say(
+                &ctx,
+                lm.get(&language, "prefix/too_long")
+                    .replace("{max}", &MAX_PREFIX_LENGTH.to_string()),
+            )
             .await;
     } else if args.is_empty() {
         let _ = msg
@@ -385,101 +803,1131 @@ async fn prefix(ctx: &Context, msg: &Message, args: String) {
     }
 }
 
-#[command]
+#[command("defaultchannel")]
 #[supports_dm(false)]
 #[permission_level(Restricted)]
-async fn restrict(ctx: &Context, msg: &Message, args: String) {
+async fn default_channel(ctx: &Context, msg: &Message, args: String) {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
     let language = UserData::language_of(&msg.author, &pool).await;
-    let guild_data = GuildData::from_guild(msg.guild(&ctx).unwrap(), &pool)
-        .await
-        .unwrap();
-
-    let role_tag_match = REGEX_ROLE.find(&args);
-
-    if let Some(role_tag) = role_tag_match {
-        let commands = REGEX_COMMANDS
-            .find_iter(&args.to_lowercase())
-            .map(|c| c.as_str().to_string())
-            .collect::<Vec<String>>();
-        let role_id = RoleId(
-            role_tag.as_str()[3..role_tag.as_str().len() - 1]
-                .parse::<u64>()
-                .unwrap(),
-        );
 
-        let role_opt = role_id.to_role_cached(&ctx);
+    if args.to_lowercase() == "off" {
+        guild_data.write().await.default_channel = None;
+        guild_data.read().await.commit_changes(&pool).await;
 
-        if let Some(role) = role_opt {
-            let _ = sqlx::query!(
-                "
-DELETE FROM command_restrictions WHERE role_id = (SELECT id FROM roles WHERE role = ?)
-                ",
-                role.id.as_u64()
-            )
-            .execute(&pool)
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "defaultchannel/removed"))
             .await;
+    } else if let Some(channel_id) = REGEX_CHANNEL
+        .captures(&args)
+        .map(|cap| cap.get(1))
+        .flatten()
+        .map(|c| c.as_str().parse::<u64>().ok())
+        .flatten()
+        .map(ChannelId)
+    {
+        let channel = channel_id
+            .to_channel_cached(&ctx)
+            .map(|channel| channel.guild())
+            .flatten()
+            .filter(|channel| Some(channel.guild_id) == msg.guild_id);
+
+        let direct_delivery = guild_data.read().await.direct_delivery;
+
+        // reminders are sent through a webhook unless the guild uses direct delivery, in which
+        // case the bot only needs to be able to post
+        let can_post = channel
+            .as_ref()
+            .map(|channel| {
+                channel
+                    .permissions_for_user(&ctx, ctx.cache.current_user_id())
+                    .map(|perms| {
+                        perms.send_messages() && (direct_delivery || perms.manage_webhooks())
+                    })
+                    .unwrap_or(false)
+            })
+            .unwrap_or(false);
 
-            if commands.is_empty() {
-                let _ = msg
-                    .channel_id
-                    .say(&ctx, lm.get(&language, "restrict/disabled"))
-                    .await;
-            } else {
-                let _ = sqlx::query!(
-                    "
-INSERT IGNORE INTO roles (role, name, guild_id) VALUES (?, ?, ?)
-                    ",
-                    role.id.as_u64(),
-                    role.name,
-                    guild_data.id
+        if channel.is_none() {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "defaultchannel/invalid"))
+                .await;
+        } else if !can_post {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "defaultchannel/no_perms"))
+                .await;
+        } else {
+            guild_data.write().await.default_channel = Some(*channel_id.as_u64());
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "defaultchannel/set")
+                        .replace("{channel}", &channel_id.mention().to_string()),
                 )
-                .execute(&pool)
                 .await;
+        }
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
 
-                for command in commands {
-                    let res = sqlx::query!(
-                        "
-INSERT INTO command_restrictions (role_id, command) VALUES ((SELECT id FROM roles WHERE role = ?), ?)
-                        ", role.id.as_u64(), command)
-                        .execute(&pool)
-                        .await;
+        command_help(ctx, msg, lm, &prefix, &language, "defaultchannel").await;
+    }
+}
 
-                    if res.is_err() {
-                        println!("{:?}", res);
+/// `logchannel #channel|off`: post a copy of every reminder sent in the guild to a staff channel,
+/// with where it went and who set it. The copies are sent by the reminder sender
+#[command("logchannel")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn log_channel(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
 
-                        let content = lm.get(&language, "restrict/failure").replacen(
-                            "{command}",
-                            &command,
-                            1,
-                        );
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
 
-                        let _ = msg.channel_id.say(&ctx, content).await;
-                    }
-                }
+    if args.to_lowercase() == "off" {
+        guild_data.write().await.log_channel = None;
+        guild_data.read().await.commit_changes(&pool).await;
 
-                let _ = msg
-                    .channel_id
-                    .say(&ctx, lm.get(&language, "restrict/enabled"))
-                    .await;
-            }
-        }
-    } else if args.is_empty() {
-        let guild_id = msg.guild_id.unwrap().as_u64().to_owned();
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "logchannel/removed"))
+            .await;
+    } else if let Some(channel_id) = REGEX_CHANNEL
+        .captures(&args)
+        .map(|cap| cap.get(1))
+        .flatten()
+        .map(|c| c.as_str().parse::<u64>().ok())
+        .flatten()
+        .map(ChannelId)
+    {
+        let channel = channel_id
+            .to_channel_cached(&ctx)
+            .map(|channel| channel.guild())
+            .flatten()
+            .filter(|channel| Some(channel.guild_id) == msg.guild_id);
+
+        // the copies are posted by the bot itself as embeds, never through a webhook
+        let can_post = channel
+            .as_ref()
+            .map(|channel| {
+                channel
+                    .permissions_for_user(&ctx, ctx.cache.current_user_id())
+                    .map(|perms| perms.send_messages() && perms.embed_links())
+                    .unwrap_or(false)
+            })
+            .unwrap_or(false);
 
-        let rows = sqlx::query!(
-            "
-SELECT
-    roles.role, command_restrictions.command
-FROM
-    command_restrictions
-INNER JOIN
-    roles
+        if channel.is_none() {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "logchannel/invalid"))
+                .await;
+        } else if !can_post {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "logchannel/no_perms"))
+                .await;
+        } else {
+            guild_data.write().await.log_channel = Some(*channel_id.as_u64());
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "logchannel/set")
+                        .replace("{channel}", &channel_id.mention().to_string()),
+                )
+                .await;
+        }
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "logchannel").await;
+    }
+}
+
+/// `naturalwords <send|to|every> <word>|reset`: replace the words `natural` commands are split on,
+/// for guilds that phrase reminders differently. `naturalwords` shows the words in use
+#[command("naturalwords")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn natural_words(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    // the words currently recognised for each keyword, defaults included
+    let words = |word: &Option<String>, default: &'static str| match word {
+        Some(word) => vec![word.clone()],
+
+        None => default
+            .split('|')
+            .map(String::from)
+            .collect::<Vec<String>>(),
+    };
+
+    let args = args.to_lowercase();
+    let mut split = args.split_whitespace();
+
+    match (split.next(), split.next(), split.next()) {
+        (None, _, _) => {
+            let guild_data = guild_data.read().await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "naturalwords/current")
+                        .replace(
+                            "{send}",
+                            &words(&guild_data.natural_send, DEFAULT_NATURAL_SEND).join("/"),
+                        )
+                        .replace(
+                            "{to}",
+                            &words(&guild_data.natural_to, DEFAULT_NATURAL_TO).join("/"),
+                        )
+                        .replace(
+                            "{every}",
+                            &words(&guild_data.natural_every, DEFAULT_NATURAL_EVERY).join("/"),
+                        ),
+                )
+                .await;
+        }
+
+        (Some("reset"), None, _) => {
+            {
+                let mut guild_data = guild_data.write().await;
+
+                guild_data.natural_send = None;
+                guild_data.natural_to = None;
+                guild_data.natural_every = None;
+            }
+
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "naturalwords/reset"))
+                .await;
+        }
+
+        (Some(keyword), Some(word), None) if ["send", "to", "every"].contains(&keyword) => {
+            if word.chars().count() > 16 || !word.chars().all(char::is_alphanumeric) {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "naturalwords/invalid"))
+                    .await;
+
+                return;
+            }
+
+            let conflicts = {
+                let guild_data = guild_data.read().await;
+
+                // a word can't split a command two ways, so it has to be unused by the others
+                let others = match keyword {
+                    "send" => vec![
+                        words(&guild_data.natural_to, DEFAULT_NATURAL_TO),
+                        words(&guild_data.natural_every, DEFAULT_NATURAL_EVERY),
+                    ],
+                    "to" => vec![
+                        words(&guild_data.natural_send, DEFAULT_NATURAL_SEND),
+                        words(&guild_data.natural_every, DEFAULT_NATURAL_EVERY),
+                    ],
+                    _ => vec![
+                        words(&guild_data.natural_send, DEFAULT_NATURAL_SEND),
+                        words(&guild_data.natural_to, DEFAULT_NATURAL_TO),
+                    ],
+                };
+
+                RESERVED_NATURAL_WORDS.contains(&word)
+                    || others.iter().flatten().any(|other| other == word)
+            };
+
+            if conflicts {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(&language, "naturalwords/conflict")
+                            .replace("{word}", word),
+                    )
+                    .await;
+
+                return;
+            }
+
+            {
+                let mut guild_data = guild_data.write().await;
+
+                let setting = match keyword {
+                    "send" => &mut guild_data.natural_send,
+                    "to" => &mut guild_data.natural_to,
+                    _ => &mut guild_data.natural_every,
+                };
+
+                *setting = Some(word.to_string());
+            }
+
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "naturalwords/set")
+                        .replace("{keyword}", keyword)
+                        .replace("{word}", word),
+                )
+                .await;
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "naturalwords").await;
+        }
+    }
+}
+
+#[command("announcements")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn announcements(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let channel_id = REGEX_CHANNEL
+        .captures(&args)
+        .map(|cap| cap.get(1))
+        .flatten()
+        .map(|c| c.as_str().parse::<u64>().ok())
+        .flatten()
+        .map(ChannelId);
+
+    if args.to_lowercase() == "off" {
+        guild_data.write().await.announcements = false;
+        guild_data.read().await.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "announcements/off"))
+            .await;
+    } else if args.to_lowercase() == "on" {
+        {
+            let mut guild_data = guild_data.write().await;
+
+            guild_data.announcements = true;
+            guild_data.announcement_channel = None;
+        }
+        guild_data.read().await.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "announcements/on"))
+            .await;
+    } else if let Some(channel_id) = channel_id {
+        let in_guild = channel_id
+            .to_channel_cached(&ctx)
+            .map(|channel| channel.guild())
+            .flatten()
+            .map_or(false, |channel| Some(channel.guild_id) == msg.guild_id);
+
+        if in_guild {
+            {
+                let mut guild_data = guild_data.write().await;
+
+                guild_data.announcements = true;
+                guild_data.announcement_channel = Some(*channel_id.as_u64());
+            }
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "announcements/channel")
+                        .replace("{channel}", &channel_id.mention().to_string()),
+                )
+                .await;
+        } else {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "announcements/invalid"))
+                .await;
+        }
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "announcements").await;
+    }
+}
+
+#[command("delivery")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn delivery(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let direct_delivery = match args.to_lowercase().as_str() {
+        "webhook" => Some(false),
+        "bot" => Some(true),
+        _ => None,
+    };
+
+    match direct_delivery {
+        Some(direct_delivery) => {
+            guild_data.write().await.direct_delivery = direct_delivery;
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let response = if direct_delivery {
+                "delivery/bot"
+            } else {
+                "delivery/webhook"
+            };
+
+            let _ = msg.channel_id.say(&ctx, lm.get(&language, response)).await;
+        }
+
+        None => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "delivery").await;
+        }
+    }
+}
+
+/// `digest on|off`: post this channel's reminders in a thread for each day rather than in the
+/// channel itself
+#[command("digest")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn digest(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let thread_digest = match args.to_lowercase().as_str() {
+        "on" => Some(true),
+        "off" => Some(false),
+        _ => None,
+    };
+
+    match thread_digest {
+        Some(thread_digest) => {
+            let channel = msg.channel(&ctx).await.unwrap();
+
+            // threads can only be made in text and announcement channels
+            let supports_threads = matches!(
+                &channel,
+                Channel::Guild(channel)
+                    if channel.kind == ChannelType::Text || channel.kind == ChannelType::News
+            );
+
+            if thread_digest && !supports_threads {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "digest/unsupported"))
+                    .await;
+
+                return;
+            }
+
+            let mut channel_data = ChannelData::from_channel(channel, &pool).await.unwrap();
+
+            channel_data.thread_digest = thread_digest;
+            channel_data.commit_changes(&pool).await;
+
+            let response = if thread_digest {
+                "digest/enabled"
+            } else {
+                "digest/disabled"
+            };
+
+            let _ = msg.channel_id.say(&ctx, lm.get(&language, response)).await;
+        }
+
+        None => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "digest").await;
+        }
+    }
+}
+
+#[command("everyone")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn everyone(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await.unwrap();
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let setting = match args.to_lowercase().as_str() {
+        "off" => Some((false, false)),
+        "confirm" => Some((true, false)),
+        "restrict" => Some((true, true)),
+        _ => None,
+    };
+
+    if let Some((confirm, restrict)) = setting {
+        {
+            let mut guild_data = guild_data.write().await;
+
+            guild_data.confirm_everyone = confirm;
+            guild_data.restrict_everyone = restrict;
+        }
+
+        guild_data.read().await.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(
+                &ctx,
+                lm.get(&language, "everyone/success")
+                    .replace("{setting}", &args.to_lowercase()),
+            )
+            .await;
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "everyone").await;
+    }
+}
+
+// the commands a role can be allowed to use. The restrictions table only accepts these
+const RESTRICTABLE_COMMANDS: [&str; 9] = [
+    "todos",
+    "natural",
+    "remind",
+    "interval",
+    "timer",
+    "del",
+    "look",
+    "alias",
+    "countdown",
+];
+
+// the Discord permissions a restrictable command can be opened up to, by their names in `restrict
+// permission`. Manage Server is left out since it already allows every command
+const GATE_PERMISSIONS: [(&str, Permissions); 9] = [
+    ("administrator", Permissions::ADMINISTRATOR),
+    ("manage_channels", Permissions::MANAGE_CHANNELS),
+    ("manage_roles", Permissions::MANAGE_ROLES),
+    ("manage_webhooks", Permissions::MANAGE_WEBHOOKS),
+    ("manage_nicknames", Permissions::MANAGE_NICKNAMES),
+    ("manage_emojis", Permissions::MANAGE_EMOJIS),
+    ("kick_members", Permissions::KICK_MEMBERS),
+    ("ban_members", Permissions::BAN_MEMBERS),
+    ("mention_everyone", Permissions::MENTION_EVERYONE),
+];
+
+fn permission_names(permissions: Permissions) -> Vec<&'static str> {
+    GATE_PERMISSIONS
+        .iter()
+        .filter(|(_, permission)| permissions.contains(*permission))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// `restrict permission <command> [permissions...]`: let anyone with all of the given Discord
+/// permissions use a command, as well as the roles allowed to. No permissions removes the gate
+async fn restrict_permission(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    pool: &MySqlPool,
+    guild_id: u32,
+    args: &str,
+) {
+    let mut split = args.split_whitespace();
+
+    let command = match split.next() {
+        Some(command) => command.to_lowercase(),
+
+        None => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, language, "restrict").await;
+
+            return;
+        }
+    };
+
+    if !RESTRICTABLE_COMMANDS.contains(&command.as_str()) {
+        let _ = msg
+            .channel_id
+            .say(
+                &ctx,
+                lm.get(language, "restrict/failure")
+                    .replacen("{command}", &command, 1),
+            )
+            .await;
+
+        return;
+    }
+
+    let mut permissions = Permissions::empty();
+
+    for name in split {
+        match GATE_PERMISSIONS
+            .iter()
+            .find(|(permission_name, _)| permission_name.eq_ignore_ascii_case(name))
+        {
+            Some((_, permission)) => permissions.insert(*permission),
+
+            None => {
+                let names = GATE_PERMISSIONS
+                    .iter()
+                    .map(|(name, _)| format!("`{}`", name))
+                    .collect::<Vec<String>>();
+
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(language, "restrict/permission_invalid")
+                            .replace("{permission}", name)
+                            .replace("{permissions}", &names.join(" ")),
+                    )
+                    .await;
+
+                return;
+            }
+        }
+    }
+
+    let response = if permissions.is_empty() {
+        let _ = sqlx::query!(
+            "
+DELETE FROM command_permissions WHERE guild_id = ? AND command = ?
+            ",
+            guild_id,
+            command
+        )
+        .execute(pool)
+        .await;
+
+        lm.get(language, "restrict/permission_removed")
+            .replace("{command}", &command)
+    } else {
+        let _ = sqlx::query!(
+            "
+INSERT INTO command_permissions (guild_id, command, permissions) VALUES (?, ?, ?)
+    ON DUPLICATE KEY UPDATE permissions = ?
+            ",
+            guild_id,
+            command,
+            permissions.bits(),
+            permissions.bits()
+        )
+        .execute(pool)
+        .await;
+
+        lm.get(language, "restrict/permission_set")
+            .replace("{command}", &command)
+            .replace("{permissions}", &permission_names(permissions).join(", "))
+    };
+
+    Event::Restrict.record(pool, 1, msg.guild_id.unwrap(), msg.author.id);
+
+    let _ = msg.channel_id.say(&ctx, response).await;
+}
+
+/// `restrict show @role`: the commands one role is allowed to use
+async fn restrict_show(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    pool: &MySqlPool,
+    guild_id: u32,
+    args: &str,
+) -> CommandResult {
+    let role_id = match REGEX_ROLE.captures(args) {
+        Some(captures) => captures.get(1).unwrap().as_str().parse::<u64>().unwrap(),
+
+        None => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, language, "restrict").await;
+
+            return Ok(());
+        }
+    };
+
+    let rows = sqlx::query!(
+        "
+SELECT command_restrictions.command, command_restrictions.expires
+FROM command_restrictions
+INNER JOIN roles ON roles.id = command_restrictions.role_id
+WHERE
+    roles.role = ? AND
+    roles.guild_id = ? AND
+    (command_restrictions.expires IS NULL OR command_restrictions.expires > NOW())
+ORDER BY command_restrictions.command
+        ",
+        role_id,
+        guild_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let role = format!("<@&{}>", role_id);
+
+    if rows.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(
+                &ctx,
+                lm.get(language, "restrict/show_none")
+                    .replace("{role}", &role),
+            )
+            .await;
+    } else {
+        let commands = rows
+            .iter()
+            .map(|row| match row.expires {
+                Some(expires) => format!("`{}` (<t:{}:R>)", row.command, expires.timestamp()),
+
+                None => format!("`{}`", row.command),
+            })
+            .collect::<Vec<String>>();
+
+        let title = lm.get(language, "restrict/show_title");
+
+        let _ = msg
+            .channel_id
+            .send_message(&ctx, |m| {
+                m.embed(|e| {
+                    e.title(title)
+                        .description(format!("{}\n{}", role, commands.join("\n")))
+                        .color(*THEME_COLOR)
+                })
+            })
+            .await;
+    }
+
+    Ok(())
+}
+
+/// Replace the commands a role is allowed to use. With an `expires` timestamp, the commands are
+/// instead added until then, alongside the role's other grants. Permanent grants stay permanent.
+/// Returns the commands that couldn't be allowed
+async fn set_restrictions(
+    pool: &MySqlPool,
+    guild_id: u32,
+    role: &Role,
+    commands: &[String],
+    expires: Option<i64>,
+) -> Vec<String> {
+    if expires.is_none() || commands.is_empty() {
+        let _ = sqlx::query!(
+            "
+DELETE FROM command_restrictions WHERE role_id = (SELECT id FROM roles WHERE role = ?)
+            ",
+            role.id.as_u64()
+        )
+        .execute(pool)
+        .await;
+    }
+
+    if commands.is_empty() {
+        return vec![];
+    }
+
+    let _ = sqlx::query!(
+        "
+INSERT IGNORE INTO roles (role, name, guild_id) VALUES (?, ?, ?)
+        ",
+        role.id.as_u64(),
+        role.name,
+        guild_id
+    )
+    .execute(pool)
+    .await;
+
+    let mut failed = vec![];
+
+    for command in commands {
+        let res = sqlx::query!(
+            "
+INSERT INTO command_restrictions (role_id, command, expires)
+    VALUES ((SELECT id FROM roles WHERE role = ?), ?, FROM_UNIXTIME(?))
+    ON DUPLICATE KEY UPDATE expires = IF(expires IS NULL, NULL, VALUES(expires))
+            ",
+            role.id.as_u64(),
+            command,
+            expires
+        )
+        .execute(pool)
+        .await;
+
+        if res.is_err() {
+            failed.push(command.clone());
+        }
+    }
+
+    failed
+}
+
+/// `restrict menu`: pick a role, then the commands it may use, from select menus. Only the 25
+/// highest roles fit in a menu, so the text form is still needed for the rest
+async fn restrict_menu(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    pool: &MySqlPool,
+    guild_id: u32,
+) {
+    let guild = match msg.guild(&ctx) {
+        Some(guild) => guild,
+
+        None => return,
+    };
+
+    // @everyone shares the guild's ID, and managed roles belong to integrations
+    let mut roles = guild
+        .roles
+        .values()
+        .filter(|role| role.id.as_u64() != guild.id.as_u64() && !role.managed)
+        .collect::<Vec<&Role>>();
+
+    roles.sort_by_key(|role| std::cmp::Reverse(role.position));
+    roles.truncate(25);
+
+    if roles.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(language, "restrict/menu_no_roles"))
+            .await;
+
+        return;
+    }
+
+    let prompt = msg
+        .channel_id
+        .send_message(&ctx, |m| {
+            m.content(lm.get(language, "restrict/menu_role"))
+                .components(|c| {
+                    c.create_action_row(|row| {
+                        row.create_select_menu(|menu| {
+                            menu.custom_id("restrict:role").options(|opts| {
+                                for role in &roles {
+                                    opts.create_option(|o| {
+                                        o.label(&role.name).value(role.id.as_u64())
+                                    });
+                                }
+
+                                opts
+                            })
+                        })
+                    })
+                })
+        })
+        .await;
+
+    let prompt = match prompt {
+        Ok(prompt) => prompt,
+
+        Err(_) => return,
+    };
+
+    let role_interaction = prompt
+        .await_component_interaction(&ctx)
+        .author_id(msg.author.id)
+        .timeout(Duration::from_secs(60))
+        .await;
+
+    let selected = role_interaction.map(|interaction| {
+        let role = interaction
+            .data
+            .values
+            .first()
+            .map(|value| value.parse::<u64>().ok())
+            .flatten()
+            .map(|role_id| guild.roles.get(&RoleId(role_id)))
+            .flatten();
+
+        (interaction, role)
+    });
+
+    let (role_interaction, role) = match selected {
+        Some((interaction, Some(role))) => (interaction, role),
+
+        _ => {
+            let _ = prompt.delete(&ctx).await;
+
+            return;
+        }
+    };
+
+    let allowed = sqlx::query!(
+        "
+SELECT command FROM command_restrictions WHERE role_id = (SELECT id FROM roles WHERE role = ?)
+        ",
+        role.id.as_u64()
+    )
+    .fetch_all(pool)
+    .await
+    .map(|rows| rows.into_iter().map(|row| row.command).collect())
+    .unwrap_or_else(|_| vec![]);
+
+    let _ = role_interaction
+        .create_interaction_response(&ctx, |r| {
+            r.kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|d| {
+                    d.content(
+                        lm.get(language, "restrict/menu_commands")
+                            .replace("{role}", &role.name),
+                    )
+                    .components(|c| {
+                        c.create_action_row(|row| {
+                            row.create_select_menu(|menu| {
+                                menu.custom_id("restrict:commands")
+                                    .min_values(0)
+                                    .max_values(RESTRICTABLE_COMMANDS.len() as u64)
+                                    .options(|opts| {
+                                        for command in RESTRICTABLE_COMMANDS.iter() {
+                                            opts.create_option(|o| {
+                                                o.label(command).value(command).default_selection(
+                                                    allowed.iter().any(|c: &String| c == command),
+                                                )
+                                            });
+                                        }
+
+                                        opts
+                                    })
+                            })
+                        })
+                    })
+                })
+        })
+        .await;
+
+    let commands_interaction = prompt
+        .await_component_interaction(&ctx)
+        .author_id(msg.author.id)
+        .timeout(Duration::from_secs(60))
+        .await;
+
+    match commands_interaction {
+        Some(interaction) => {
+            let commands = &interaction.data.values;
+            let failed = set_restrictions(pool, guild_id, role, commands, None).await;
+
+            // clearing a role's commands still counts as one change
+            Event::Restrict.record(
+                pool,
+                (commands.len() - failed.len()).max(1) as u64,
+                msg.guild_id.unwrap(),
+                msg.author.id,
+            );
+
+            let mut content = lm
+                .get(
+                    language,
+                    if commands.is_empty() {
+                        "restrict/disabled"
+                    } else {
+                        "restrict/enabled"
+                    },
+                )
+                .to_string();
+
+            for command in failed {
+                content.push('\n');
+                content.push_str(&lm.get(language, "restrict/failure").replacen(
+                    "{command}",
+                    &command,
+                    1,
+                ));
+            }
+
+            let _ = interaction
+                .create_interaction_response(&ctx, |r| {
+                    r.kind(InteractionResponseType::UpdateMessage)
+                        .interaction_response_data(|d| d.content(content).components(|c| c))
+                })
+                .await;
+        }
+
+        None => {
+            let _ = prompt.delete(&ctx).await;
+        }
+    }
+}
+
+#[command]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+#[can_disable(false)]
+async fn restrict(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let guild_data = GuildData::from_id(msg.guild_id.unwrap(), None, &pool).await?;
+
+    let role_tag_match = REGEX_ROLE.find(&args);
+
+    if args == "menu" {
+        restrict_menu(ctx, msg, &lm, &language, &pool, guild_data.id).await;
+    } else if let Some(permission_args) = args
+        .strip_prefix("permission")
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        restrict_permission(
+            ctx,
+            msg,
+            &lm,
+            &language,
+            &pool,
+            guild_data.id,
+            permission_args,
+        )
+        .await;
+    } else if let Some(show_args) = args
+        .strip_prefix("show")
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    {
+        restrict_show(ctx, msg, &lm, &language, &pool, guild_data.id, show_args).await?;
+    } else if let Some(role_tag) = role_tag_match {
+        // a trailing duration like `24h` makes the grant temporary. It's taken out of the
+        // arguments first, so its unit isn't read as a command name
+        let (duration, rest): (Vec<&str>, Vec<&str>) = args
+            .split_whitespace()
+            .partition(|token| token.starts_with(|c: char| c.is_ascii_digit()));
+
+        let expires = match duration.first() {
+            Some(duration) => {
+                let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+                match TimeParser::new(duration, timezone).timestamp() {
+                    Ok(expires) if expires > Utc::now().timestamp() => Some(expires),
+
+                    _ => {
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "restrict/invalid_duration"))
+                            .await;
+
+                        return Ok(());
+                    }
+                }
+            }
+
+            None => None,
+        };
+
+        let commands = REGEX_COMMANDS
+            .find_iter(&rest.join(" ").to_lowercase())
+            .map(|c| c.as_str().to_string())
+            .collect::<Vec<String>>();
+        let role_id = RoleId(
+            role_tag.as_str()[3..role_tag.as_str().len() - 1]
+                .parse::<u64>()
+                .unwrap(),
+        );
+
+        // roles can be missing from the cache, so ask Discord before deciding the role is gone.
+        // only the guild's own roles are looked at, so other guilds' roles can't be restricted
+        let role_opt = match msg
+            .guild(&ctx)
+            .map(|guild| guild.roles.get(&role_id).cloned())
+            .flatten()
+        {
+            Some(role) => Some(role),
+
+            None => msg
+                .guild_id
+                .unwrap()
+                .roles(&ctx)
+                .await
+                .ok()
+                .map(|mut roles| roles.remove(&role_id))
+                .flatten(),
+        };
+
+        if let Some(role) = role_opt {
+            let failed = set_restrictions(&pool, guild_data.id, &role, &commands, expires).await;
+
+            // clearing a role's commands still counts as one change
+            Event::Restrict.record(
+                &pool,
+                (commands.len() - failed.len()).max(1) as u64,
+                msg.guild_id.unwrap(),
+                msg.author.id,
+            );
+
+            for command in failed {
+                let content =
+                    lm.get(&language, "restrict/failure")
+                        .replacen("{command}", &command, 1);
+
+                let _ = msg.channel_id.say(&ctx, content).await;
+            }
+
+            let response = if commands.is_empty() {
+                lm.get(&language, "restrict/disabled").to_string()
+            } else if let Some(expires) = expires {
+                lm.get(&language, "restrict/enabled_until")
+                    .replace("{time}", &format!("<t:{}:R>", expires))
+            } else {
+                lm.get(&language, "restrict/enabled").to_string()
+            };
+
+            let _ = msg.channel_id.say(&ctx, response).await;
+        } else {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "restrict/role_not_found"))
+                .await;
+        }
+    } else if args.is_empty() {
+        let guild_id = msg.guild_id.unwrap().as_u64().to_owned();
+
+        let rows = sqlx::query!(
+            "
+SELECT
+    roles.role, command_restrictions.command, command_restrictions.expires
+FROM
+    command_restrictions
+INNER JOIN
+    roles
 ON
     roles.id = command_restrictions.role_id
 WHERE
-    roles.guild_id = (SELECT id FROM guilds WHERE guild = ?)
+    roles.guild_id = (SELECT id FROM guilds WHERE guild = ?) AND
+    (command_restrictions.expires IS NULL OR command_restrictions.expires > NOW())
+            ",
+            guild_id
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let permission_rows = sqlx::query!(
+            "
+SELECT command, permissions
+FROM command_permissions
+WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?)
             ",
             guild_id
         )
@@ -490,13 +1938,32 @@ WHERE
         let mut commands_roles: HashMap<&str, Vec<String>> = HashMap::new();
 
         rows.iter().for_each(|row| {
+            let role = match row.expires {
+                Some(expires) => format!("<@&{}> (<t:{}:R>)", row.role, expires.timestamp()),
+
+                None => format!("<@&{}>", row.role),
+            };
+
             if let Some(vec) = commands_roles.get_mut(&row.command.as_str()) {
-                vec.push(format!("<@&{}>", row.role));
+                vec.push(role);
             } else {
-                commands_roles.insert(&row.command, vec![format!("<@&{}>", row.role)]);
+                commands_roles.insert(&row.command, vec![role]);
             }
         });
 
+        permission_rows.iter().for_each(|row| {
+            let permissions = permission_names(Permissions::from_bits_truncate(row.permissions))
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<String>>()
+                .join(" + ");
+
+            commands_roles
+                .entry(&row.command)
+                .or_insert_with(Vec::new)
+                .push(permissions);
+        });
+
         let fields = commands_roles
             .iter()
             .map(|(key, value)| (key.to_title_case(), value.join("\n"), true));
@@ -514,6 +1981,362 @@ WHERE
 
         command_help(ctx, msg, lm, &prefix, &language, "restrict").await;
     }
+
+    Ok(())
+}
+
+/// Events shown on each page of `audit`
+const AUDIT_PAGE_SIZE: i64 = 10;
+
+/// `audit [page]`: the guild's recorded bulk reminder changes and restriction changes, newest
+/// first, with who made them and when
+#[command("audit")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn audit(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let guild_id = msg.guild_id.unwrap().as_u64().to_owned();
+
+    let total = sqlx::query!(
+        "
+SELECT COUNT(1) AS count FROM events WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?)
+        ",
+        guild_id
+    )
+    .fetch_one(&pool)
+    .await?
+    .count;
+
+    if total == 0 {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "audit/empty"))
+            .await;
+
+        return Ok(());
+    }
+
+    let pages = (total + AUDIT_PAGE_SIZE - 1) / AUDIT_PAGE_SIZE;
+
+    let page = if args.is_empty() {
+        1
+    } else {
+        match args.parse::<i64>() {
+            Ok(page) if page >= 1 && page <= pages => page,
+
+            _ => {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(&language, "audit/invalid_page")
+                            .replace("{pages}", &pages.to_string()),
+                    )
+                    .await;
+
+                return Ok(());
+            }
+        }
+    };
+
+    let rows = sqlx::query!(
+        "
+SELECT events.event_name, events.bulk_count, events.time, users.user
+FROM events
+LEFT JOIN users ON users.id = events.user_id
+WHERE events.guild_id = (SELECT id FROM guilds WHERE guild = ?)
+ORDER BY events.id DESC
+LIMIT ? OFFSET ?
+        ",
+        guild_id,
+        AUDIT_PAGE_SIZE,
+        (page - 1) * AUDIT_PAGE_SIZE
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let lines = rows
+        .iter()
+        .map(|row| {
+            let actor = match row.user {
+                Some(user) => format!("<@{}>", user),
+
+                None => lm.get(&language, "audit/unknown_user").to_string(),
+            };
+
+            let action = lm
+                .get(&language, &format!("audit/{}", row.event_name))
+                .replace("{count}", &row.bulk_count.unwrap_or(1).to_string());
+
+            format!("<t:{}:f> {} {}", row.time.timestamp(), actor, action)
+        })
+        .collect::<Vec<String>>();
+
+    let title = lm.get(&language, "audit/title");
+    let footer = lm
+        .get(&language, "audit/page")
+        .replace("{page}", &page.to_string())
+        .replace("{pages}", &pages.to_string());
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                e.title(title)
+                    .description(lines.join("\n"))
+                    .footer(|f| f.text(footer))
+                    .color(*THEME_COLOR)
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command("disable")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+#[can_blacklist(false)]
+#[can_disable(false)]
+async fn disable(ctx: &Context, msg: &Message, args: String) {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let guild_id = msg.guild_id.unwrap().as_u64().to_owned();
+
+    if args.is_empty() {
+        let disabled = sqlx::query!(
+            "
+SELECT command FROM disabled_commands WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?)
+            ",
+            guild_id
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|row| format!("`{}`", row.command))
+        .collect::<Vec<String>>();
+
+        let content = if disabled.is_empty() {
+            lm.get(&language, "disable/none").to_string()
+        } else {
+            lm.get(&language, "disable/list")
+                .replace("{commands}", &disabled.join(" "))
+        };
+
+        let _ = msg.channel_id.say(&ctx, content).await;
+    } else {
+        let framework = ctx
+            .data
+            .read()
+            .await
+            .get::<FrameworkCtx>()
+            .cloned()
+            .expect("Could not get FrameworkCtx from data");
+
+        let mut lines = vec![];
+
+        for name in args.to_lowercase().split_whitespace() {
+            // aliases of a command resolve to the same name, so disabling one disables them all
+            let line = match framework.commands.get(name) {
+                Some(command) if !command.can_disable => lm
+                    .get(&language, "disable/not_allowed")
+                    .replace("{command}", command.name),
+
+                Some(command) => {
+                    let removed = sqlx::query!(
+                        "
+DELETE FROM disabled_commands WHERE command = ? AND guild_id = (SELECT id FROM guilds WHERE guild = ?)
+                        ",
+                        command.name,
+                        guild_id
+                    )
+                    .execute(&pool)
+                    .await
+                    .unwrap()
+                    .rows_affected();
+
+                    if removed > 0 {
+                        lm.get(&language, "disable/enabled")
+                            .replace("{command}", command.name)
+                    } else {
+                        sqlx::query!(
+                            "
+INSERT INTO disabled_commands (guild_id, command) VALUES ((SELECT id FROM guilds WHERE guild = ?), ?)
+                            ",
+                            guild_id,
+                            command.name
+                        )
+                        .execute(&pool)
+                        .await
+                        .unwrap();
+
+                        lm.get(&language, "disable/disabled")
+                            .replace("{command}", command.name)
+                    }
+                }
+
+                None => lm
+                    .get(&language, "disable/unknown")
+                    .replace("{command}", name),
+            };
+
+            lines.push(line);
+        }
+
+        let _ = msg.channel_id.say_lines(&ctx, lines.into_iter()).await;
+    }
+}
+
+#[command("settings")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+#[can_disable(false)]
+async fn settings(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let guild = msg.guild(&ctx).unwrap();
+    let guild_data = ctx.guild_data(guild.id).await?;
+
+    match args.trim().to_lowercase().as_str() {
+        "export" => {
+            let settings = GuildSettings::from_guild(&*guild_data.read().await, &pool).await?;
+            let data = serde_json::to_vec_pretty(&settings)
+                .map_err(|e| CommandError::Other(Box::new(e)))?;
+
+            let _ = msg
+                .channel_id
+                .send_message(&ctx, |m| {
+                    m.content(lm.get(&language, "settings/exported")).add_file(
+                        AttachmentType::Bytes {
+                            data: data.into(),
+                            filename: format!("settings-{}.json", guild.id),
+                        },
+                    )
+                })
+                .await;
+        }
+
+        "import" => {
+            let attachment = match msg.attachments.get(0) {
+                Some(attachment) => attachment,
+
+                None => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "settings/no_attachment"))
+                        .await;
+
+                    return Ok(());
+                }
+            };
+
+            let settings = match attachment.download().await.map(|bytes| {
+                serde_json::from_slice::<GuildSettings>(&bytes).map_err(|e| e.to_string())
+            }) {
+                Ok(Ok(settings)) => settings,
+
+                Ok(Err(e)) => {
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&language, "settings/invalid").replace("{error}", &e),
+                        )
+                        .await;
+
+                    return Ok(());
+                }
+
+                Err(_) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "settings/download_failed"))
+                        .await;
+
+                    return Ok(());
+                }
+            };
+
+            if !await_confirmation(
+                ctx,
+                msg,
+                &lm,
+                &language,
+                lm.get(&language, "settings/confirm"),
+            )
+            .await
+            {
+                return Ok(());
+            }
+
+            let framework = ctx
+                .data
+                .read()
+                .await
+                .get::<FrameworkCtx>()
+                .cloned()
+                .expect("Could not get FrameworkCtx from data");
+
+            // disabled commands are stored by name, so aliases of a command aren't accepted
+            let can_disable = |name: &str| {
+                framework
+                    .commands
+                    .get(name)
+                    .map_or(false, |command| command.can_disable && command.name == name)
+            };
+
+            let conflicts = {
+                let mut guild_data = guild_data.write().await;
+
+                match settings
+                    .apply(&guild, &mut guild_data, &pool, can_disable)
+                    .await
+                {
+                    Ok(conflicts) => {
+                        guild_data.commit_changes(&pool).await;
+
+                        conflicts
+                    }
+
+                    Err(e) => {
+                        warn!("Couldn't import settings into {}: {:?}", guild.id, e);
+
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "settings/import_failed"))
+                            .await;
+
+                        return Ok(());
+                    }
+                }
+            };
+
+            let lines = iter::once(lm.get(&language, "settings/imported").to_string()).chain(
+                conflicts.iter().map(|conflict| {
+                    lm.get(&language, conflict.key())
+                        .replace("{name}", conflict.name())
+                }),
+            );
+
+            let _ = msg.channel_id.say_lines(&ctx, lines).await;
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "settings").await;
+        }
+    }
+
+    Ok(())
 }
 
 #[command("alias")]
@@ -629,7 +2452,25 @@ SELECT command FROM command_aliases WHERE guild_id = (SELECT id FROM guilds WHER
                         },
 
                         Err(_) => {
-                            let content = lm.get(&language, "alias/not_found").replace("{name}", name);
+                            // only looked for on a miss, so exact matches stay a single query
+                            let suggestion = sqlx::query!(
+                                "
+SELECT name FROM command_aliases WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?)
+                                ", guild_id)
+                                .fetch_all(&pool)
+                                .await
+                                .unwrap_or_default()
+                                .into_iter()
+                                .map(|row| row.name)
+                                .find(|alias| levenshtein(alias, name) == 1);
+
+                            let content = match suggestion {
+                                Some(suggestion) => lm.get(&language, "alias/suggest")
+                                    .replace("{name}", name)
+                                    .replace("{suggestion}", &suggestion),
+
+                                None => lm.get(&language, "alias/not_found").replace("{name}", name),
+                            };
 
                             let _ = msg.channel_id.say(&ctx, content).await;
                         },
@@ -0,0 +1,535 @@
+use regex_command_attr::command;
+
+use serenity::{
+    client::Context,
+    http::{AttachmentType, HttpError, StatusCode},
+    model::{
+        channel::Message,
+        id::{ChannelId, GuildId},
+    },
+    Error,
+};
+
+use crate::{
+    backoff::with_backoff,
+    consts::{BROADCAST_DELAY, OWNER_IDS, REGEX_USER},
+    deletion_log::{content_hash, DeletionLog},
+    framework::SendIterator,
+    models::{
+        reminder_dump::{ImportOutcome, ReminderDump},
+        CtxGuildData,
+    },
+    SQLPool,
+};
+
+use log::warn;
+
+use std::{collections::BTreeMap, time::Duration};
+
+use tokio::time::sleep;
+
+// how many guilds or reminders are processed between updates of the progress message
+const PROGRESS_INTERVAL: usize = 50;
+
+// how many deletions are shown by a single search
+const DELETED_LIMIT: usize = 10;
+
+// Discord's error code for a channel that doesn't exist
+const UNKNOWN_CHANNEL: isize = 10003;
+
+#[command]
+#[can_blacklist(false)]
+async fn broadcast(ctx: &Context, msg: &Message, args: String) {
+    if !OWNER_IDS.contains(msg.author.id.as_u64()) {
+        return;
+    }
+
+    if args.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, "Usage: `broadcast <message>`")
+            .await;
+
+        return;
+    }
+
+    let guilds = ctx.cache.guilds();
+    let total = guilds.len();
+
+    let mut progress = match msg
+        .channel_id
+        .say(&ctx, format!("Broadcasting to {} guilds...", total))
+        .await
+    {
+        Ok(progress) => progress,
+
+        Err(_) => return,
+    };
+
+    let (mut sent, mut skipped, mut failed) = (0, 0, 0);
+
+    for (count, guild_id) in guilds.into_iter().enumerate() {
+        // unavailable guilds are listed but not cached
+        let guild = match guild_id.to_guild_cached(&ctx) {
+            Some(guild) => guild,
+
+            None => {
+                skipped += 1;
+
+                continue;
+            }
+        };
+
+        let target = match ctx.guild_data(guild_id).await {
+            Ok(guild_data) => {
+                let guild_data = guild_data.read().await;
+
+                if guild_data.announcements {
+                    guild_data
+                        .announcement_channel
+                        .map(Into::into)
+                        .or(guild.system_channel_id)
+                } else {
+                    None
+                }
+            }
+
+            Err(_) => None,
+        };
+
+        match target {
+            Some(channel_id) => {
+                let res = with_backoff("Sending broadcast", || channel_id.say(&ctx, &args)).await;
+
+                if res.is_ok() {
+                    sent += 1;
+                } else {
+                    failed += 1;
+                }
+
+                sleep(Duration::from_millis(*BROADCAST_DELAY)).await;
+            }
+
+            None => skipped += 1,
+        }
+
+        if (count + 1) % PROGRESS_INTERVAL == 0 {
+            let _ = progress
+                .edit(&ctx, |m| {
+                    m.content(format!("Broadcasting... {}/{} guilds", count + 1, total))
+                })
+                .await;
+        }
+    }
+
+    let _ = progress
+        .edit(&ctx, |m| {
+            m.content(format!(
+                "Broadcast finished: {} sent, {} skipped, {} failed",
+                sent, skipped, failed
+            ))
+        })
+        .await;
+}
+
+#[command]
+#[can_blacklist(false)]
+async fn deleted(ctx: &Context, msg: &Message, args: String) {
+    if !OWNER_IDS.contains(msg.author.id.as_u64()) {
+        return;
+    }
+
+    let deletion_log = match ctx.data.read().await.get::<DeletionLog>().cloned() {
+        Some(deletion_log) => deletion_log,
+
+        None => return,
+    };
+
+    let args = args.trim();
+
+    let entries = if args.is_empty() {
+        deletion_log.search(|_| true, DELETED_LIMIT)
+    } else if let Some(content) = args.strip_prefix("hash ") {
+        let hash = content_hash(content.trim());
+
+        deletion_log.search(|entry| entry.content_hash == hash, DELETED_LIMIT)
+    } else if let Some(user) = REGEX_USER
+        .captures(args)
+        .map(|caps| caps.get(1).unwrap().as_str())
+        .or_else(|| Some(args).filter(|args| args.chars().all(|c| c.is_digit(10))))
+        .and_then(|user| user.parse::<u64>().ok())
+    {
+        deletion_log.search(|entry| *entry.deleted_by.as_u64() == user, DELETED_LIMIT)
+    } else {
+        deletion_log.search(|entry| entry.uid == args, DELETED_LIMIT)
+    };
+
+    if entries.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(
+                &ctx,
+                "No matching deletions. Usage: `deleted [<uid> | <user> | hash <content>]`",
+            )
+            .await;
+    } else {
+        let display = entries.iter().map(|entry| {
+            format!(
+                "`{}` (hash `{:x}`) deleted by <@{}> <t:{}:R>",
+                entry.uid,
+                entry.content_hash,
+                entry.deleted_by,
+                entry.deleted_at.timestamp()
+            )
+        });
+
+        let _ = msg.channel_id.say_lines(&ctx, display).await;
+    }
+}
+
+/// `migrate export [guild id]` dumps reminders to a JSON file, and `migrate import` with that file
+/// attached recreates them, for moving reminders between instances
+#[command]
+#[can_blacklist(false)]
+async fn migrate(ctx: &Context, msg: &Message, args: String) {
+    if !OWNER_IDS.contains(msg.author.id.as_u64()) {
+        return;
+    }
+
+    let pool = ctx
+        .data
+        .read()
+        .await
+        .get::<SQLPool>()
+        .cloned()
+        .expect("Could not get SQLPool from data");
+
+    let mut split = args.split_whitespace();
+
+    match (split.next(), split.next()) {
+        (Some("export"), guild_id) => {
+            let guild_id = match guild_id.map(|guild_id| guild_id.parse::<u64>()) {
+                Some(Ok(guild_id)) => Some(GuildId(guild_id)),
+
+                Some(Err(_)) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, "Usage: `migrate export [guild id]`")
+                        .await;
+
+                    return;
+                }
+
+                None => None,
+            };
+
+            let dump = match ReminderDump::export(guild_id, &pool).await {
+                Ok(dump) => dump,
+
+                Err(e) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, format!("Export failed: {}", e))
+                        .await;
+
+                    return;
+                }
+            };
+
+            let filename = match guild_id {
+                Some(guild_id) => format!("reminders-{}.json", guild_id),
+
+                None => "reminders.json".to_string(),
+            };
+
+            let res = msg
+                .channel_id
+                .send_message(&ctx, |m| {
+                    m.content(format!("Exported {} reminders", dump.reminders.len()))
+                        .add_file(AttachmentType::Bytes {
+                            data: serde_json::to_vec(&dump).unwrap().into(),
+                            filename,
+                        })
+                })
+                .await;
+
+            // most likely too large to upload
+            if let Err(e) = res {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        format!(
+                            "Couldn't send the export ({}). Try exporting a single guild",
+                            e
+                        ),
+                    )
+                    .await;
+            }
+        }
+
+        (Some("import"), None) => {
+            let dump = match msg.attachments.get(0) {
+                Some(attachment) => match attachment.download().await {
+                    Ok(bytes) => serde_json::from_slice::<ReminderDump>(&bytes)
+                        .map_err(|e| format!("Invalid dump: {}", e)),
+
+                    Err(_) => Err("Couldn't download the dump".to_string()),
+                },
+
+                None => Err("Attach a dump made by `migrate export`".to_string()),
+            };
+
+            let dump = match dump {
+                Ok(dump) => dump,
+
+                Err(e) => {
+                    let _ = msg.channel_id.say(&ctx, e).await;
+
+                    return;
+                }
+            };
+
+            let total = dump.reminders.len();
+
+            let mut progress = match msg
+                .channel_id
+                .say(&ctx, format!("Importing {} reminders...", total))
+                .await
+            {
+                Ok(progress) => progress,
+
+                Err(_) => return,
+            };
+
+            let (mut imported, mut existing) = (0, 0);
+            let mut conflicts = vec![];
+
+            for (count, reminder) in dump.reminders.iter().enumerate() {
+                match reminder.import(&ctx, &pool, &dump.embed_fields).await {
+                    Ok(ImportOutcome::Imported) => imported += 1,
+
+                    Ok(ImportOutcome::Exists) => existing += 1,
+
+                    Ok(outcome) => {
+                        conflicts.push(format!("`{}`: {}", reminder.uid, outcome.describe()))
+                    }
+
+                    Err(e) => conflicts.push(format!("`{}`: {}", reminder.uid, e)),
+                }
+
+                if (count + 1) % PROGRESS_INTERVAL == 0 {
+                    let _ = progress
+                        .edit(&ctx, |m| {
+                            m.content(format!("Importing... {}/{} reminders", count + 1, total))
+                        })
+                        .await;
+                }
+            }
+
+            let _ = progress
+                .edit(&ctx, |m| {
+                    m.content(format!(
+                        "Import finished: {} imported, {} already present, {} skipped. Running \
+                        the import again retries the skipped reminders",
+                        imported,
+                        existing,
+                        conflicts.len()
+                    ))
+                })
+                .await;
+
+            let _ = msg.channel_id.say_lines(&ctx, conflicts.into_iter()).await;
+        }
+
+        _ => {
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    "Usage: `migrate export [guild id]` or `migrate import`",
+                )
+                .await;
+        }
+    }
+}
+
+/// Whether a request failed because the channel has been deleted, rather than because the bot
+/// can't see it or Discord had a problem
+fn is_unknown_channel(error: &Error) -> bool {
+    match error {
+        Error::Http(http_error) => match http_error.as_ref() {
+            HttpError::UnsuccessfulRequest(response) => {
+                response.status_code == StatusCode::NOT_FOUND
+                    && response.error.code == UNKNOWN_CHANNEL
+            }
+
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+/// `orphans [disable|delete]` finds guild reminders whose channel has been deleted, and reports
+/// them per guild. With `disable` or `delete` it also turns them off or removes them. Channels
+/// missing from the cache are only counted as gone once Discord says they don't exist
+#[command]
+#[can_blacklist(false)]
+async fn orphans(ctx: &Context, msg: &Message, args: String) {
+    if !OWNER_IDS.contains(msg.author.id.as_u64()) {
+        return;
+    }
+
+    let action = match args.trim() {
+        "" => None,
+
+        "disable" => Some("disable"),
+
+        "delete" => Some("delete"),
+
+        _ => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, "Usage: `orphans [disable|delete]`")
+                .await;
+
+            return;
+        }
+    };
+
+    let pool = ctx
+        .data
+        .read()
+        .await
+        .get::<SQLPool>()
+        .cloned()
+        .expect("Could not get SQLPool from data");
+
+    let rows = match sqlx::query!(
+        "
+SELECT channels.channel, guilds.guild, COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+INNER JOIN guilds ON guilds.id = channels.guild_id
+GROUP BY channels.channel, guilds.guild
+        "
+    )
+    .fetch_all(&pool)
+    .await
+    {
+        Ok(rows) => rows,
+
+        Err(e) => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, format!("Scan failed: {}", e))
+                .await;
+
+            return;
+        }
+    };
+
+    let _ = msg
+        .channel_id
+        .say(&ctx, format!("Checking {} channels...", rows.len()))
+        .await;
+
+    // guild ID to the number of missing channels and the reminders in them
+    let mut missing = BTreeMap::new();
+    let (mut unconfirmed, mut affected) = (0, 0);
+
+    for row in rows {
+        let channel_id = ChannelId(row.channel);
+
+        if channel_id.to_channel_cached(&ctx).is_some() {
+            continue;
+        }
+
+        match with_backoff("Checking channel", || channel_id.to_channel(&ctx)).await {
+            Ok(_) => continue,
+
+            Err(e) if is_unknown_channel(&e) => {}
+
+            Err(_) => {
+                unconfirmed += 1;
+
+                continue;
+            }
+        }
+
+        let entry = missing.entry(row.guild).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += row.count;
+
+        let action = match action {
+            Some(action) => action,
+
+            None => continue,
+        };
+
+        let res = if action == "disable" {
+            sqlx::query!(
+                "
+UPDATE reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+SET reminders.enabled = 0
+WHERE channels.channel = ?
+                ",
+                row.channel
+            )
+            .execute(&pool)
+            .await
+            .map(|result| result.rows_affected())
+        } else {
+            sqlx::query!(
+                "
+DELETE reminders FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE channels.channel = ?
+                ",
+                row.channel
+            )
+            .execute(&pool)
+            .await
+            .map(|result| result.rows_affected())
+        };
+
+        match res {
+            Ok(count) => affected += count,
+
+            Err(e) => warn!("Failed to {} reminders in {}: {:?}", action, row.channel, e),
+        }
+    }
+
+    let total = missing.values().map(|(_, count)| count).sum::<i64>();
+
+    let summary = match action {
+        Some(action) => format!(
+            "{} reminders in {} deleted channels, {}d {}. {} channels couldn't be checked and \
+            were left alone",
+            total,
+            missing.values().map(|(channels, _)| channels).sum::<i64>(),
+            action,
+            affected,
+            unconfirmed
+        ),
+
+        None => format!(
+            "{} reminders in {} deleted channels. {} channels couldn't be checked. Run \
+            `orphans disable` or `orphans delete` to act on them",
+            total,
+            missing.values().map(|(channels, _)| channels).sum::<i64>(),
+            unconfirmed
+        ),
+    };
+
+    let lines = std::iter::once(summary).chain(missing.iter().map(|(guild, (channels, count))| {
+        format!(
+            "`{}`: {} reminders in {} deleted channels",
+            guild, count, channels
+        )
+    }));
+
+    let _ = msg.channel_id.say_lines(&ctx, lines).await;
+}
@@ -2,38 +2,59 @@ use regex_command_attr::command;
 
 use serenity::{
     client::Context,
-    http::CacheHttp,
+    http::{CacheHttp, HttpError},
     model::{
         channel::Message,
-        channel::{Channel, GuildChannel},
+        channel::{Channel, ChannelType, GuildChannel},
         guild::Guild,
-        id::{ChannelId, GuildId, UserId},
+        id::{ChannelId, GuildId, MessageId, UserId},
+        interactions::{message_component::ButtonStyle, InteractionResponseType},
         misc::Mentionable,
         webhook::Webhook,
     },
-    Result as SerenityResult,
+    Error as SerenityError, Result as SerenityResult,
 };
 
 use crate::{
-    check_subscription_on_message, command_help,
+    backoff::with_backoff,
+    check_subscription, check_subscription_on_message, command_help,
     consts::{
-        CHARACTERS, MAX_TIME, MIN_INTERVAL, REGEX_CHANNEL_USER, REGEX_CONTENT_SUBSTITUTION,
-        REGEX_NATURAL_COMMAND_1, REGEX_NATURAL_COMMAND_2, REGEX_REMIND_COMMAND, THEME_COLOR,
+        CHARACTERS, DUPLICATE_WINDOW, EMBED_DESCRIPTION_LIMIT, EMBED_FOOTER_LIMIT,
+        IDEMPOTENCY_WINDOW, KEYWORDS_ENABLED, MAX_CONTENT_LENGTH, MAX_GUILD_INTERVALS,
+        MAX_GUILD_INTERVALS_SUBSCRIBED, MAX_KEYWORDS, MAX_REMINDER_TARGETS, MAX_TEMPLATES,
+        MAX_TIME, MAX_USER_REMINDERS, MESSAGE_CONTENT_LIMIT, MIN_INTERVAL, PAST_TIME_GRACE,
+        PRESENCE_ENABLED, REGEX_CHANNEL, REGEX_CHANNEL_USER, REGEX_CONTENT_SUBSTITUTION,
+        REGEX_MESSAGE_LINK, REGEX_NATURAL_COMMAND_1, REGEX_NATURAL_COMMAND_2, REGEX_ONLINE_COMMAND,
+        REGEX_REMIND_COMMAND, REGEX_TEMPLATE_COMMAND, REGEX_TEMPLATE_EMBED_COMMAND, THEME_COLOR,
     },
-    framework::SendIterator,
+    deletion_log::DeletionLog,
+    framework::{CommandResult, SendIterator},
     get_ctx_data,
+    language_manager::LanguageManager,
+    metrics::Metrics,
     models::{
         channel_data::ChannelData,
+        event::Event,
         guild_data::GuildData,
-        reminder::{LookFlags, Reminder},
+        keyword_trigger::KeywordTrigger,
+        presence_reminder::PresenceReminder,
+        reminder::{
+            displacement_units, longhand_displacement, render_reminder, LookFlags, Reminder,
+        },
+        template::{
+            Template, TEMPLATE_CONTENT_LIMIT, TEMPLATE_DESCRIPTION_LIMIT, TEMPLATE_TITLE_LIMIT,
+        },
         timer::Timer,
         user_data::UserData,
         CtxGuildData,
     },
-    time_parser::{natural_parser, TimeParser},
+    slow_query::TimedQuery,
+    time_parser::{natural_interval, natural_parser, NaturalInterval, TimeParser},
+    LastNatural,
 };
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
 
 use rand::{rngs::OsRng, seq::IteratorRandom};
 
@@ -41,6 +62,8 @@ use sqlx::MySqlPool;
 
 use num_integer::Integer;
 
+use log::warn;
+
 use std::{
     collections::HashSet,
     convert::TryInto,
@@ -48,10 +71,13 @@ use std::{
     env,
     fmt::Display,
     string::ToString,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use regex::Captures;
+use regex::{Captures, Regex};
+
+// how long a `natural` input can be repeated with `natural again`
+const LAST_NATURAL_TTL: Duration = Duration::from_secs(15 * 60);
 
 async fn create_webhook(
     ctx: impl CacheHttp,
@@ -77,18 +103,161 @@ async fn create_webhook(
         .await
 }
 
+/// A webhook the bot already made for reminders in the channel, found when the stored one has been
+/// lost. Reusing it keeps the channel clear of duplicates and under Discord's webhook limit
+async fn reusable_webhook(ctx: &Context, channel: &GuildChannel) -> Option<Webhook> {
+    let current_user_id = ctx.cache.current_user_id();
+
+    channel
+        .webhooks(&ctx)
+        .await
+        .ok()?
+        .into_iter()
+        .find(|webhook| {
+            webhook.token.is_some()
+                && webhook.name.as_deref() == Some("Reminder")
+                && webhook.user.as_ref().map(|user| user.id) == Some(current_user_id)
+        })
+}
+
+/// Give a channel the webhook reminders are sent through, unless its guild delivers reminders
+/// directly
+async fn ensure_webhook(
+    ctx: &Context,
+    pool: &MySqlPool,
+    channel_data: &mut ChannelData,
+    guild_channel: GuildChannel,
+) -> Result<(), ReminderError> {
+    let direct_delivery = match ctx.guild_data(guild_channel.guild_id).await {
+        Ok(guild_data) => guild_data.read().await.direct_delivery,
+
+        Err(_) => false,
+    };
+
+    if !direct_delivery
+        && (channel_data.webhook_token.is_none() || channel_data.webhook_id.is_none())
+    {
+        let webhook = match reusable_webhook(ctx, &guild_channel).await {
+            Some(webhook) => Ok(webhook),
+
+            None => {
+                with_backoff("Creating webhook", || {
+                    create_webhook(&ctx, guild_channel.clone(), "Reminder")
+                })
+                .await
+            }
+        };
+
+        match webhook {
+            Ok(webhook) => {
+                channel_data.webhook_id = Some(webhook.id.as_u64().to_owned());
+                channel_data.webhook_token = webhook.token;
+
+                channel_data.commit_changes(&pool).await;
+            }
+
+            Err(e) => {
+                return Err(ReminderError::DiscordError(e.to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[command("webhook")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn webhook(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let channel = match msg.channel(&ctx).await {
+        Ok(channel) => channel,
+
+        Err(_) => return Ok(()),
+    };
+
+    let mut channel_data = ChannelData::from_channel(channel.clone(), &pool).await?;
+
+    // only the webhook's ID is ever shown. The token is as good as a password
+    let status = match (channel_data.webhook_id, channel_data.webhook_token.as_ref()) {
+        (Some(webhook_id), Some(token)) => {
+            match ctx.http.get_webhook_with_token(webhook_id, token).await {
+                Ok(_) => lm
+                    .get(&language, "webhook/ok")
+                    .replace("{id}", &webhook_id.to_string()),
+
+                // request errors can include the URL, which contains the token
+                Err(_) => lm
+                    .get(&language, "webhook/unreachable")
+                    .replace("{id}", &webhook_id.to_string()),
+            }
+        }
+
+        _ => lm.get(&language, "webhook/none").to_string(),
+    };
+
+    let prompt = format!("{}\n{}", status, lm.get(&language, "webhook/recreate"));
+
+    if await_confirmation(ctx, msg, &lm, &language, &prompt).await {
+        if let Some(guild_channel) = channel.guild() {
+            match with_backoff("Creating webhook", || {
+                create_webhook(&ctx, guild_channel.clone(), "Reminder")
+            })
+            .await
+            {
+                Ok(webhook) => {
+                    // the old webhook may already be gone, which is why it's being recreated
+                    if let (Some(webhook_id), Some(token)) =
+                        (channel_data.webhook_id, channel_data.webhook_token.as_ref())
+                    {
+                        let _ = ctx.http.delete_webhook_with_token(webhook_id, token).await;
+                    }
+
+                    channel_data.webhook_id = Some(webhook.id.as_u64().to_owned());
+                    channel_data.webhook_token = webhook.token;
+
+                    channel_data.commit_changes(&pool).await;
+
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&language, "webhook/recreated")
+                                .replace("{id}", &webhook.id.to_string()),
+                        )
+                        .await;
+                }
+
+                Err(e) => {
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&language, "remind/generic_error")
+                                .replace("{error}", &e.to_string()),
+                        )
+                        .await;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[command]
 #[supports_dm(false)]
 #[permission_level(Restricted)]
-async fn pause(ctx: &Context, msg: &Message, args: String) {
+async fn pause(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let language = UserData::language_of(&msg.author, &pool).await;
     let timezone = UserData::timezone_of(&msg.author, &pool).await;
 
-    let mut channel = ChannelData::from_channel(msg.channel(&ctx).await.unwrap(), &pool)
-        .await
-        .unwrap();
+    let mut channel = ChannelData::from_channel(msg.channel(&ctx).await?, &pool).await?;
 
     if args.is_empty() {
         channel.paused = !channel.paused;
@@ -135,14 +304,200 @@ async fn pause(ctx: &Context, msg: &Message, args: String) {
             }
         }
     }
+
+    Ok(())
+}
+
+/// `pause-mine [time]`: turn off every reminder the user set, in any channel, until `resume-mine`
+/// or the time given. Reminders that were already off are left alone
+#[command("pause-mine")]
+async fn pause_mine(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    let resume_at = if args.is_empty() {
+        None
+    } else {
+        match TimeParser::new(&args, timezone).timestamp() {
+            Ok(timestamp) => Some(timestamp),
+
+            Err(_) => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "pause/invalid_time"))
+                    .await;
+
+                return Ok(());
+            }
+        }
+    };
+
+    let user_id = msg.author.id.as_u64().to_owned();
+
+    // pausing again moves the end of any pause already in place
+    sqlx::query!(
+        "
+UPDATE paused_reminders
+INNER JOIN reminders ON reminders.id = paused_reminders.reminder_id
+SET paused_reminders.resume_at = FROM_UNIXTIME(?)
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?)
+        ",
+        resume_at,
+        user_id
+    )
+    .execute(&pool)
+    .await?;
+
+    // counted before pausing, so each guild's audit events cover only what this pause turned off
+    let guild_counts = sqlx::query!(
+        "
+SELECT guilds.guild, COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+INNER JOIN guilds ON guilds.id = channels.guild_id
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?) AND reminders.enabled = 1
+GROUP BY guilds.guild
+        ",
+        user_id
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    // a reminder turned back on by hand while paused still has its row, which the update above
+    // has already moved to this pause's end
+    sqlx::query!(
+        "
+INSERT IGNORE INTO paused_reminders (reminder_id, resume_at)
+SELECT id, FROM_UNIXTIME(?)
+FROM reminders
+WHERE set_by = (SELECT id FROM users WHERE user = ?) AND enabled = 1
+        ",
+        resume_at,
+        user_id
+    )
+    .execute(&pool)
+    .timed("pause user reminders")
+    .await?;
+
+    // only the reminders this pause turned off, not those paused before
+    let count = sqlx::query!(
+        "
+UPDATE reminders
+INNER JOIN paused_reminders ON paused_reminders.reminder_id = reminders.id
+SET reminders.enabled = 0
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?) AND reminders.enabled = 1
+        ",
+        user_id
+    )
+    .execute(&pool)
+    .await?
+    .rows_affected();
+
+    for row in guild_counts {
+        Event::Disable.record(&pool, row.count as u64, GuildId(row.guild), msg.author.id);
+    }
+
+    let content = match resume_at {
+        Some(timestamp) => lm
+            .get(&language, "pause_mine/paused_until")
+            .replace("{count}", &count.to_string())
+            .replace("{time}", &format!("<t:{}:f>", timestamp)),
+
+        None => lm
+            .get(&language, "pause_mine/paused")
+            .replace("{count}", &count.to_string()),
+    };
+
+    let _ = msg.channel_id.say(&ctx, content).await;
+
+    Ok(())
+}
+
+/// `resume-mine`: turn back on the reminders `pause-mine` turned off
+#[command("resume-mine")]
+async fn resume_mine(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let user_id = msg.author.id.as_u64().to_owned();
+
+    let guild_counts = sqlx::query!(
+        "
+SELECT guilds.guild, COUNT(1) AS count
+FROM paused_reminders
+INNER JOIN reminders ON reminders.id = paused_reminders.reminder_id
+INNER JOIN channels ON channels.id = reminders.channel_id
+INNER JOIN guilds ON guilds.id = channels.guild_id
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?)
+GROUP BY guilds.guild
+        ",
+        user_id
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    let count = sqlx::query!(
+        "
+UPDATE reminders
+INNER JOIN paused_reminders ON paused_reminders.reminder_id = reminders.id
+SET reminders.enabled = 1
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?)
+        ",
+        user_id
+    )
+    .execute(&pool)
+    .timed("resume user reminders")
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        "
+DELETE paused_reminders
+FROM paused_reminders
+INNER JOIN reminders ON reminders.id = paused_reminders.reminder_id
+WHERE reminders.set_by = (SELECT id FROM users WHERE user = ?)
+        ",
+        user_id
+    )
+    .execute(&pool)
+    .await?;
+
+    for row in guild_counts {
+        Event::Enable.record(&pool, row.count as u64, GuildId(row.guild), msg.author.id);
+    }
+
+    let content = if count == 0 {
+        lm.get(&language, "pause_mine/not_paused").to_string()
+    } else {
+        lm.get(&language, "pause_mine/resumed")
+            .replace("{count}", &count.to_string())
+    };
+
+    let _ = msg.channel_id.say(&ctx, content).await;
+
+    Ok(())
 }
 
+/// The most reminders `offset --preview` lists. The rest are only counted
+const OFFSET_PREVIEW_LIMIT: usize = 10;
+
+/// `offset [--preview] <time>`: move every reminder in the guild, or in the user's DMs, by the
+/// time given. `--preview` lists what would move and where to without changing anything
 #[command]
 #[permission_level(Restricted)]
-async fn offset(ctx: &Context, msg: &Message, args: String) {
+async fn offset(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
-    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
+
+    let (preview, args) = match args.strip_prefix("--preview") {
+        Some(rest) => (true, rest.trim()),
+
+        None => (false, args.as_str()),
+    };
 
     if args.is_empty() {
         let prefix = ctx.prefix(msg.guild_id).await;
@@ -152,44 +507,102 @@ async fn offset(ctx: &Context, msg: &Message, args: String) {
         let parser = TimeParser::new(&args, user_data.timezone());
 
         if let Ok(displacement) = parser.displacement() {
-            if let Some(guild) = msg.guild(&ctx) {
-                let guild_data = GuildData::from_guild(guild, &pool).await.unwrap();
+            let guild_data = match msg.guild(&ctx) {
+                Some(guild) => Some(GuildData::from_guild(guild, &pool).await?),
 
-                sqlx::query!(
-                    "
+                None => None,
+            };
+
+            if preview {
+                // selected with the same predicates as the updates below
+                let reminders = Reminder::offset_targets(
+                    &pool,
+                    guild_data.as_ref().map(|guild_data| guild_data.id),
+                    user_data.dm_channel,
+                )
+                .await?;
+
+                let header = lm
+                    .get(&user_data.language, "offset/preview")
+                    .replace("{count}", &reminders.len().to_string())
+                    .replace("{offset}", &displacement.to_string());
+
+                let lines = reminders
+                    .iter()
+                    .take(OFFSET_PREVIEW_LIMIT)
+                    .map(|reminder| {
+                        let time = reminder.utc_time.timestamp();
+
+                        format!(
+                            "'{}' *<#{}>* <t:{}> → <t:{}>",
+                            reminder.display_content(),
+                            reminder.channel,
+                            time,
+                            time + displacement
+                        )
+                    })
+                    .collect::<Vec<String>>();
+
+                let more = reminders.len().saturating_sub(OFFSET_PREVIEW_LIMIT);
+                let footer = if more > 0 {
+                    Some(
+                        lm.get(&user_data.language, "offset/preview_more")
+                            .replace("{count}", &more.to_string()),
+                    )
+                } else {
+                    None
+                };
+
+                let _ = msg
+                    .channel_id
+                    .say_lines(
+                        &ctx,
+                        std::iter::once(header)
+                            .chain(lines.into_iter())
+                            .chain(footer.into_iter()),
+                    )
+                    .await;
+            } else {
+                if let (Some(guild_id), Some(guild_data)) = (msg.guild_id, guild_data) {
+                    let updated = sqlx::query!(
+                        "
 UPDATE reminders
     INNER JOIN `channels`
         ON `channels`.id = reminders.channel_id
     SET
         reminders.`utc_time` = DATE_ADD(reminders.`utc_time`, INTERVAL ? SECOND)
     WHERE channels.guild_id = ?
-                    ",
-                    displacement,
-                    guild_data.id
-                )
-                .execute(&pool)
-                .await
-                .unwrap();
-            } else {
-                sqlx::query!(
-                    "
+                        ",
+                        displacement,
+                        guild_data.id
+                    )
+                    .execute(&pool)
+                    .timed("offset guild reminders")
+                    .await?
+                    .rows_affected();
+
+                    Event::Offset.record(&pool, updated, guild_id, msg.author.id);
+                } else {
+                    sqlx::query!(
+                        "
 UPDATE reminders SET `utc_time` = DATE_ADD(`utc_time`, INTERVAL ? SECOND) WHERE reminders.channel_id = ?
-                    ",
-                    displacement,
-                    user_data.dm_channel
-                )
-                .execute(&pool)
-                .await
-                .unwrap();
-            }
+                        ",
+                        displacement,
+                        user_data.dm_channel
+                    )
+                    .execute(&pool)
+                    .timed("offset DM reminders")
+                    .await?;
+                }
 
-            let response = lm.get(&user_data.language, "offset/success").replacen(
-                "{}",
-                &displacement.to_string(),
-                1,
-            );
+                let response = lm.get(&user_data.language, "offset/success").replacen(
+                    "{}",
+                    &displacement.to_string(),
+                    1,
+                );
 
-            let _ = msg.channel_id.say(&ctx, response).await;
+                let _ = msg.channel_id.say(&ctx, response).await;
+            }
         } else {
             let _ = msg
                 .channel_id
@@ -197,26 +610,138 @@ UPDATE reminders SET `utc_time` = DATE_ADD(`utc_time`, INTERVAL ? SECOND) WHERE
                 .await;
         }
     }
+
+    Ok(())
+}
+
+#[command("snooze-all")]
+#[permission_level(Managed)]
+async fn snooze_all(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    if args.is_empty() {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "snooze-all").await;
+    } else if let Ok(displacement) = TimeParser::new(&args, timezone).displacement() {
+        let channel_id = msg.channel_id.as_u64().to_owned();
+
+        let due = sqlx::query!(
+            "
+SELECT COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE channels.channel = ? AND reminders.`utc_time` < UTC_TIMESTAMP() AND reminders.enabled = 1
+            ",
+            channel_id
+        )
+        .fetch_one(&pool)
+        .timed("snooze-all count")
+        .await?
+        .count;
+
+        if due == 0 {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "snooze/none"))
+                .await;
+        } else if await_confirmation(
+            ctx,
+            msg,
+            &lm,
+            &language,
+            &lm.get(&language, "snooze/confirm")
+                .replace("{count}", &due.to_string()),
+        )
+        .await
+        {
+            let updated = sqlx::query!(
+                "
+UPDATE reminders
+    INNER JOIN `channels`
+        ON `channels`.id = reminders.channel_id
+    SET
+        reminders.`utc_time` = DATE_ADD(UTC_TIMESTAMP(), INTERVAL ? SECOND)
+    WHERE channels.channel = ? AND reminders.`utc_time` < UTC_TIMESTAMP() AND reminders.enabled = 1
+                ",
+                displacement,
+                channel_id
+            )
+            .execute(&pool)
+            .timed("snooze-all update")
+            .await?
+            .rows_affected();
+
+            if let Some(guild_id) = msg.guild_id {
+                Event::Snooze.record(&pool, updated, guild_id, msg.author.id);
+            }
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "snooze/success")
+                        .replace("{count}", &updated.to_string()),
+                )
+                .await;
+        }
+    } else {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "offset/invalid_time"))
+            .await;
+    }
+
+    Ok(())
 }
 
 #[command]
 #[permission_level(Restricted)]
-async fn nudge(ctx: &Context, msg: &Message, args: String) {
+async fn nudge(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let language = UserData::language_of(&msg.author, &pool).await;
     let timezone = UserData::timezone_of(&msg.author, &pool).await;
 
-    let mut channel = ChannelData::from_channel(msg.channel(&ctx).await.unwrap(), &pool)
-        .await
-        .unwrap();
+    let mut channel = ChannelData::from_channel(msg.channel(&ctx).await?, &pool).await?;
 
     if args.is_empty() {
-        let content = lm
+        let mut content = lm
             .get(&language, "nudge/no_argument")
             .replace("{nudge}", &format!("{}s", &channel.nudge.to_string()));
 
+        if channel.nudge != 0 {
+            // show the effect on a reminder for the start of the next hour
+            let unix_time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64;
+            let example = (unix_time / 3600 + 1) * 3600;
+
+            content.push('\n');
+            content.push_str(
+                &lm.get(&language, "nudge/example")
+                    .replace("{time}", &format!("<t:{}:T>", example))
+                    .replace(
+                        "{nudged}",
+                        &format!("<t:{}:T>", example + channel.nudge as i64),
+                    ),
+            );
+        }
+
         let _ = msg.channel_id.say(&ctx, content).await;
+    } else if args.eq_ignore_ascii_case("reset") {
+        channel.nudge = 0;
+
+        channel.commit_changes(&pool).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "nudge/reset"))
+            .await;
     } else {
         let parser = TimeParser::new(&args, timezone);
         let nudge_time = parser.displacement();
@@ -251,16 +776,18 @@ async fn nudge(ctx: &Context, msg: &Message, args: String) {
             }
         }
     }
+
+    Ok(())
 }
 
 #[command("look")]
 #[permission_level(Managed)]
-async fn look(ctx: &Context, msg: &Message, args: String) {
+async fn look(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let language = UserData::language_of(&msg.author, &pool).await;
 
-    let flags = LookFlags::from_string(&args);
+    let mut flags = LookFlags::from_string(&args);
 
     let channel_opt = msg.channel_id.to_channel_cached(&ctx);
 
@@ -274,6 +801,50 @@ async fn look(ctx: &Context, msg: &Message, args: String) {
         msg.channel_id
     };
 
+    if let Ok(channel) = channel_id.to_channel(&ctx).await {
+        if let Ok(channel_data) = ChannelData::from_channel(channel, &pool).await {
+            flags.display_timezone = channel_data.display_timezone();
+        }
+    }
+
+    if let Some(role_id) = flags.role_id {
+        let visible = match channel_id.to_channel_cached(&ctx) {
+            Some(Channel::Guild(channel)) => channel
+                .permissions_for_role(&ctx, role_id)
+                .map(|permissions| permissions.view_channel())
+                .ok(),
+
+            _ => None,
+        };
+
+        match visible {
+            Some(true) => {}
+
+            Some(false) => {
+                let _ = msg
+                    .channel_id
+                    .send_message(&ctx, |m| {
+                        m.content(
+                            lm.get(&language, "look/role_hidden")
+                                .replace("{role}", &role_id.mention().to_string()),
+                        )
+                        .allowed_mentions(|am| am.empty_parse())
+                    })
+                    .await;
+
+                return Ok(());
+            }
+
+            // without cached permissions, show everything rather than nothing
+            None => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "look/role_unknown"))
+                    .await;
+            }
+        }
+    }
+
     let reminders = Reminder::from_channel(ctx, channel_id, &flags).await;
 
     if reminders.is_empty() {
@@ -283,270 +854,2174 @@ async fn look(ctx: &Context, msg: &Message, args: String) {
             .await;
     } else {
         let inter = lm.get(&language, "look/inter");
+        let units = displacement_units(&lm, &language);
+        let departed = departed_creators(ctx, msg.guild_id, &reminders).await;
 
         let display = reminders
             .iter()
-            .map(|reminder| reminder.display(&flags, &inter));
+            .map(|reminder| reminder.display(&flags, &inter, &units, &departed));
 
         let _ = msg.channel_id.say_lines(&ctx, display).await;
     }
+
+    Ok(())
 }
 
-#[command("del")]
-#[permission_level(Managed)]
-async fn delete(ctx: &Context, msg: &Message, _args: String) {
-    let (pool, lm) = get_ctx_data(&ctx).await;
+/// Most members Discord returns from one request for a guild's member list
+const MEMBER_PAGE_SIZE: u64 = 1000;
 
-    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+/// The creators of `reminders` who are no longer members of the guild. Creators in the member
+/// cache are members. The rest are looked up one request each, unless paging through the
+/// guild's member list takes fewer requests
+async fn departed_creators(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    reminders: &[Reminder],
+) -> HashSet<u64> {
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id,
 
-    let _ = msg
-        .channel_id
-        .say(&ctx, lm.get(&user_data.language, "del/listing"))
-        .await;
+        None => return HashSet::new(),
+    };
 
-    let mut reminder_ids: Vec<u32> = vec![];
+    let mut departed = reminders
+        .iter()
+        .filter_map(|reminder| reminder.set_by)
+        .filter(|creator| ctx.cache.member(guild_id, *creator).is_none())
+        .collect::<HashSet<u64>>();
+
+    let pages = ctx
+        .cache
+        .guild_field(guild_id, |guild| guild.member_count)
+        .map_or(u64::MAX, |member_count| member_count / MEMBER_PAGE_SIZE + 1);
+
+    if departed.len() as u64 <= pages {
+        for creator in departed.clone() {
+            if guild_id.member(&ctx, creator).await.is_ok() {
+                departed.remove(&creator);
+            }
+        }
+    } else {
+        let mut after = None;
 
-    let reminders = Reminder::from_guild(ctx, msg.guild_id, msg.author.id).await;
+        while !departed.is_empty() {
+            let members = match guild_id.members(&ctx, Some(MEMBER_PAGE_SIZE), after).await {
+                Ok(members) => members,
 
-    let enumerated_reminders = reminders.iter().enumerate().map(|(count, reminder)| {
-        reminder_ids.push(reminder.id);
+                // without the full list, no one can be said to have left
+                Err(_) => return HashSet::new(),
+            };
 
-        format!(
-            "**{}**: '{}' *<#{}>* at <t:{}>",
-            count + 1,
-            reminder.display_content(),
-            reminder.channel,
-            reminder.utc_time.timestamp()
+            for member in &members {
+                departed.remove(member.user.id.as_u64());
+            }
+
+            match members.last() {
+                Some(last) if members.len() as u64 == MEMBER_PAGE_SIZE => {
+                    after = Some(last.user.id)
+                }
+
+                _ => break,
+            }
+        }
+    }
+
+    departed
+}
+
+#[command("count")]
+#[permission_level(Managed)]
+async fn count(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let rows = if let Some(guild_id) = msg.guild_id {
+        sqlx::query_as!(
+            ChannelCount,
+            "
+SELECT
+    channels.channel,
+    COUNT(IF(reminders.enabled, 1, NULL)) AS enabled,
+    COUNT(IF(reminders.enabled, NULL, 1)) AS disabled
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE channels.guild_id = (SELECT id FROM guilds WHERE guild = ?)
+GROUP BY channels.channel
+ORDER BY enabled DESC
+            ",
+            guild_id.as_u64()
         )
-    });
+        .fetch_all(&pool)
+        .timed("count guild reminders")
+        .await
+    } else {
+        sqlx::query_as!(
+            ChannelCount,
+            "
+SELECT
+    channels.channel,
+    COUNT(IF(reminders.enabled, 1, NULL)) AS enabled,
+    COUNT(IF(reminders.enabled, NULL, 1)) AS disabled
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE channels.channel = ?
+GROUP BY channels.channel
+            ",
+            msg.channel_id.as_u64()
+        )
+        .fetch_all(&pool)
+        .timed("count channel reminders")
+        .await
+    }?;
+
+    if rows.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "count/no_reminders"))
+            .await;
+    } else {
+        let row_format = lm.get(&language, "count/row");
+
+        let mut description = String::new();
+
+        for row in &rows {
+            let line = row_format
+                .replace("{channel}", &format!("<#{}>", row.channel))
+                .replace("{enabled}", &row.enabled.to_string())
+                .replace("{disabled}", &row.disabled.to_string());
+
+            if description.chars().count() + line.chars().count() + 1 > EMBED_DESCRIPTION_LIMIT {
+                break;
+            }
+
+            description.push_str(&line);
+            description.push('\n');
+        }
+
+        let _ = msg
+            .channel_id
+            .send_message(&ctx, |m| {
+                m.embed(|e| {
+                    e.title(
+                        lm.get(&language, "count/title")
+                            .replace(
+                                "{enabled}",
+                                &rows.iter().map(|r| r.enabled).sum::<i64>().to_string(),
+                            )
+                            .replace(
+                                "{disabled}",
+                                &rows.iter().map(|r| r.disabled).sum::<i64>().to_string(),
+                            ),
+                    )
+                    .description(description)
+                    .color(*THEME_COLOR)
+                })
+            })
+            .await;
+    }
+
+    Ok(())
+}
+
+struct ChannelCount {
+    channel: u64,
+    enabled: i64,
+    disabled: i64,
+}
+
+/// How many of the guild's enabled reminders are due within the next hour, day and week, to see
+/// bursts coming. Repeating reminders are counted once, at their next time
+#[command("stats")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn stats(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let row = sqlx::query!(
+        "
+SELECT
+    COUNT(IF(reminders.`utc_time` < DATE_ADD(UTC_TIMESTAMP(), INTERVAL 1 HOUR), 1, NULL)) AS next_hour,
+    COUNT(IF(reminders.`utc_time` < DATE_ADD(UTC_TIMESTAMP(), INTERVAL 1 DAY), 1, NULL)) AS next_day,
+    COUNT(1) AS next_week
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE
+    channels.guild_id = (SELECT id FROM guilds WHERE guild = ?) AND
+    reminders.enabled = 1 AND
+    reminders.`utc_time` >= UTC_TIMESTAMP() AND
+    reminders.`utc_time` < DATE_ADD(UTC_TIMESTAMP(), INTERVAL 7 DAY)
+        ",
+        msg.guild_id.unwrap().as_u64()
+    )
+    .fetch_one(&pool)
+    .timed("upcoming reminder stats")
+    .await?;
 
-    let _ = msg.channel_id.say_lines(&ctx, enumerated_reminders).await;
     let _ = msg
         .channel_id
-        .say(&ctx, lm.get(&user_data.language, "del/listed"))
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                e.title(lm.get(&language, "stats/title"))
+                    .field(lm.get(&language, "stats/hour"), row.next_hour, true)
+                    .field(lm.get(&language, "stats/day"), row.next_day, true)
+                    .field(lm.get(&language, "stats/week"), row.next_week, true)
+                    .color(*THEME_COLOR)
+            })
+        })
         .await;
 
-    let reply = msg
+    Ok(())
+}
+
+/// `list-channels`: every channel in the guild with enabled reminders, and how many, to find
+/// reminders left in forgotten channels. Channels that no longer exist are shown by ID
+#[command("list-channels")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn list_channels(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let rows = sqlx::query!(
+        "
+SELECT channels.channel, COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE
+    channels.guild_id = (SELECT id FROM guilds WHERE guild = ?) AND
+    reminders.enabled = 1
+GROUP BY channels.channel
+ORDER BY count DESC
+        ",
+        msg.guild_id.unwrap().as_u64()
+    )
+    .fetch_all(&pool)
+    .timed("reminder channels")
+    .await?;
+
+    if rows.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "list_channels/none"))
+            .await;
+
+        return Ok(());
+    }
+
+    let lines = rows.iter().map(|row| {
+        let channel_id = ChannelId(row.channel);
+
+        let location = if channel_id.to_channel_cached(&ctx).is_some() {
+            channel_id.mention().to_string()
+        } else {
+            lm.get(&language, "list_channels/deleted")
+                .replace("{id}", &row.channel.to_string())
+        };
+
+        lm.get(&language, "list_channels/line")
+            .replace("{channel}", &location)
+            .replace("{number}", &row.count.to_string())
+    });
+
+    // whatever doesn't fit in the embed is only counted
+    let mut description = String::new();
+    let mut shown = 0;
+
+    for line in lines {
+        if description.len() + line.len() + 1 > EMBED_DESCRIPTION_LIMIT {
+            break;
+        }
+
+        description.push_str(&line);
+        description.push('\n');
+        shown += 1;
+    }
+
+    let total = rows.iter().map(|row| row.count).sum::<i64>();
+    let mut footer = lm
+        .get(&language, "list_channels/total")
+        .replace("{number}", &total.to_string());
+
+    if shown < rows.len() {
+        footer = format!(
+            "{} {}",
+            footer,
+            lm.get(&language, "list_channels/more")
+                .replace("{number}", &(rows.len() - shown).to_string())
+        );
+    }
+
+    let _ = msg
         .channel_id
-        .await_reply(&ctx)
-        .author_id(msg.author.id)
-        .channel_id(msg.channel_id)
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                e.title(lm.get(&language, "list_channels/title"))
+                    .description(description)
+                    .footer(|f| f.text(footer))
+                    .color(*THEME_COLOR)
+            })
+        })
         .await;
 
-    if let Some(content) = reply.map(|m| m.content.replace(",", " ")) {
-        let parts = content
-            .split(' ')
-            .filter(|i| !i.is_empty())
-            .collect::<Vec<&str>>();
+    Ok(())
+}
 
-        let valid_parts = parts
-            .iter()
-            .filter_map(|i| {
-                i.parse::<usize>()
-                    .ok()
-                    .filter(|val| val > &0)
-                    .map(|val| reminder_ids.get(val - 1))
-                    .flatten()
-            })
-            .map(|item| item.to_string())
-            .collect::<Vec<String>>();
+#[command("online")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn online(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
 
-        if parts.len() == valid_parts.len() {
-            let joined = valid_parts.join(",");
+    let language = UserData::language_of(&msg.author, &pool).await;
 
-            let count_row = sqlx::query!(
-                "
-SELECT COUNT(1) AS count FROM reminders WHERE FIND_IN_SET(id, ?)
-                ",
-                joined
-            )
-            .fetch_one(&pool)
-            .await
-            .unwrap();
+    let mut split = args.split_whitespace();
 
-            sqlx::query!(
-                "
-DELETE FROM reminders WHERE FIND_IN_SET(id, ?)
-                ",
-                joined
+    if !*PRESENCE_ENABLED {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "online/disabled"))
+            .await;
+    } else if args.trim() == "list" {
+        let reminders = PresenceReminder::from_channel(*msg.channel_id.as_u64(), &pool).await;
+
+        if reminders.is_empty() {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "online/none"))
+                .await;
+        } else {
+            let lines = reminders.iter().map(|reminder| {
+                format!(
+                    "`{}` {}: {}",
+                    reminder.id,
+                    UserId(reminder.target_user).mention(),
+                    reminder.content
+                )
+            });
+
+            let _ = msg.channel_id.say_lines(&ctx, lines).await;
+        }
+    } else if let (Some("cancel"), Some(id), None) = (split.next(), split.next(), split.next()) {
+        let deleted = match id.parse::<u32>() {
+            Ok(id) => PresenceReminder::delete(id, *msg.channel_id.as_u64(), &pool).await,
+
+            Err(_) => false,
+        };
+
+        let response = if deleted {
+            "online/cancelled"
+        } else {
+            "online/not_found"
+        };
+
+        let _ = msg.channel_id.say(&ctx, lm.get(&language, response)).await;
+    } else if let Some(captures) = REGEX_ONLINE_COMMAND.captures(&args) {
+        let target = UserId(
+            captures
+                .name("user")
+                .unwrap()
+                .as_str()
+                .parse::<u64>()
+                .unwrap(),
+        );
+        let content = captures.name("content").unwrap().as_str();
+
+        if content.chars().count() > MESSAGE_CONTENT_LIMIT {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "online/too_long"))
+                .await;
+        } else if msg.guild_id.unwrap().member(&ctx, target).await.is_err() {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "remind/invalid_tag"))
+                .await;
+        } else {
+            PresenceReminder::create(
+                *target.as_u64(),
+                *msg.channel_id.as_u64(),
+                content,
+                *msg.author.id.as_u64(),
+                &pool,
             )
-            .execute(&pool)
-            .await
-            .unwrap();
+            .await?;
 
-            if let Some(guild_id) = msg.guild_id {
-                let _ = sqlx::query!(
-                    "
-INSERT INTO events (event_name, bulk_count, guild_id, user_id) VALUES ('delete', ?, ?, ?)
-                    ",
-                    count_row.count,
-                    guild_id.as_u64(),
-                    user_data.id
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "online/success")
+                        .replace("{user}", &target.mention().to_string()),
                 )
-                .execute(&pool)
                 .await;
+        }
+    } else {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "online").await;
+    }
+
+    Ok(())
+}
+
+/// `keyword add <word> <message>` and `keyword remove <word>`: messages sent in this channel when
+/// someone says a word, at most once per `KEYWORD_COOLDOWN`. `keyword on|off` turns them on or off
+/// for the whole guild, and no arguments lists the channel's
+#[command("keyword")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn keyword(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    if !*KEYWORDS_ENABLED {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "keyword/disabled"))
+            .await;
+
+        return Ok(());
+    }
+
+    let channel_id = *msg.channel_id.as_u64();
+
+    let mut split = args.splitn(3, char::is_whitespace);
+    let subcommand = split.next().unwrap_or("").to_lowercase();
+
+    match (subcommand.as_str(), split.next(), split.next()) {
+        ("", _, _) => {
+            let triggers = KeywordTrigger::from_channel(channel_id, &pool).await;
+
+            if triggers.is_empty() {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "keyword/none"))
+                    .await;
+            } else {
+                let _ = msg
+                    .channel_id
+                    .send_message(&ctx, |m| {
+                        m.embed(|e| {
+                            e.title(lm.get(&language, "keyword/title"))
+                                .fields(triggers.iter().map(|trigger| {
+                                    (format!("`{}`", trigger.keyword), &trigger.content, false)
+                                }))
+                                .color(*THEME_COLOR)
+                        })
+                    })
+                    .await;
+            }
+        }
+
+        (setting @ "on", None, _) | (setting @ "off", None, _) => {
+            let enabled = setting == "on";
+            let guild_data = ctx.guild_data(msg.guild_id.unwrap()).await?;
+
+            guild_data.write().await.keyword_triggers = enabled;
+            guild_data.read().await.commit_changes(&pool).await;
+
+            let response = if enabled { "keyword/on" } else { "keyword/off" };
+
+            let _ = msg.channel_id.say(&ctx, lm.get(&language, response)).await;
+        }
+
+        ("add", Some(word), Some(content)) if !content.trim().is_empty() => {
+            let word = word.to_lowercase();
+            let content = content.trim();
+
+            let triggers = KeywordTrigger::from_channel(channel_id, &pool).await;
+            // replacing an existing keyword's content doesn't add a trigger
+            let at_limit = triggers.len() >= *MAX_KEYWORDS
+                && !triggers.iter().any(|trigger| trigger.keyword == word);
+
+            let response = if !KeywordTrigger::valid_keyword(&word) {
+                lm.get(&language, "keyword/invalid_keyword").to_string()
+            } else if content.chars().count() > MESSAGE_CONTENT_LIMIT {
+                lm.get(&language, "keyword/too_long").to_string()
+            } else if at_limit {
+                lm.get(&language, "keyword/limit").to_string()
+            } else {
+                KeywordTrigger::create(channel_id, &word, content, *msg.author.id.as_u64(), &pool)
+                    .await?;
+
+                lm.get(&language, "keyword/added")
+                    .replace("{keyword}", &word)
+            };
+
+            let _ = msg.channel_id.say(&ctx, response).await;
+        }
+
+        ("remove", Some(word), None) => {
+            let response = if KeywordTrigger::delete(channel_id, &word.to_lowercase(), &pool).await
+            {
+                lm.get(&language, "keyword/removed")
+                    .replace("{keyword}", &word.to_lowercase())
+            } else {
+                lm.get(&language, "keyword/not_found").to_string()
+            };
+
+            let _ = msg.channel_id.say(&ctx, response).await;
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "keyword").await;
+        }
+    }
+
+    Ok(())
+}
+
+#[command("next")]
+#[permission_level(Managed)]
+async fn next(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let channel = ChannelData::from_channel(msg.channel(&ctx).await?, &pool).await?;
+
+    if channel.blacklisted {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "next/blacklisted"))
+            .await;
+    } else if channel.paused && channel.paused_until.is_none() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "next/paused"))
+            .await;
+    } else {
+        // reminders due while the channel is paused won't be sent, so look past the pause
+        let after = channel.paused_until.filter(|_| channel.paused);
+
+        match Reminder::next_in_channel(ctx, msg.channel_id, after).await {
+            Some(reminder) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs() as i64;
+
+                let delta = reminder.utc_time.timestamp() - now;
+                let units = displacement_units(&lm, &language);
+
+                // a reminder that is due but not yet sent shows how late it is
+                let key = if delta > 0 {
+                    "next/next"
+                } else {
+                    "next/overdue"
+                };
+
+                let mut content = lm
+                    .get(&language, key)
+                    .replace("{content}", reminder.display_content())
+                    .replace(
+                        "{time}",
+                        &longhand_displacement(delta.abs().max(1) as u64, &units),
+                    );
+
+                if let Some(interval) = reminder.interval {
+                    content.push('\n');
+                    content.push_str(&lm.get(&language, "next/repeating").replace(
+                        "{interval}",
+                        &longhand_displacement(interval as u64, &units),
+                    ));
+                }
+
+                let _ = msg
+                    .channel_id
+                    .send_message(&ctx, |m| {
+                        m.content(content).allowed_mentions(|am| am.empty_parse())
+                    })
+                    .await;
+            }
+
+            None => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "next/no_reminders"))
+                    .await;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[command("agenda")]
+#[permission_level(Managed)]
+async fn agenda(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
+
+    let mut split = args.split_whitespace();
+
+    let (start, end) = match (split.next(), split.next(), split.next()) {
+        (Some(start), Some(end), None) => (start, end),
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &user_data.language, "agenda").await;
+
+            return Ok(());
+        }
+    };
+
+    let timezone = user_data.timezone();
+
+    let (start, end) = match (
+        TimeParser::new(start, timezone).timestamp(),
+        TimeParser::new(end, timezone).timestamp(),
+    ) {
+        (Ok(start), Ok(end)) if start <= end => (start, end),
+
+        (Ok(_), Ok(_)) => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "agenda/backwards"))
+                .await;
+
+            return Ok(());
+        }
+
+        _ => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&user_data.language, "agenda/invalid_time"))
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    let reminders = Reminder::between(ctx, msg.guild_id, msg.author.id, start, end).await;
+
+    if reminders.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&user_data.language, "agenda/no_reminders"))
+            .await;
+    } else {
+        let header = lm
+            .get(&user_data.language, "agenda/title")
+            .replace("{start}", &format!("<t:{}>", start))
+            .replace("{end}", &format!("<t:{}>", end));
+
+        let display = std::iter::once(header).chain(reminders.iter().map(|reminder| {
+            let timestamp = reminder.utc_time.timestamp();
+
+            format!(
+                "{}<t:{}:f> (<t:{}:R>) *<#{}>* '{}'",
+                if reminder.interval.is_some() {
+                    "🔁 "
+                } else {
+                    ""
+                },
+                timestamp,
+                timestamp,
+                reminder.channel,
+                reminder.display_content()
+            )
+        }));
+
+        let _ = msg.channel_id.say_lines(&ctx, display).await;
+    }
+
+    Ok(())
+}
+
+/// `inspect <position> [look options]`: the stored values of one of the channel's reminders, for
+/// tracking down timezone problems. The position is the one `look` shows with the same options
+#[command("inspect")]
+#[permission_level(Managed)]
+async fn inspect(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    let mut split = args.splitn(2, char::is_whitespace);
+
+    let position = match split.next().map(|position| position.parse::<usize>().ok()) {
+        Some(Some(position)) => position,
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "inspect").await;
+
+            return Ok(());
+        }
+    };
+
+    let flags = LookFlags::from_string(split.next().unwrap_or(""));
+
+    // only reminders in this guild can be looked at from here
+    let channel_id = flags
+        .channel_id
+        .filter(|channel_id| match channel_id.to_channel_cached(&ctx) {
+            Some(Channel::Guild(channel)) => Some(channel.guild_id) == msg.guild_id,
+
+            _ => false,
+        })
+        .unwrap_or(msg.channel_id);
+
+    let reminders = Reminder::from_channel(ctx, channel_id, &flags).await;
+
+    match position
+        .checked_sub(1)
+        .map(|index| reminders.get(index))
+        .flatten()
+    {
+        Some(reminder) => {
+            let utc_time = Utc.from_utc_datetime(&reminder.utc_time);
+
+            let mut lines = vec![
+                format!("uid:        {}", reminder.uid),
+                format!(
+                    "utc_time:   {} ({})",
+                    reminder.utc_time,
+                    reminder.utc_time.timestamp()
+                ),
+                format!(
+                    "interval:   {}",
+                    reminder
+                        .interval
+                        .map_or("NULL".to_string(), |interval| interval.to_string())
+                ),
+                format!(
+                    "expires:    {}",
+                    reminder
+                        .expires
+                        .map_or("NULL".to_string(), |expires| format!(
+                            "{} ({})",
+                            expires,
+                            expires.timestamp()
+                        ))
+                ),
+                format!("enabled:    {}", reminder.enabled),
+                format!("next (UTC): {}", utc_time.format("%Y-%m-%d %H:%M:%S")),
+            ];
+
+            lines.push(format!(
+                "next ({}): {}",
+                timezone,
+                utc_time
+                    .with_timezone(&timezone)
+                    .format("%Y-%m-%d %H:%M:%S %Z")
+            ));
+
+            let _ = msg
+                .channel_id
+                .send_message(&ctx, |m| {
+                    m.embed(|e| {
+                        e.title(lm.get(&language, "inspect/title"))
+                            .description(format!("```\n{}\n```", lines.join("\n")))
+                            .color(*THEME_COLOR)
+                    })
+                })
+                .await;
+        }
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "inspect/not_found"))
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// The most reminders `preview` sends, so that a busy channel doesn't flood the user's DMs
+const PREVIEW_LIMIT: u16 = 10;
+
+// Discord's error code for a message to a user who doesn't accept DMs from the bot
+const CANNOT_MESSAGE_USER: isize = 50007;
+
+/// Whether a DM failed because the user has closed their DMs, rather than because of a problem
+/// on Discord's side
+fn is_dms_closed(error: &SerenityError) -> bool {
+    match error {
+        SerenityError::Http(http_error) => match http_error.as_ref() {
+            HttpError::UnsuccessfulRequest(response) => response.error.code == CANNOT_MESSAGE_USER,
+
+            _ => false,
+        },
+
+        _ => false,
+    }
+}
+
+/// `preview [look options]`: send this channel's upcoming reminders to the user's DMs, looking as
+/// they will when they're sent, soonest first
+#[command("preview")]
+#[supports_dm(false)]
+#[permission_level(Managed)]
+async fn preview(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let mut flags = LookFlags::from_string(&args);
+
+    flags.limit = flags.limit.min(PREVIEW_LIMIT);
+    flags.show_disabled = false;
+
+    // only reminders in this guild can be looked at from here
+    let channel_id = flags
+        .channel_id
+        .filter(|channel_id| match channel_id.to_channel_cached(&ctx) {
+            Some(Channel::Guild(channel)) => Some(channel.guild_id) == msg.guild_id,
+
+            _ => false,
+        })
+        .unwrap_or(msg.channel_id);
+
+    let reminders = Reminder::from_channel(ctx, channel_id, &flags).await;
+
+    if reminders.is_empty() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "preview/none"))
+            .await;
+
+        return Ok(());
+    }
+
+    let intro = msg
+        .author
+        .dm(&ctx, |m| {
+            m.content(
+                lm.get(&language, "preview/intro")
+                    .replace("{count}", &reminders.len().to_string())
+                    .replace("{channel}", &channel_id.mention().to_string()),
+            )
+        })
+        .await;
+
+    if let Err(e) = intro {
+        // reminders set by others couldn't be delivered to them either
+        let response = if is_dms_closed(&e) {
+            let mut user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
+
+            user_data.dm_opt_out = true;
+            user_data.commit_changes(&pool).await;
+
+            lm.get(&language, "preview/dms_closed")
+                .replace("{prefix}", &ctx.prefix(msg.guild_id).await)
+        } else {
+            lm.get(&language, "preview/dm_failed").to_string()
+        };
+
+        let _ = msg.channel_id.say(&ctx, response).await;
+
+        return Ok(());
+    }
+
+    for reminder in &reminders {
+        let rendered = render_reminder(&pool, reminder.id).await?;
+
+        let _ = msg.author.dm(&ctx, |m| rendered.build(m)).await;
+    }
+
+    let _ = msg
+        .channel_id
+        .say(
+            &ctx,
+            lm.get(&language, "preview/sent")
+                .replace("{count}", &reminders.len().to_string()),
+        )
+        .await;
+
+    Ok(())
+}
+
+/// `after <reminder> <offset> <content>`: a reminder set relative to another in this channel. The
+/// anchor is either its position in `look`, or a word from its content. The time is worked out
+/// once, when the reminder is created, so later changes to the anchor don't move it
+#[command("after")]
+#[permission_level(Managed)]
+async fn after(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
+    let language = &user_data.language;
+
+    let mut split = args.splitn(3, char::is_whitespace);
+
+    let (anchor, offset, content) = match (split.next(), split.next(), split.next()) {
+        (Some(anchor), Some(offset), Some(content)) if !content.trim().is_empty() => {
+            (anchor, offset, content.trim())
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, language, "after").await;
+
+            return Ok(());
+        }
+    };
+
+    let reminders = Reminder::from_channel(ctx, msg.channel_id, &LookFlags::from_string("")).await;
+
+    let anchor_reminder = match anchor.parse::<usize>() {
+        Ok(index) => index
+            .checked_sub(1)
+            .map(|index| reminders.get(index))
+            .flatten(),
+
+        Err(_) => {
+            let anchor = anchor.to_lowercase();
+
+            reminders
+                .iter()
+                .find(|reminder| reminder.display_content().to_lowercase().contains(&anchor))
+        }
+    };
+
+    let anchor_reminder = match anchor_reminder {
+        Some(reminder) => reminder,
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(language, "after/not_found"))
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    // only durations make sense here, not times of day
+    let offset = if offset.contains(|c: char| c == ':' || c == '/') {
+        Err(())
+    } else {
+        TimeParser::new(offset, user_data.timezone())
+            .displacement()
+            .map_err(|_| ())
+    };
+
+    let offset = match offset {
+        Ok(offset) => offset,
+
+        Err(_) => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(language, "after/invalid_offset"))
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    let mut content = match Content::build(content, msg).await {
+        Ok(content) => content,
+
+        Err(content_error) => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(language, content_error.to_response()))
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    if !check_everyone(ctx, msg, &lm, language, &content).await {
+        return Ok(());
+    }
+
+    let scope = ReminderScope::Channel(msg.channel_id.into());
+    let timestamp = anchor_reminder.utc_time.timestamp() + offset;
+
+    let res = create_reminder(
+        &ctx,
+        &pool,
+        msg.author.id,
+        msg.guild_id,
+        &scope,
+        timestamp,
+        None,
+        None,
+        &mut content,
+        ReminderFlags::default(),
+        msg.id,
+        &format!("{} {}", anchor, offset),
+    )
+    .await;
+
+    let number = if res.is_ok() { "1" } else { "0" };
+
+    let response = match res {
+        Ok(_) => lm
+            .get(language, "remind/success")
+            .replace("{location}", &scope.mention())
+            .replace("{offset}", &format!("<t:{}:R>", timestamp)),
+
+        Err(e) => describe_error(
+            &lm,
+            language,
+            e.to_response(),
+            &e,
+            guild_subscribed(ctx, *msg.author.id.as_u64(), msg.guild_id).await,
+        ),
+    };
+
+    let _ = msg
+        .channel_id
+        .send_message(&ctx, |m| {
+            m.embed(|e| {
+                e.title(lm.get(language, "remind/title").replace("{number}", number))
+                    .description(response)
+                    .color(*THEME_COLOR)
+            })
+        })
+        .await;
+
+    Ok(())
+}
+
+#[command("del")]
+#[permission_level(Managed)]
+async fn delete(ctx: &Context, msg: &Message, _args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
+
+    let _ = msg
+        .channel_id
+        .say(&ctx, lm.get(&user_data.language, "del/listing"))
+        .await;
+
+    let mut reminder_ids: Vec<u32> = vec![];
+
+    let reminders = Reminder::from_guild(ctx, msg.guild_id, msg.author.id).await;
+    let departed = departed_creators(ctx, msg.guild_id, &reminders).await;
+
+    let enumerated_reminders = reminders.iter().enumerate().map(|(count, reminder)| {
+        reminder_ids.push(reminder.id);
+
+        format!(
+            "**{}**: '{}' *<#{}>* at <t:{}> (set by {})",
+            count + 1,
+            reminder.display_content(),
+            reminder.channel,
+            reminder.utc_time.timestamp(),
+            reminder.creator_display(&departed)
+        )
+    });
+
+    let _ = msg.channel_id.say_lines(&ctx, enumerated_reminders).await;
+    let _ = msg
+        .channel_id
+        .say(&ctx, lm.get(&user_data.language, "del/listed"))
+        .await;
+
+    let reply = msg
+        .channel_id
+        .await_reply(&ctx)
+        .author_id(msg.author.id)
+        .channel_id(msg.channel_id)
+        .await;
+
+    if let Some(content) = reply.map(|m| m.content.replace(",", " ")) {
+        let parts = content
+            .split(' ')
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<&str>>();
+
+        let valid_parts = parts
+            .iter()
+            .filter_map(|i| {
+                i.parse::<usize>()
+                    .ok()
+                    .filter(|val| val > &0)
+                    .map(|val| reminder_ids.get(val - 1))
+                    .flatten()
+            })
+            .map(|item| item.to_string())
+            .collect::<Vec<String>>();
+
+        if parts.len() == valid_parts.len() {
+            let joined = valid_parts.join(",");
+
+            let count_row = sqlx::query!(
+                "
+SELECT COUNT(1) AS count FROM reminders WHERE FIND_IN_SET(id, ?)
+                ",
+                joined
+            )
+            .fetch_one(&pool)
+            .timed("del count")
+            .await?;
+
+            // found before deleting, since deleting a reminder unlinks its heads-ups
+            let heads_up_ids = sqlx::query!(
+                "
+SELECT id FROM reminders WHERE FIND_IN_SET(heads_up_for, ?) AND NOT FIND_IN_SET(id, ?)
+                ",
+                joined,
+                joined
+            )
+            .fetch_all(&pool)
+            .await?
+            .iter()
+            .map(|row| row.id.to_string())
+            .collect::<Vec<String>>();
+
+            sqlx::query!(
+                "
+DELETE FROM reminders WHERE FIND_IN_SET(id, ?)
+                ",
+                joined
+            )
+            .execute(&pool)
+            .timed("del delete")
+            .await?;
+
+            let mut deleted_ids = valid_parts;
+            let mut count = count_row.count as u64;
+
+            if !heads_up_ids.is_empty() {
+                let prompt = lm
+                    .get(&user_data.language, "del/heads_up_prompt")
+                    .replace("{count}", &heads_up_ids.len().to_string());
+
+                if await_confirmation(ctx, msg, &lm, &user_data.language, &prompt).await {
+                    count += sqlx::query!(
+                        "
+DELETE FROM reminders WHERE FIND_IN_SET(id, ?)
+                        ",
+                        heads_up_ids.join(",")
+                    )
+                    .execute(&pool)
+                    .await?
+                    .rows_affected();
+
+                    deleted_ids.extend(heads_up_ids);
+                }
+            }
+
+            if let Some(deletion_log) = ctx.data.read().await.get::<DeletionLog>() {
+                for reminder in reminders
+                    .iter()
+                    .filter(|reminder| deleted_ids.contains(&reminder.id.to_string()))
+                {
+                    deletion_log.record(&reminder.uid, reminder.display_content(), msg.author.id);
+                }
+            }
+
+            if let Some(guild_id) = msg.guild_id {
+                Event::Delete.record(&pool, count, guild_id, msg.author.id);
+            }
+
+            let content =
+                lm.get(&user_data.language, "del/count")
+                    .replacen("{}", &count.to_string(), 1);
+
+            let _ = msg.channel_id.say(&ctx, content).await;
+        } else {
+            let content = lm
+                .get(&user_data.language, "del/count")
+                .replacen("{}", "0", 1);
+
+            let _ = msg.channel_id.say(&ctx, content).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// `del-uid <uid>`: delete one reminder by its UID, without the interactive listing. Only a
+/// reminder `del` would list here can be deleted
+#[command("del-uid")]
+#[permission_level(Managed)]
+async fn delete_uid(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let uid = args.trim();
+
+    if uid.is_empty() {
+        let prefix = ctx.prefix(msg.guild_id).await;
+
+        command_help(ctx, msg, lm, &prefix, &language, "del-uid").await;
+
+        return Ok(());
+    }
+
+    let reminder = Reminder::from_guild(ctx, msg.guild_id, msg.author.id)
+        .await
+        .into_iter()
+        .find(|reminder| reminder.uid == uid);
+
+    match reminder {
+        Some(reminder) => {
+            sqlx::query!(
+                "
+DELETE FROM reminders WHERE id = ?
+                ",
+                reminder.id
+            )
+            .execute(&pool)
+            .timed("del-uid delete")
+            .await?;
+
+            if let Some(deletion_log) = ctx.data.read().await.get::<DeletionLog>() {
+                deletion_log.record(&reminder.uid, reminder.display_content(), msg.author.id);
+            }
+
+            if let Some(guild_id) = msg.guild_id {
+                Event::Delete.record(&pool, 1, guild_id, msg.author.id);
+            }
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "del_uid/deleted").replace("{uid}", uid),
+                )
+                .await;
+        }
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "del_uid/not_found").replace("{uid}", uid),
+                )
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// `mute <uid> <time>|off`: keep sending a reminder, but without pinging anyone or notifying
+/// until the time given. Unlike pausing, nothing is held back
+#[command("mute")]
+#[permission_level(Managed)]
+async fn mute(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    let mut split = args.trim().splitn(2, char::is_whitespace);
+
+    let (uid, until) = match (split.next(), split.next().map(|until| until.trim())) {
+        (Some(uid), Some(until)) if !uid.is_empty() && !until.is_empty() => (uid, until),
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "mute").await;
+
+            return Ok(());
+        }
+    };
+
+    let reminder = match Reminder::from_guild(ctx, msg.guild_id, msg.author.id)
+        .await
+        .into_iter()
+        .find(|reminder| reminder.uid == uid)
+    {
+        Some(reminder) => reminder,
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "del_uid/not_found").replace("{uid}", uid),
+                )
+                .await;
+
+            return Ok(());
+        }
+    };
+
+    if until.eq_ignore_ascii_case("off") {
+        reminder.mute_pings(ctx, None).await;
+
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(&language, "mute/off").replace("{uid}", uid))
+            .await;
+
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64;
+
+    // bounded like reminder times, so the time always fits in a DATETIME
+    let muted_until = TimeParser::new(until, timezone)
+        .timestamp()
+        .ok()
+        .filter(|timestamp| *timestamp > now && *timestamp - now <= *MAX_TIME)
+        .and_then(|timestamp| NaiveDateTime::from_timestamp_opt(timestamp, 0));
+
+    match muted_until {
+        Some(muted_until) => {
+            reminder.mute_pings(ctx, Some(muted_until)).await;
+
+            let _ = msg
+                .channel_id
+                .say(
+                    &ctx,
+                    lm.get(&language, "mute/set")
+                        .replace("{uid}", uid)
+                        .replace("{time}", &format!("<t:{}:f>", muted_until.timestamp())),
+                )
+                .await;
+        }
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "mute/invalid_time"))
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+#[command("bump")]
+#[permission_level(Managed)]
+async fn bump(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    let mut args_iter = args.split_whitespace();
+
+    match (args_iter.next(), args_iter.next()) {
+        (Some(index), Some(duration)) => {
+            // indices match the listing shown by `del`
+            let reminders = Reminder::from_guild(ctx, msg.guild_id, msg.author.id).await;
+
+            let reminder = index
+                .parse::<usize>()
+                .ok()
+                .filter(|val| val > &0)
+                .map(|val| reminders.get(val - 1))
+                .flatten();
+
+            match (reminder, TimeParser::new(duration, timezone).displacement()) {
+                (None, _) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "bump/invalid_index"))
+                        .await;
+                }
+
+                (Some(_), Err(_)) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "bump/invalid_time"))
+                        .await;
+                }
+
+                (Some(reminder), Ok(displacement)) => {
+                    let new_time = reminder.utc_time.timestamp() + displacement;
+
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .expect("Time calculated as going backwards. Very bad")
+                        .as_secs() as i64;
+
+                    if new_time <= now {
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "bump/past"))
+                            .await;
+                    } else {
+                        sqlx::query!(
+                            "
+UPDATE reminders SET `utc_time` = DATE_ADD(`utc_time`, INTERVAL ? SECOND) WHERE id = ?
+                            ",
+                            displacement,
+                            reminder.id
+                        )
+                        .execute(&pool)
+                        .timed("bump update")
+                        .await?;
+
+                        let _ = msg
+                            .channel_id
+                            .say(
+                                &ctx,
+                                lm.get(&language, "bump/success")
+                                    .replace("{content}", reminder.display_content())
+                                    .replace("{time}", &format!("<t:{}>", new_time)),
+                            )
+                            .await;
+                    }
+                }
+            }
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "bump").await;
+        }
+    }
+
+    Ok(())
+}
+
+#[command("timer")]
+#[permission_level(Managed)]
+async fn timer(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    fn time_difference(start_time: NaiveDateTime) -> String {
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let now = NaiveDateTime::from_timestamp(unix_time, 0);
+
+        let delta = (now - start_time).num_seconds();
+
+        let (minutes, seconds) = delta.div_rem(&60);
+        let (hours, minutes) = minutes.div_rem(&60);
+        let (days, hours) = hours.div_rem(&24);
+
+        format!("{} days, {:02}:{:02}:{:02}", days, hours, minutes, seconds)
+    }
+
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let language = UserData::language_of(&msg.author, &pool).await;
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+
+    let mut args_iter = args.splitn(2, ' ');
+
+    let owner = msg
+        .guild_id
+        .map(|g| g.as_u64().to_owned())
+        .unwrap_or_else(|| msg.author.id.as_u64().to_owned());
+
+    match args_iter.next() {
+        Some("list") => {
+            let timers = Timer::from_owner(owner, &pool).await;
+
+            let _ = msg
+                .channel_id
+                .send_message(&ctx, |m| {
+                    m.embed(|e| {
+                        e.fields(timers.iter().map(|timer| {
+                            // timers in DMs are the user's own, so only say who started a guild's
+                            let creator = match timer.created_by {
+                                Some(user) if msg.guild_id.is_some() => format!("\n👤 <@{}>", user),
+
+                                _ => String::new(),
+                            };
+
+                            (
+                                &timer.name,
+                                format!(
+                                    "⏳ `{}`\n🕒 {}{}",
+                                    time_difference(timer.start_time),
+                                    timer.display_start(timezone),
+                                    creator
+                                ),
+                                false,
+                            )
+                        }))
+                    })
+                })
+                .await;
+        }
+
+        Some("mine") => {
+            let timers = Timer::from_creator(msg.author.id.as_u64().to_owned(), &pool).await;
+
+            if timers.is_empty() {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "timer/none"))
+                    .await;
+            } else {
+                let _ = msg
+                    .channel_id
+                    .send_message(&ctx, |m| {
+                        m.embed(|e| {
+                            e.fields(timers.iter().map(|timer| {
+                                // timers set in DMs are owned by the user, not a server
+                                let location = if timer.owner == *msg.author.id.as_u64() {
+                                    None
+                                } else {
+                                    GuildId(timer.owner).name(&ctx)
+                                };
+
+                                (
+                                    match location {
+                                        Some(guild_name) => {
+                                            format!("{} ({})", timer.name, guild_name)
+                                        }
+                                        None => timer.name.clone(),
+                                    },
+                                    format!(
+                                        "⏳ `{}`\n🕒 {}",
+                                        time_difference(timer.start_time),
+                                        timer.display_start(timezone)
+                                    ),
+                                    false,
+                                )
+                            }))
+                        })
+                    })
+                    .await;
+            }
+        }
+
+        Some("start") => {
+            let count = Timer::count_from_owner(owner, &pool).await;
+
+            if count >= 25 {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "timer/limit"))
+                    .await;
+            } else {
+                let name = match args_iter.next() {
+                    Some(name) => name.to_string(),
+
+                    // deleted timers can leave gaps, so find the first number not in use
+                    None => {
+                        let mut number = count + 1;
+
+                        while Timer::exists(owner, &format!("New timer #{}", number), &pool).await {
+                            number += 1;
+                        }
+
+                        format!("New timer #{}", number)
+                    }
+                };
+
+                if Timer::exists(owner, &name, &pool).await {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "timer/name_taken"))
+                        .await;
+                } else if name.len() <= 32 {
+                    Timer::create(&name, owner, msg.author.id.as_u64().to_owned(), &pool).await;
+
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "timer/success"))
+                        .await;
+                } else {
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&language, "timer/name_length")
+                                .replace("{}", &name.len().to_string()),
+                        )
+                        .await;
+                }
+            }
+        }
+
+        Some("rename") => {
+            let rename_args = args_iter.next().unwrap_or("");
+
+            // names can contain spaces, so the old name is whichever existing timer the arguments
+            // start with. The longest is taken, in case one name begins with another
+            let timers = Timer::from_owner(owner, &pool).await;
+
+            let renamed = timers
+                .iter()
+                .filter_map(|timer| {
+                    rename_args
+                        .strip_prefix(&timer.name)
+                        .filter(|rest| rest.starts_with(' '))
+                        .map(|rest| (&timer.name, rest.trim()))
+                })
+                .filter(|(_, new_name)| !new_name.is_empty())
+                .max_by_key(|(name, _)| name.len());
+
+            match renamed {
+                Some((name, new_name)) => {
+                    if new_name.len() > 32 {
+                        let _ = msg
+                            .channel_id
+                            .say(
+                                &ctx,
+                                lm.get(&language, "timer/name_length")
+                                    .replace("{}", &new_name.len().to_string()),
+                            )
+                            .await;
+                    } else if timers.iter().any(|timer| timer.name == new_name) {
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "timer/name_taken"))
+                            .await;
+                    } else {
+                        Timer::rename(owner, name, new_name, &pool).await;
+
+                        let _ = msg
+                            .channel_id
+                            .say(
+                                &ctx,
+                                lm.get(&language, "timer/renamed")
+                                    .replace("{name}", new_name),
+                            )
+                            .await;
+                    }
+                }
+
+                None => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "timer/not_found"))
+                        .await;
+                }
+            }
+        }
+
+        Some("delete") => {
+            if let Some(name) = args_iter.next() {
+                if Timer::delete(owner, name, &pool).await {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "timer/deleted"))
+                        .await;
+                } else {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "timer/not_found"))
+                        .await;
+                }
+            } else {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "timer/help"))
+                    .await;
             }
+        }
 
-            let content = lm.get(&user_data.language, "del/count").replacen(
-                "{}",
-                &count_row.count.to_string(),
-                1,
-            );
-
-            let _ = msg.channel_id.say(&ctx, content).await;
-        } else {
-            let content = lm
-                .get(&user_data.language, "del/count")
-                .replacen("{}", "0", 1);
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
 
-            let _ = msg.channel_id.say(&ctx, content).await;
+            command_help(ctx, msg, lm, &prefix, &language, "timer").await;
         }
     }
-}
-
-#[command("timer")]
-#[permission_level(Managed)]
-async fn timer(ctx: &Context, msg: &Message, args: String) {
-    fn time_difference(start_time: NaiveDateTime) -> String {
-        let unix_time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as i64;
-        let now = NaiveDateTime::from_timestamp(unix_time, 0);
-
-        let delta = (now - start_time).num_seconds();
-
-        let (minutes, seconds) = delta.div_rem(&60);
-        let (hours, minutes) = minutes.div_rem(&60);
-        let (days, hours) = hours.div_rem(&24);
 
-        format!("{} days, {:02}:{:02}:{:02}", days, hours, minutes, seconds)
-    }
+    Ok(())
+}
 
+#[command("template")]
+#[supports_dm(false)]
+#[permission_level(Restricted)]
+async fn template(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let language = UserData::language_of(&msg.author, &pool).await;
 
-    let mut args_iter = args.splitn(2, ' ');
+    let guild_id = msg.guild_id.unwrap().as_u64().to_owned();
 
-    let owner = msg
-        .guild_id
-        .map(|g| g.as_u64().to_owned())
-        .unwrap_or_else(|| msg.author.id.as_u64().to_owned());
+    let mut args_iter = args.splitn(2, ' ');
 
     match args_iter.next() {
         Some("list") => {
-            let timers = Timer::from_owner(owner, &pool).await;
-
-            let _ = msg
-                .channel_id
-                .send_message(&ctx, |m| {
-                    m.embed(|e| {
-                        e.fields(timers.iter().map(|timer| {
-                            (
-                                &timer.name,
-                                format!("⏳ `{}`", time_difference(timer.start_time)),
-                                false,
-                            )
-                        }))
-                    })
-                })
-                .await;
-        }
-
-        Some("start") => {
-            let count = Timer::count_from_owner(owner, &pool).await;
+            let templates = Template::from_guild(guild_id, &pool).await;
 
-            if count >= 25 {
+            if templates.is_empty() {
                 let _ = msg
                     .channel_id
-                    .say(&ctx, lm.get(&language, "timer/limit"))
+                    .say(&ctx, lm.get(&language, "template/none"))
                     .await;
             } else {
-                let name = args_iter
-                    .next()
-                    .map(|s| s.to_string())
-                    .unwrap_or(format!("New timer #{}", count + 1));
+                let units = displacement_units(&lm, &language);
+
+                let _ = msg
+                    .channel_id
+                    .send_message(&ctx, |m| {
+                        m.embed(|e| {
+                            e.color(*THEME_COLOR)
+                                .fields(templates.iter().map(|template| {
+                                    // embed field values are limited to 1024 characters
+                                    let content =
+                                        template.content.chars().take(900).collect::<String>();
+
+                                    (
+                                        &template.name,
+                                        match template.interval {
+                                            Some(interval) => format!(
+                                                "{}\n🔁 {}",
+                                                content,
+                                                longhand_displacement(interval as u64, &units)
+                                            ),
+                                            None => content,
+                                        },
+                                        false,
+                                    )
+                                }))
+                        })
+                        .allowed_mentions(|am| am.empty_parse())
+                    })
+                    .await;
+            }
+        }
+
+        Some("create") => match args_iter
+            .next()
+            .map(|rest| REGEX_TEMPLATE_COMMAND.captures(rest))
+            .flatten()
+        {
+            Some(captures) => {
+                let name = captures.name("name").unwrap().as_str().to_lowercase();
+                let content = captures.name("content").unwrap().as_str();
+
+                let timezone = UserData::timezone_of(&msg.author, &pool).await;
 
-                if name.len() <= 32 {
-                    Timer::create(&name, owner, &pool).await;
+                let interval = captures
+                    .name("interval")
+                    .map(|mat| TimeParser::new(mat.as_str(), timezone))
+                    .map(|parser| parser.displacement());
 
+                if !Template::valid_name(&name) {
                     let _ = msg
                         .channel_id
-                        .say(&ctx, lm.get(&language, "timer/success"))
+                        .say(&ctx, lm.get(&language, "template/invalid_name"))
+                        .await;
+                } else if Template::from_name(guild_id, &name, &pool).await.is_none()
+                    && Template::count_from_guild(guild_id, &pool).await >= *MAX_TEMPLATES
+                {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "template/limit"))
+                        .await;
+                } else if let Some(Err(_)) = interval {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "interval/invalid_interval"))
+                        .await;
+                } else if content.chars().count() > TEMPLATE_CONTENT_LIMIT {
+                    let _ = msg
+                        .channel_id
+                        .say(
+                            &ctx,
+                            lm.get(&language, "template/too_long")
+                                .replace("{max_length}", &TEMPLATE_CONTENT_LIMIT.to_string()),
+                        )
                         .await;
                 } else {
+                    Template::create(
+                        guild_id,
+                        &name,
+                        interval.transpose().unwrap_or(None),
+                        content,
+                        &pool,
+                    )
+                    .await?;
+
                     let _ = msg
                         .channel_id
                         .say(
                             &ctx,
-                            lm.get(&language, "timer/name_length")
-                                .replace("{}", &name.len().to_string()),
+                            lm.get(&language, "template/created")
+                                .replace("{name}", &name),
                         )
                         .await;
                 }
             }
-        }
 
-        Some("delete") => {
-            if let Some(name) = args_iter.next() {
-                let exists = sqlx::query!(
-                    "
-SELECT 1 as _r FROM timers WHERE owner = ? AND name = ?
-                    ",
-                    owner,
-                    name
-                )
-                .fetch_one(&pool)
-                .await;
+            None => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "template/help"))
+                    .await;
+            }
+        },
+
+        Some("embed") => match args_iter
+            .next()
+            .map(|rest| REGEX_TEMPLATE_EMBED_COMMAND.captures(rest.trim()))
+            .flatten()
+        {
+            Some(captures) => {
+                let name = captures.name("name").unwrap().as_str().to_lowercase();
+                let title = captures.name("title").unwrap().as_str();
+                let description = captures.name("description").map_or("", |m| m.as_str());
+
+                // `off` on its own removes the embed
+                let (title, description) =
+                    if title.eq_ignore_ascii_case("off") && description.is_empty() {
+                        ("", "")
+                    } else {
+                        (title, description)
+                    };
 
-                if exists.is_ok() {
-                    sqlx::query!(
-                        "
-DELETE FROM timers WHERE owner = ? AND name = ?
-                        ",
-                        owner,
-                        name
-                    )
-                    .execute(&pool)
-                    .await
-                    .unwrap();
+                let color = captures.name("color").map_or(*THEME_COLOR, |m| {
+                    u32::from_str_radix(m.as_str(), 16).unwrap()
+                });
+
+                match Template::from_name(guild_id, &name, &pool).await {
+                    Some(_)
+                        if title.chars().count() > TEMPLATE_TITLE_LIMIT
+                            || description.chars().count() > TEMPLATE_DESCRIPTION_LIMIT =>
+                    {
+                        let _ = msg
+                            .channel_id
+                            .say(
+                                &ctx,
+                                lm.get(&language, "template/embed_too_long")
+                                    .replace("{max_title}", &TEMPLATE_TITLE_LIMIT.to_string())
+                                    .replace(
+                                        "{max_description}",
+                                        &TEMPLATE_DESCRIPTION_LIMIT.to_string(),
+                                    ),
+                            )
+                            .await;
+                    }
+
+                    Some(template) => {
+                        template.set_embed(title, description, color, &pool).await?;
+
+                        let response = if title.is_empty() && description.is_empty() {
+                            "template/embed_removed"
+                        } else {
+                            "template/embed_set"
+                        };
 
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, response).replace("{name}", &name))
+                            .await;
+                    }
+
+                    None => {
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "template/not_found"))
+                            .await;
+                    }
+                }
+            }
+
+            None => {
+                let _ = msg
+                    .channel_id
+                    .say(&ctx, lm.get(&language, "template/help"))
+                    .await;
+            }
+        },
+
+        Some("delete") => {
+            if let Some(name) = args_iter.next() {
+                if Template::delete(guild_id, &name.to_lowercase(), &pool).await {
                     let _ = msg
                         .channel_id
-                        .say(&ctx, lm.get(&language, "timer/deleted"))
+                        .say(&ctx, lm.get(&language, "template/deleted"))
                         .await;
                 } else {
                     let _ = msg
                         .channel_id
-                        .say(&ctx, lm.get(&language, "timer/not_found"))
+                        .say(&ctx, lm.get(&language, "template/not_found"))
                         .await;
                 }
             } else {
                 let _ = msg
                     .channel_id
-                    .say(&ctx, lm.get(&language, "timer/help"))
+                    .say(&ctx, lm.get(&language, "template/help"))
+                    .await;
+            }
+        }
+
+        _ => {
+            let prefix = ctx.prefix(msg.guild_id).await;
+
+            command_help(ctx, msg, lm, &prefix, &language, "template").await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a reminder in the current channel from one of the guild's templates:
+/// `remind template <name> <time>`
+async fn remind_template(ctx: &Context, msg: &Message, args: &str) -> CommandResult {
+    let (pool, lm) = get_ctx_data(&ctx).await;
+
+    let timezone = UserData::timezone_of(&msg.author, &pool).await;
+    let language = UserData::language_of(&msg.author, &pool).await;
+
+    let mut args_iter = args.splitn(2, ' ');
+
+    let template = match (msg.guild_id, args_iter.next()) {
+        (Some(guild_id), Some(name)) => {
+            Template::from_name(*guild_id.as_u64(), &name.to_lowercase(), &pool).await
+        }
+
+        _ => None,
+    };
+
+    let time = args_iter.next().map(|time| time.trim()).unwrap_or("");
+
+    match template {
+        Some(template) if !time.is_empty() => {
+            let interval = template.interval.map(|interval| interval as i64);
+
+            if interval.is_some() && !check_subscription_on_message(&ctx, msg).await {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(&language, "interval/donor")
+                            .replace("{prefix}", &ctx.prefix(msg.guild_id).await),
+                    )
                     .await;
+
+                return Ok(());
+            }
+
+            let mut content = match Content::build(&template.content, msg).await {
+                Ok(content) => content,
+
+                Err(content_error) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, content_error.to_response()))
+                        .await;
+
+                    return Ok(());
+                }
+            };
+
+            if !check_everyone(ctx, msg, &lm, &language, &content).await {
+                return Ok(());
+            }
+
+            let scope = default_scope(ctx, msg).await;
+            let time_parser = TimeParser::new(time, timezone);
+
+            let timestamps = time_parser
+                .timestamp()
+                .ok()
+                .into_iter()
+                .collect::<Vec<i64>>();
+
+            if !check_duplicate(
+                ctx,
+                msg,
+                &pool,
+                &lm,
+                &language,
+                std::slice::from_ref(&scope),
+                &timestamps,
+                &content,
+            )
+            .await
+            {
+                return Ok(());
+            }
+
+            let res = create_reminder(
+                &ctx,
+                &pool,
+                msg.author.id,
+                msg.guild_id,
+                &scope,
+                &time_parser,
+                None,
+                interval,
+                &mut content,
+                ReminderFlags::default(),
+                msg.id,
+                time,
+            )
+            .await;
+
+            let (number, response) = match res {
+                Ok(reminder) => {
+                    template.apply_embed(reminder.id, &pool).await;
+
+                    (
+                        1,
+                        lm.get(&language, "remind/success")
+                            .replace("{location}", &scope.mention())
+                            .replace(
+                                "{offset}",
+                                &format!("<t:{}:R>", time_parser.timestamp().unwrap()),
+                            ),
+                    )
+                }
+
+                Err(e) => (
+                    0,
+                    describe_error(
+                        &lm,
+                        &language,
+                        e.to_response(),
+                        &e,
+                        guild_subscribed(ctx, *msg.author.id.as_u64(), msg.guild_id).await,
+                    ),
+                ),
+            };
+
+            let _ = msg
+                .channel_id
+                .send_message(&ctx, |m| {
+                    m.embed(|e| {
+                        e.title(
+                            lm.get(&language, "remind/title")
+                                .replace("{number}", &number.to_string()),
+                        )
+                        .description(response)
+                        .color(*THEME_COLOR)
+                    })
+                })
+                .await;
+        }
+
+        Some(_) => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "template/help"))
+                .await;
+        }
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(&language, "template/not_found"))
+                .await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Where a reminder goes when no channel or user is given: the user's own default channel if it
+/// is in this guild and they can still post there, then the guild's default channel if one is set
+/// and still exists, otherwise the channel the command was used in.
+async fn default_scope(ctx: &Context, msg: &Message) -> ReminderScope {
+    ReminderScope::Channel(default_channel(ctx, msg).await)
+}
+
+async fn default_channel(ctx: &Context, msg: &Message) -> u64 {
+    if let Some(guild_id) = msg.guild_id {
+        let (pool, _) = get_ctx_data(&ctx).await;
+
+        if let Some(channel_id) = UserData::default_channel_of(&msg.author, &pool).await {
+            let can_post = ChannelId(channel_id)
+                .to_channel_cached(&ctx)
+                .map(|channel| channel.guild())
+                .flatten()
+                .filter(|channel| channel.guild_id == guild_id)
+                .map(|channel| {
+                    channel
+                        .permissions_for_user(&ctx, msg.author.id)
+                        .map(|perms| perms.send_messages())
+                        .unwrap_or(false)
+                })
+                .unwrap_or(false);
+
+            if can_post {
+                return channel_id;
             }
         }
 
-        _ => {
-            let prefix = ctx.prefix(msg.guild_id).await;
+        if let Ok(guild_data) = ctx.guild_data(guild_id).await {
+            if let Some(channel_id) = guild_data.read().await.default_channel {
+                if ChannelId(channel_id).to_channel_cached(&ctx).is_some() {
+                    return channel_id;
+                }
+            }
+        }
+    }
+
+    msg.channel_id.into()
+}
+
+/// The most channels `--fallback` accepts on one reminder
+const FALLBACK_LIMIT: usize = 5;
+
+/// The channels given to `--fallback`, in order. `None` if there are too many, or any isn't a
+/// channel in this guild. Nothing is set up for them until `prepare_fallback`
+fn resolve_fallback(ctx: &Context, msg: &Message, channels: &str) -> Option<Vec<GuildChannel>> {
+    let guild_id = msg.guild_id?;
+    let mentions = channels.split(',').collect::<Vec<&str>>();
+
+    if mentions.len() > FALLBACK_LIMIT {
+        return None;
+    }
+
+    mentions
+        .into_iter()
+        .map(|mention| {
+            REGEX_CHANNEL
+                .captures(mention)?
+                .get(1)?
+                .as_str()
+                .parse::<u64>()
+                .ok()
+                .map(ChannelId)?
+                .to_channel_cached(&ctx)?
+                .guild()
+                .filter(|channel| channel.guild_id == guild_id)
+        })
+        .collect()
+}
+
+/// The internal IDs of the fallback channels, with their webhooks set up. Only done once the
+/// command is known to go ahead, so a rejected command doesn't leave webhooks behind
+async fn prepare_fallback(
+    ctx: &Context,
+    pool: &MySqlPool,
+    channels: &[GuildChannel],
+) -> Result<Vec<u32>, ReminderError> {
+    let mut channel_ids = vec![];
+
+    for guild_channel in channels {
+        let mut channel_data =
+            ChannelData::from_channel(Channel::Guild(guild_channel.clone()), pool)
+                .await
+                .map_err(|e| ReminderError::DatabaseError(e.to_string()))?;
 
-            command_help(ctx, msg, lm, &prefix, &language, "timer").await;
-        }
+        ensure_webhook(ctx, pool, &mut channel_data, guild_channel.clone()).await?;
+
+        channel_ids.push(channel_data.id);
     }
+
+    Ok(channel_ids)
 }
 
 #[derive(PartialEq)]
 enum RemindCommand {
     Remind,
     Interval,
+    Nag,
 }
 
+/// Where a reminder is sent. `User` goes to the user's DMs, while `Ping` is sent to a channel with
+/// the user mentioned at the start, as set with `--here`
 enum ReminderScope {
     User(u64),
     Channel(u64),
+    Ping(u64, u64),
 }
 
 impl ReminderScope {
@@ -554,19 +3029,44 @@ impl ReminderScope {
         match self {
             Self::User(id) => format!("<@{}>", id),
             Self::Channel(id) => format!("<#{}>", id),
+            Self::Ping(user_id, channel_id) => format!("<@{}> (<#{}>)", user_id, channel_id),
         }
     }
 }
 
+/// Options written with the reminder itself rather than updated afterwards, so that the sender
+/// can never pick up a reminder without them
+#[derive(Clone, Copy, Default)]
+struct ReminderFlags {
+    acknowledge: bool,
+    silent: bool,
+    crosspost: bool,
+    reply_to: Option<MessageId>,
+    /// The id of the reminder this is a heads-up for
+    heads_up_for: Option<u32>,
+    /// Edit the previously sent message each time, rather than sending a new one
+    edit_in_place: bool,
+    /// Repeat on the nth weekday of each month rather than every interval
+    monthly: Option<(Weekday, u32)>,
+}
+
 #[derive(PartialEq, Eq, Hash, Debug)]
 enum ReminderError {
     LongInterval,
     PastTime,
+    NudgedPastTime,
+    HeadsUpPastTime,
     ShortInterval,
     InvalidTag,
     InvalidTime,
     InvalidExpiration,
+    UserLimit,
+    GuildIntervalLimit,
+    DmOptOut,
+    ContentTooLong,
+    DuplicateRequest,
     DiscordError(String),
+    DatabaseError(String),
 }
 
 impl std::fmt::Display for ReminderError {
@@ -588,11 +3088,18 @@ impl ToResponse for ReminderError {
         match self {
             Self::LongInterval => "interval/long_interval",
             Self::PastTime => "remind/past_time",
+            Self::NudgedPastTime => "remind/nudged_past_time",
+            Self::HeadsUpPastTime => "remind/heads_up_past_time",
             Self::ShortInterval => "interval/short_interval",
             Self::InvalidTag => "remind/invalid_tag",
             Self::InvalidTime => "remind/invalid_time",
             Self::InvalidExpiration => "interval/invalid_expiration",
-            Self::DiscordError(_) => "remind/generic_error",
+            Self::UserLimit => "remind/user_limit",
+            Self::GuildIntervalLimit => "interval/guild_limit",
+            Self::DmOptOut => "remind/dm_opt_out",
+            Self::ContentTooLong => "remind/content_too_long",
+            Self::DuplicateRequest => "remind/duplicate_request",
+            Self::DiscordError(_) | Self::DatabaseError(_) => "remind/generic_error",
         }
     }
 
@@ -622,6 +3129,110 @@ impl<T> ToResponse for Result<T, ReminderError> {
     }
 }
 
+/// Record an error against the location it happened for, once per location
+fn push_location_error(
+    errors: &mut Vec<(String, ReminderError)>,
+    location: String,
+    err: ReminderError,
+) {
+    if !errors.iter().any(|(l, e)| *l == location && *e == err) {
+        errors.push((location, err));
+    }
+}
+
+/// Whether the guild gets the subscribed interval limit, because either the user setting the
+/// reminder or the guild's owner is subscribed
+async fn guild_subscribed(ctx: &Context, user_id: u64, guild_id: Option<GuildId>) -> bool {
+    let owner_id = guild_id
+        .map(|guild_id| guild_id.to_guild_cached(&ctx))
+        .flatten()
+        .map(|guild| guild.owner_id);
+
+    check_subscription(&ctx, user_id).await
+        || match owner_id {
+            Some(owner_id) => check_subscription(&ctx, owner_id).await,
+
+            None => false,
+        }
+}
+
+fn max_guild_intervals(subscribed: bool) -> Option<i64> {
+    if subscribed {
+        *MAX_GUILD_INTERVALS_SUBSCRIBED
+    } else {
+        *MAX_GUILD_INTERVALS
+    }
+}
+
+/// The string `key` for a reminder error, with the limits it can mention filled in. `subscribed`
+/// is whether the guild gets the subscribed interval limit
+fn describe_error(
+    lm: &LanguageManager,
+    language: &str,
+    key: &str,
+    err: &ReminderError,
+    subscribed: bool,
+) -> String {
+    let response = lm
+        .get(language, key)
+        .replace("{min_interval}", &MIN_INTERVAL.to_string())
+        .replace(
+            "{max_reminders}",
+            &MAX_USER_REMINDERS.unwrap_or(0).to_string(),
+        )
+        .replace(
+            "{max_intervals}",
+            &max_guild_intervals(subscribed).unwrap_or(0).to_string(),
+        )
+        .replace("{max_length}", &MAX_CONTENT_LENGTH.to_string());
+
+    match err {
+        ReminderError::DiscordError(s) | ReminderError::DatabaseError(s) => {
+            response.replace("{error}", s)
+        }
+
+        _ => response,
+    }
+}
+
+/// Join `lines` into at most `limit` characters. Lines that don't fit are left out and replaced by
+/// `more`, given how many were left out
+fn join_lines_within(lines: &[String], limit: usize, more: impl Fn(usize) -> String) -> String {
+    let mut joined = String::new();
+    let mut length = 0;
+
+    for (index, line) in lines.iter().enumerate() {
+        let separator = if joined.is_empty() { 0 } else { 1 };
+        let left_after = lines.len() - index - 1;
+
+        // room is kept to say how many lines are left out, in case the next doesn't fit
+        let reserved = if left_after > 0 {
+            more(left_after).chars().count() + 1
+        } else {
+            0
+        };
+
+        if length + separator + line.chars().count() + reserved > limit {
+            if !joined.is_empty() {
+                joined.push('\n');
+            }
+
+            joined.push_str(&more(lines.len() - index));
+
+            break;
+        }
+
+        if !joined.is_empty() {
+            joined.push('\n');
+        }
+
+        joined.push_str(line);
+        length += separator + line.chars().count();
+    }
+
+    joined
+}
+
 fn generate_uid() -> String {
     let mut generator: OsRng = Default::default();
 
@@ -723,9 +3334,340 @@ impl Content {
     }
 }
 
+/// Apply the guild's `everyone` setting to reminders that would mention @everyone or @here.
+/// Returns whether the reminder should be created.
+async fn check_everyone(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    content: &Content,
+) -> bool {
+    let mentions_everyone = ["@everyone", "@here", "<<everyone>>", "<<here>>"]
+        .iter()
+        .any(|mention| content.content.contains(mention));
+
+    let (guild, guild_data) = match (msg.guild(&ctx), msg.guild_id) {
+        (Some(guild), Some(guild_id)) if mentions_everyone => {
+            match ctx.guild_data(guild_id).await {
+                Ok(guild_data) => (guild, guild_data),
+                Err(_) => return true,
+            }
+        }
+
+        _ => return true,
+    };
+
+    let (confirm, restrict) = {
+        let guild_data = guild_data.read().await;
+
+        (guild_data.confirm_everyone, guild_data.restrict_everyone)
+    };
+
+    if restrict {
+        let permitted = guild
+            .member_permissions(&ctx, msg.author.id)
+            .await
+            .map(|permissions| permissions.mention_everyone())
+            .unwrap_or(false);
+
+        if !permitted {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(language, "everyone/no_permission"))
+                .await;
+
+            return false;
+        }
+    }
+
+    if !confirm {
+        return true;
+    }
+
+    await_confirmation(ctx, msg, lm, language, lm.get(language, "everyone/confirm")).await
+}
+
+/// Check that a command isn't setting reminders for more locations than `MAX_REMINDER_TARGETS`.
+/// Returns whether the reminders should be created.
+async fn check_target_count(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    scopes: &[ReminderScope],
+) -> bool {
+    if *MAX_REMINDER_TARGETS == 0 || scopes.len() <= *MAX_REMINDER_TARGETS {
+        return true;
+    }
+
+    let _ = msg
+        .channel_id
+        .say(
+            &ctx,
+            lm.get(language, "remind/too_many_targets")
+                .replace("{number}", &scopes.len().to_string())
+                .replace("{max}", &MAX_REMINDER_TARGETS.to_string()),
+        )
+        .await;
+
+    false
+}
+
+/// Check that every location is an announcement channel the bot can publish in. Publishing a
+/// webhook's message needs Manage Messages. Returns whether the reminders should be created.
+async fn check_publish(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    scopes: &[ReminderScope],
+) -> bool {
+    let current_user = ctx.cache.current_user_id();
+
+    for scope in scopes {
+        let channel = match scope {
+            ReminderScope::Channel(id) | ReminderScope::Ping(_, id) => {
+                ChannelId(*id).to_channel_cached(&ctx)
+            }
+
+            ReminderScope::User(_) => None,
+        };
+
+        let key = match channel {
+            Some(Channel::Guild(channel)) if channel.kind == ChannelType::News => {
+                match channel.permissions_for_user(&ctx, current_user) {
+                    Ok(permissions) if permissions.manage_messages() => continue,
+
+                    _ => "remind/publish_no_permission",
+                }
+            }
+
+            _ => "remind/publish_not_announcement",
+        };
+
+        let _ = msg
+            .channel_id
+            .say(
+                &ctx,
+                lm.get(language, key)
+                    .replace("{location}", &scope.mention()),
+            )
+            .await;
+
+        return false;
+    }
+
+    true
+}
+
+/// Find the message for `--reply`, from a message link or the ID of a message in this channel.
+/// The message has to exist and be in the one location the reminders are set for. Otherwise the
+/// user is told why, and no message is returned
+async fn resolve_reply(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    scopes: &[ReminderScope],
+    reference: &str,
+) -> Option<MessageId> {
+    let parsed = match REGEX_MESSAGE_LINK.captures(reference) {
+        Some(captures) => captures["channel"]
+            .parse::<u64>()
+            .ok()
+            .zip(captures["message"].parse::<u64>().ok()),
+
+        None => reference
+            .parse::<u64>()
+            .ok()
+            .map(|message_id| (*msg.channel_id.as_u64(), message_id)),
+    };
+
+    let (channel_id, message_id) = match parsed {
+        Some((channel_id, message_id)) => (ChannelId(channel_id), MessageId(message_id)),
+
+        None => {
+            let _ = msg
+                .channel_id
+                .say(&ctx, lm.get(language, "remind/reply_not_found"))
+                .await;
+
+            return None;
+        }
+    };
+
+    // a reply can only be sent in the replied-to message's own channel
+    for scope in scopes {
+        match scope {
+            ReminderScope::Channel(id) | ReminderScope::Ping(_, id)
+                if *id == *channel_id.as_u64() => {}
+
+            _ => {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(language, "remind/reply_wrong_channel")
+                            .replace("{location}", &scope.mention()),
+                    )
+                    .await;
+
+                return None;
+            }
+        }
+    }
+
+    if channel_id.message(&ctx, message_id).await.is_err() {
+        let _ = msg
+            .channel_id
+            .say(&ctx, lm.get(language, "remind/reply_not_found"))
+            .await;
+
+        return None;
+    }
+
+    Some(message_id)
+}
+
+/// Warn when a reminder with the same content already exists in one of the locations within
+/// `DUPLICATE_WINDOW` seconds of one of the times. Returns whether the reminders should be created.
+async fn check_duplicate(
+    ctx: &Context,
+    msg: &Message,
+    pool: &MySqlPool,
+    lm: &LanguageManager,
+    language: &str,
+    scopes: &[ReminderScope],
+    timestamps: &[i64],
+    content: &Content,
+) -> bool {
+    if *DUPLICATE_WINDOW == 0 {
+        return true;
+    }
+
+    for scope in scopes {
+        for timestamp in timestamps {
+            let count = match scope {
+                ReminderScope::Channel(channel_id)
+                | ReminderScope::Ping(_, channel_id) => sqlx::query!(
+                    "
+SELECT COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE
+    channels.channel = ? AND
+    reminders.content = ? AND
+    ABS(TIMESTAMPDIFF(SECOND, reminders.`utc_time`, DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND))) <= ?
+                    ",
+                    channel_id,
+                    content.content,
+                    timestamp,
+                    *DUPLICATE_WINDOW
+                )
+                .fetch_one(pool)
+                .timed("duplicate channel reminders")
+                .await
+                .map(|row| row.count),
+
+                ReminderScope::User(user_id) => sqlx::query!(
+                    "
+SELECT COUNT(1) AS count
+FROM reminders
+WHERE
+    reminders.channel_id = (SELECT dm_channel FROM users WHERE user = ?) AND
+    reminders.content = ? AND
+    ABS(TIMESTAMPDIFF(SECOND, reminders.`utc_time`, DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND))) <= ?
+                    ",
+                    user_id,
+                    content.content,
+                    timestamp,
+                    *DUPLICATE_WINDOW
+                )
+                .fetch_one(pool)
+                .timed("duplicate user reminders")
+                .await
+                .map(|row| row.count),
+            };
+
+            if count.unwrap_or(0) > 0 {
+                return await_confirmation(
+                    ctx,
+                    msg,
+                    lm,
+                    language,
+                    &lm.get(language, "remind/duplicate")
+                        .replace("{location}", &scope.mention()),
+                )
+                .await;
+            }
+        }
+    }
+
+    true
+}
+
+/// Ask the author to confirm an action with buttons. Returns false if they cancel or don't
+/// respond within 30 seconds.
+pub async fn await_confirmation(
+    ctx: &Context,
+    msg: &Message,
+    lm: &LanguageManager,
+    language: &str,
+    prompt: &str,
+) -> bool {
+    let prompt = msg
+        .channel_id
+        .send_message(&ctx, |m| {
+            m.content(prompt).components(|c| {
+                c.create_action_row(|row| {
+                    row.create_button(|b| {
+                        b.style(ButtonStyle::Danger)
+                            .label(lm.get(language, "confirm/confirm_button"))
+                            .custom_id("confirm:yes")
+                    })
+                    .create_button(|b| {
+                        b.style(ButtonStyle::Secondary)
+                            .label(lm.get(language, "confirm/cancel_button"))
+                            .custom_id("confirm:no")
+                    })
+                })
+            })
+        })
+        .await;
+
+    match prompt {
+        Ok(prompt) => {
+            let interaction = prompt
+                .await_component_interaction(&ctx)
+                .author_id(msg.author.id)
+                .timeout(Duration::from_secs(30))
+                .await;
+
+            let _ = prompt.delete(&ctx).await;
+
+            match interaction {
+                Some(interaction) => {
+                    let _ = interaction
+                        .create_interaction_response(&ctx, |r| {
+                            r.kind(InteractionResponseType::DeferredUpdateMessage)
+                        })
+                        .await;
+
+                    interaction.data.custom_id == "confirm:yes"
+                }
+
+                None => false,
+            }
+        }
+
+        Err(_) => false,
+    }
+}
+
 #[command("countdown")]
 #[permission_level(Managed)]
-async fn countdown(ctx: &Context, msg: &Message, args: String) {
+async fn countdown(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
     let language = UserData::language_of(&msg.author, &pool).await;
 
@@ -797,8 +3739,7 @@ INSERT INTO reminders (
                         target_ts
                     )
                     .execute(&pool)
-                    .await
-                    .unwrap();
+                    .await?;
 
                     let _ = msg.channel_id.send_message(&ctx, |m| {
                         m.embed(|e| {
@@ -844,18 +3785,26 @@ INSERT INTO reminders (
             )
             .await;
     }
+
+    Ok(())
 }
 
 #[command("remind")]
 #[permission_level(Managed)]
-async fn remind(ctx: &Context, msg: &Message, args: String) {
-    remind_command(ctx, msg, args, RemindCommand::Remind).await;
+async fn remind(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    remind_command(ctx, msg, args, RemindCommand::Remind).await
 }
 
 #[command("interval")]
 #[permission_level(Managed)]
-async fn interval(ctx: &Context, msg: &Message, args: String) {
-    remind_command(ctx, msg, args, RemindCommand::Interval).await;
+async fn interval(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    remind_command(ctx, msg, args, RemindCommand::Interval).await
+}
+
+#[command("nag")]
+#[permission_level(Managed)]
+async fn nag(ctx: &Context, msg: &Message, args: String) -> CommandResult {
+    remind_command(ctx, msg, args, RemindCommand::Nag).await
 }
 
 fn parse_mention_list(mentions: &str) -> Vec<ReminderScope> {
@@ -874,27 +3823,170 @@ fn parse_mention_list(mentions: &str) -> Vec<ReminderScope> {
         .collect::<Vec<ReminderScope>>()
 }
 
-async fn remind_command(ctx: &Context, msg: &Message, args: String, command: RemindCommand) {
+async fn remind_command(
+    ctx: &Context,
+    msg: &Message,
+    args: String,
+    command: RemindCommand,
+) -> CommandResult {
+    if let Some(template_args) = args.strip_prefix("template ") {
+        return remind_template(ctx, msg, template_args.trim_start()).await;
+    }
+
+    // options are only accepted first, so that content containing them is left alone
+    let mut args = args.as_str();
+    let (mut silent, mut publish, mut fire_now, mut here, mut edit) =
+        (false, false, false, false, false);
+    let mut reply = None;
+    let mut before = None;
+    let mut fallback = None;
+
+    loop {
+        let option = args.split_whitespace().next().unwrap_or("");
+
+        match option {
+            "--silent" => silent = true,
+            "--publish" => publish = true,
+            "--now" => fire_now = true,
+            "--here" => here = true,
+            "--edit" => edit = true,
+            "--reply" => {
+                // takes the message to reply to as well
+                args = args[option.len()..].trim_start();
+
+                let reference = args.split_whitespace().next().unwrap_or("");
+
+                reply = Some(reference);
+                args = args[reference.len()..].trim_start();
+
+                continue;
+            }
+            "--before" => {
+                // takes comma-separated lead times, each setting a heads-up that long beforehand
+                args = args[option.len()..].trim_start();
+
+                let leads = args.split_whitespace().next().unwrap_or("");
+
+                before = Some(leads);
+                args = args[leads.len()..].trim_start();
+
+                continue;
+            }
+            "--fallback" => {
+                // takes comma-separated channels, tried in order when the reminder's own fails
+                args = args[option.len()..].trim_start();
+
+                let channels = args.split_whitespace().next().unwrap_or("");
+
+                fallback = Some(channels);
+                args = args[channels.len()..].trim_start();
+
+                continue;
+            }
+            _ => break,
+        }
+
+        args = args[option.len()..].trim_start();
+    }
+
     let (pool, lm) = get_ctx_data(&ctx).await;
 
     let timezone = UserData::timezone_of(&msg.author, &pool).await;
     let language = UserData::language_of(&msg.author, &pool).await;
 
-    match REGEX_REMIND_COMMAND.captures(&args) {
+    let heads_up_leads = match before {
+        Some(leads) => {
+            let parsed = leads
+                .split(',')
+                .map(|lead| TimeParser::new(lead, timezone).displacement())
+                .collect::<Result<Vec<i64>, _>>();
+
+            match parsed {
+                Ok(leads) if !leads.is_empty() && leads.iter().all(|lead| *lead > 0) => leads,
+
+                _ => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&language, "remind/invalid_heads_up"))
+                        .await;
+
+                    return Ok(());
+                }
+            }
+        }
+
+        None => vec![],
+    };
+
+    let fallback_channels = match fallback {
+        Some(channels) => match resolve_fallback(ctx, msg, channels) {
+            Some(channels) => channels,
+
+            None => {
+                let _ = msg
+                    .channel_id
+                    .say(
+                        &ctx,
+                        lm.get(&language, "remind/invalid_fallback")
+                            .replace("{max}", &FALLBACK_LIMIT.to_string()),
+                    )
+                    .await;
+
+                return Ok(());
+            }
+        },
+
+        None => vec![],
+    };
+
+    match REGEX_REMIND_COMMAND.captures(args) {
         Some(captures) => {
             let parsed = parse_mention_list(captures.name("mentions").unwrap().as_str());
 
             let scopes = if parsed.is_empty() {
-                vec![ReminderScope::Channel(msg.channel_id.into())]
+                vec![default_scope(ctx, msg).await]
+            } else if here {
+                // users are pinged in the default channel rather than sent a DM
+                let channel_id = default_channel(ctx, msg).await;
+
+                parsed
+                    .into_iter()
+                    .map(|scope| match scope {
+                        ReminderScope::User(user_id) => ReminderScope::Ping(user_id, channel_id),
+
+                        scope => scope,
+                    })
+                    .collect()
             } else {
                 parsed
             };
 
-            let time_parser = TimeParser::new(captures.name("time").unwrap().as_str(), timezone);
+            // before anything that asks for confirmation, so a rejected command never prompts
+            if !check_target_count(ctx, msg, &lm, &language, &scopes).await {
+                return Ok(());
+            }
+
+            // an explicit `tz:` token overrides the user's timezone for absolute times only
+            let parse_timezone = captures
+                .name("timezone")
+                .map(|mat| mat.as_str().parse::<Tz>().ok())
+                .flatten()
+                .unwrap_or(timezone);
+
+            // several comma-separated times each create their own set of reminders
+            let time_parsers = captures
+                .name("time")
+                .unwrap()
+                .as_str()
+                .split(',')
+                .map(|time| time.trim())
+                .filter(|time| !time.is_empty())
+                .map(|time| (time, TimeParser::new(time, parse_timezone)))
+                .collect::<Vec<(&str, TimeParser)>>();
 
             let expires_parser = captures
                 .name("expires")
-                .map(|mat| TimeParser::new(mat.as_str(), timezone));
+                .map(|mat| TimeParser::new(mat.as_str(), parse_timezone));
 
             let interval_parser = captures
                 .name("interval")
@@ -903,7 +3995,12 @@ async fn remind_command(ctx: &Context, msg: &Message, args: String, command: Rem
                 .transpose();
 
             if let Ok(interval) = interval_parser {
-                if interval.is_some() && !check_subscription_on_message(&ctx, msg).await {
+                if interval.is_none() && command == RemindCommand::Nag {
+                    // a nag that doesn't repeat is just a reminder
+                    let prefix = ctx.prefix(msg.guild_id).await;
+
+                    command_help(ctx, msg, lm, &prefix, &language, "nag").await;
+                } else if interval.is_some() && !check_subscription_on_message(&ctx, msg).await {
                     // no patreon
                     let _ = msg
                         .channel_id
@@ -922,96 +4019,368 @@ async fn remind_command(ctx: &Context, msg: &Message, args: String, command: Rem
 
                     match content_res {
                         Ok(mut content) => {
-                            let mut ok_locations = vec![];
-                            let mut ok_reminders = vec![];
-                            let mut err_locations = vec![];
-                            let mut err_types = HashSet::new();
-
-                            for scope in scopes {
-                                let res = create_reminder(
-                                    &ctx,
-                                    &pool,
-                                    msg.author.id,
-                                    msg.guild_id,
-                                    &scope,
-                                    &time_parser,
-                                    expires_parser.as_ref(),
-                                    interval,
-                                    &mut content,
-                                )
-                                .await;
+                            if !check_everyone(ctx, msg, &lm, &language, &content).await {
+                                return Ok(());
+                            }
+
+                            if publish && !check_publish(ctx, msg, &lm, &language, &scopes).await {
+                                return Ok(());
+                            }
+
+                            let reply_to = match reply {
+                                Some(reference) => {
+                                    match resolve_reply(
+                                        ctx, msg, &lm, &language, &scopes, reference,
+                                    )
+                                    .await
+                                    {
+                                        Some(message_id) => Some(message_id),
+
+                                        None => return Ok(()),
+                                    }
+                                }
+
+                                None => None,
+                            };
+
+                            let timestamps = time_parsers
+                                .iter()
+                                .filter_map(|(_, time_parser)| time_parser.timestamp().ok())
+                                .collect::<Vec<i64>>();
+
+                            if !check_duplicate(
+                                ctx,
+                                msg,
+                                &pool,
+                                &lm,
+                                &language,
+                                &scopes,
+                                &timestamps,
+                                &content,
+                            )
+                            .await
+                            {
+                                return Ok(());
+                            }
+
+                            let fallback_channels =
+                                match prepare_fallback(ctx, &pool, &fallback_channels).await {
+                                    Ok(channel_ids) => channel_ids,
 
-                                match res {
                                     Err(e) => {
-                                        err_locations.push(scope);
-                                        err_types.insert(e);
+                                        let _ = msg
+                                            .channel_id
+                                            .say(
+                                                &ctx,
+                                                describe_error(
+                                                    &lm,
+                                                    &language,
+                                                    e.to_response(),
+                                                    &e,
+                                                    false,
+                                                ),
+                                            )
+                                            .await;
+
+                                        return Ok(());
+                                    }
+                                };
+
+                            let multiple_times = time_parsers.len() > 1;
+
+                            let flags = ReminderFlags {
+                                acknowledge: command == RemindCommand::Nag,
+                                silent,
+                                crosspost: publish,
+                                reply_to,
+                                // like `--now`, only repeating reminders send more than once
+                                edit_in_place: edit && interval.is_some(),
+                                ..Default::default()
+                            };
+
+                            let mut ok_count = 0;
+                            let mut success_lines = vec![];
+                            let mut issue_lines = vec![];
+                            // each failure is kept with its location, so the reply can say which
+                            // location failed for what reason
+                            let mut location_errors = vec![];
+
+                            // `--now` only applies to repeating reminders, and fires once per
+                            // location however many times were given
+                            let fire_now = fire_now && interval.is_some();
+                            let mut fired_now = HashSet::new();
+                            let unix_time = SystemTime::now()
+                                .duration_since(UNIX_EPOCH)
+                                .unwrap()
+                                .as_secs() as i64;
+
+                            for (time, time_parser) in &time_parsers {
+                                // label each line with its time when several were given
+                                let label = if multiple_times {
+                                    format!("`{}`: ", time)
+                                } else {
+                                    String::new()
+                                };
+
+                                let mut ok_locations = vec![];
+                                let mut err_locations = vec![];
+
+                                for scope in &scopes {
+                                    let res = create_reminder(
+                                        &ctx,
+                                        &pool,
+                                        msg.author.id,
+                                        msg.guild_id,
+                                        scope,
+                                        time_parser,
+                                        expires_parser.as_ref(),
+                                        interval,
+                                        &mut content,
+                                        flags,
+                                        msg.id,
+                                        time,
+                                    )
+                                    .await;
+
+                                    match res {
+                                        Err(e) => {
+                                            err_locations.push(scope);
+                                            push_location_error(
+                                                &mut location_errors,
+                                                format!("{}{}", label, scope.mention()),
+                                                e,
+                                            );
+                                        }
+
+                                        Ok(reminder) => {
+                                            // DMs have nowhere else to go
+                                            if !matches!(scope, ReminderScope::User(_)) {
+                                                reminder
+                                                    .set_fallback_channels(ctx, &fallback_channels)
+                                                    .await;
+                                            }
+
+                                            if time_parser.is_local() {
+                                                reminder
+                                                    .set_local_timezone(ctx, parse_timezone)
+                                                    .await;
+                                            }
+
+                                            // the time before any nudge, which create_reminder
+                                            // applies to the heads-ups in the same way
+                                            let time = time_parser.timestamp().unwrap();
+
+                                            for lead in &heads_up_leads {
+                                                if time - lead < unix_time {
+                                                    push_location_error(
+                                                        &mut location_errors,
+                                                        format!("{}{}", label, scope.mention()),
+                                                        ReminderError::HeadsUpPastTime,
+                                                    );
+
+                                                    continue;
+                                                }
+
+                                                // attachments only go with the reminder itself
+                                                let mut heads_up_content = Content {
+                                                    content: lm
+                                                        .get(&language, "remind/heads_up_content")
+                                                        .replace("{content}", &content.content),
+                                                    tts: content.tts,
+                                                    attachment: None,
+                                                    attachment_name: None,
+                                                };
+
+                                                let heads_up = create_reminder(
+                                                    &ctx,
+                                                    &pool,
+                                                    msg.author.id,
+                                                    msg.guild_id,
+                                                    scope,
+                                                    time - lead,
+                                                    expires_parser
+                                                        .as_ref()
+                                                        .map(|parser| parser.timestamp().ok())
+                                                        .flatten(),
+                                                    interval,
+                                                    &mut heads_up_content,
+                                                    ReminderFlags {
+                                                        silent,
+                                                        heads_up_for: Some(reminder.id),
+                                                        ..Default::default()
+                                                    },
+                                                    msg.id,
+                                                    &format!("{} before {}", time, lead),
+                                                )
+                                                .await;
+
+                                                match heads_up {
+                                                    Ok(heads_up) => {
+                                                        if time_parser.is_local() {
+                                                            heads_up
+                                                                .set_local_timezone(
+                                                                    ctx,
+                                                                    parse_timezone,
+                                                                )
+                                                                .await;
+                                                        }
+
+                                                        if !matches!(scope, ReminderScope::User(_))
+                                                        {
+                                                            heads_up
+                                                                .set_fallback_channels(
+                                                                    ctx,
+                                                                    &fallback_channels,
+                                                                )
+                                                                .await;
+                                                        }
+                                                    }
+
+                                                    Err(e) => {
+                                                        push_location_error(
+                                                            &mut location_errors,
+                                                            format!("{}{}", label, scope.mention()),
+                                                            e,
+                                                        );
+                                                    }
+                                                }
+                                            }
+
+                                            if fire_now && fired_now.insert(scope.mention()) {
+                                                // a separate one-off reminder, so that the
+                                                // repeating one keeps its own schedule
+                                                let _ = create_reminder(
+                                                    &ctx,
+                                                    &pool,
+                                                    msg.author.id,
+                                                    msg.guild_id,
+                                                    scope,
+                                                    unix_time,
+                                                    None,
+                                                    None,
+                                                    &mut content,
+                                                    // a one-off, so it is never
+                                                    // acknowledged or edited
+                                                    ReminderFlags {
+                                                        acknowledge: false,
+                                                        edit_in_place: false,
+                                                        ..flags
+                                                    },
+                                                    msg.id,
+                                                    "now",
+                                                )
+                                                .await;
+                                            }
+
+                                            ok_locations.push(scope);
+                                        }
                                     }
+                                }
+
+                                ok_count += ok_locations.len();
+
+                                match ok_locations.len() {
+                                    0 => {}
+                                    1 => success_lines.push(format!(
+                                        "{}{}",
+                                        label,
+                                        lm.get(&language, "remind/success")
+                                            .replace("{location}", &ok_locations[0].mention())
+                                            .replace(
+                                                "{offset}",
+                                                &format!(
+                                                    "<t:{}:R>",
+                                                    time_parser.timestamp().unwrap()
+                                                ),
+                                            )
+                                    )),
+                                    n => success_lines.push(format!(
+                                        "{}{}",
+                                        label,
+                                        lm.get(&language, "remind/success_bulk")
+                                            .replace("{number}", &n.to_string())
+                                            .replace(
+                                                "{location}",
+                                                &ok_locations
+                                                    .iter()
+                                                    .map(|l| l.mention())
+                                                    .collect::<Vec<String>>()
+                                                    .join(", "),
+                                            )
+                                            .replace(
+                                                "{offset}",
+                                                &format!(
+                                                    "<t:{}:R>",
+                                                    time_parser.timestamp().unwrap()
+                                                ),
+                                            )
+                                    )),
+                                }
 
-                                    Ok(id) => {
-                                        ok_locations.push(scope);
-                                        ok_reminders.push(id);
-                                    }
+                                match err_locations.len() {
+                                    0 => {}
+                                    1 => issue_lines.push(format!(
+                                        "{}{}",
+                                        label,
+                                        lm.get(&language, "remind/issue")
+                                            .replace("{location}", &err_locations[0].mention())
+                                    )),
+                                    n => issue_lines.push(format!(
+                                        "{}{}",
+                                        label,
+                                        lm.get(&language, "remind/issue_bulk")
+                                            .replace("{number}", &n.to_string())
+                                            .replace(
+                                                "{location}",
+                                                &err_locations
+                                                    .iter()
+                                                    .map(|l| l.mention())
+                                                    .collect::<Vec<String>>()
+                                                    .join(", "),
+                                            )
+                                    )),
                                 }
                             }
 
-                            let success_part = match ok_locations.len() {
-                                0 => "".to_string(),
-                                1 => lm
-                                    .get(&language, "remind/success")
-                                    .replace("{location}", &ok_locations[0].mention())
-                                    .replace(
-                                        "{offset}",
-                                        &format!("<t:{}:R>", time_parser.timestamp().unwrap()),
-                                    ),
-                                n => lm
-                                    .get(&language, "remind/success_bulk")
-                                    .replace("{number}", &n.to_string())
-                                    .replace(
-                                        "{location}",
-                                        &ok_locations
-                                            .iter()
-                                            .map(|l| l.mention())
-                                            .collect::<Vec<String>>()
-                                            .join(", "),
+                            let success_part = success_lines.join("\n");
+
+                            // only looked up when an error could need it
+                            let subscribed = !location_errors.is_empty()
+                                && guild_subscribed(ctx, *msg.author.id.as_u64(), msg.guild_id)
+                                    .await;
+
+                            let issue_part = issue_lines.join("\n");
+
+                            let error_lines = location_errors
+                                .iter()
+                                .map(|(location, err)| {
+                                    format!(
+                                        "{}: {}",
+                                        location,
+                                        describe_error(
+                                            &lm,
+                                            &language,
+                                            err.to_response(),
+                                            err,
+                                            subscribed,
+                                        )
                                     )
-                                    .replace(
-                                        "{offset}",
-                                        &format!("<t:{}:R>", time_parser.timestamp().unwrap()),
-                                    ),
-                            };
+                                })
+                                .collect::<Vec<String>>();
+
+                            // every location can fail for a different reason, so the errors get
+                            // whatever room the rest of the description leaves
+                            let error_limit = EMBED_DESCRIPTION_LIMIT.saturating_sub(
+                                success_part.chars().count() + issue_part.chars().count() + 3,
+                            );
 
                             let error_part = format!(
                                 "{}\n{}",
-                                match err_locations.len() {
-                                    0 => "".to_string(),
-                                    1 => lm
-                                        .get(&language, "remind/issue")
-                                        .replace("{location}", &err_locations[0].mention()),
-                                    n => lm
-                                        .get(&language, "remind/issue_bulk")
-                                        .replace("{number}", &n.to_string())
-                                        .replace(
-                                            "{location}",
-                                            &err_locations
-                                                .iter()
-                                                .map(|l| l.mention())
-                                                .collect::<Vec<String>>()
-                                                .join(", "),
-                                        ),
-                                },
-                                err_types
-                                    .iter()
-                                    .map(|err| match err {
-                                        ReminderError::DiscordError(s) => lm
-                                            .get(&language, err.to_response())
-                                            .replace("{error}", &s),
-
-                                        _ => lm
-                                            .get(&language, err.to_response())
-                                            .replace("{min_interval}", &*MIN_INTERVAL.to_string()),
-                                    })
-                                    .collect::<Vec<String>>()
-                                    .join("\n")
+                                issue_part,
+                                join_lines_within(&error_lines, error_limit, |count| {
+                                    lm.get(&language, "remind/more_errors")
+                                        .replace("{count}", &count.to_string())
+                                })
                             );
 
                             let _ = msg
@@ -1019,10 +4388,8 @@ async fn remind_command(ctx: &Context, msg: &Message, args: String, command: Rem
                                 .send_message(&ctx, |m| {
                                     m.embed(|e| {
                                         e.title(
-                                            lm.get(&language, "remind/title").replace(
-                                                "{number}",
-                                                &ok_locations.len().to_string(),
-                                            ),
+                                            lm.get(&language, "remind/title")
+                                                .replace("{number}", &ok_count.to_string()),
                                         )
                                         .description(format!("{}\n\n{}", success_part, error_part))
                                         .color(*THEME_COLOR)
@@ -1073,82 +4440,240 @@ async fn remind_command(ctx: &Context, msg: &Message, args: String, command: Rem
                 RemindCommand::Interval => {
                     command_help(ctx, msg, lm, &prefix, &language, "interval").await
                 }
+
+                RemindCommand::Nag => command_help(ctx, msg, lm, &prefix, &language, "nag").await,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// How a `natural` command was split up, kept so it can be echoed back to the user.
+struct NaturalComponents<'a> {
+    time: &'a str,
+    content: &'a str,
+    interval: Option<(&'a str, NaturalInterval)>,
+    expires: Option<&'a str>,
+}
+
+impl<'a> NaturalComponents<'a> {
+    fn from_captures(
+        captures: &Captures<'a>,
+        interval_regex: &Regex,
+        allow_repeating: bool,
+    ) -> Self {
+        let time = captures.name("time").unwrap().as_str();
+        let rest_content = captures.name("msg").unwrap().as_str();
+
+        let secondary_captures = if allow_repeating {
+            interval_regex.captures(rest_content)
+        } else {
+            None
+        };
+
+        match secondary_captures {
+            Some(secondary_captures) => {
+                let interval = secondary_captures
+                    .name("interval")
+                    .map(|interval_crop| {
+                        natural_interval(interval_crop.as_str())
+                            .map(|interval| (interval_crop.as_str(), interval))
+                    })
+                    .flatten();
+
+                Self {
+                    time,
+                    content: if interval.is_some() {
+                        secondary_captures.name("msg").unwrap().as_str()
+                    } else {
+                        rest_content
+                    },
+                    interval,
+                    expires: secondary_captures.name("expires").map(|mat| mat.as_str()),
+                }
             }
+
+            None => Self {
+                time,
+                content: rest_content,
+                interval: None,
+                expires: None,
+            },
         }
     }
+
+    /// Shown in an embed footer, so cut to the footer limit
+    fn describe(&self, lm: &LanguageManager, language: &str) -> String {
+        lm.get(language, "natural/understood")
+            .replace("{time}", self.time)
+            .replace("{message}", self.content)
+            .replace(
+                "{interval}",
+                self.interval.map_or("-", |(interval, _)| interval),
+            )
+            .replace("{expires}", self.expires.unwrap_or("-"))
+            .chars()
+            .take(EMBED_FOOTER_LIMIT)
+            .collect()
+    }
+}
+
+/// The user's last `natural` input, if it was used recently enough to repeat
+async fn last_natural(ctx: &Context, user: UserId) -> Option<String> {
+    let last_natural = ctx.data.read().await.get::<LastNatural>().cloned().unwrap();
+
+    let lock = last_natural.read().await;
+
+    lock.get(&user)
+        .filter(|(_, used)| used.elapsed() < LAST_NATURAL_TTL)
+        .map(|(args, _)| args.clone())
+}
+
+async fn remember_natural(ctx: &Context, user: UserId, args: &str) {
+    let last_natural = ctx.data.read().await.get::<LastNatural>().cloned().unwrap();
+
+    let mut lock = last_natural.write().await;
+
+    lock.retain(|_, (_, used)| used.elapsed() < LAST_NATURAL_TTL);
+    lock.insert(user, (args.to_string(), Instant::now()));
 }
 
 #[command("natural")]
 #[permission_level(Managed)]
-async fn natural(ctx: &Context, msg: &Message, args: String) {
+async fn natural(ctx: &Context, msg: &Message, args: String) -> CommandResult {
     let (pool, lm) = get_ctx_data(&ctx).await;
 
-    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await.unwrap();
+    let user_data = UserData::from_user(&msg.author, &ctx, &pool).await?;
 
-    match REGEX_NATURAL_COMMAND_1.captures(&args) {
-        Some(captures) => {
-            let (expires, interval, string_content) =
-                if check_subscription_on_message(&ctx, msg).await {
-                    let rest_content = captures.name("msg").unwrap().as_str();
-
-                    match REGEX_NATURAL_COMMAND_2.captures(rest_content) {
-                        Some(secondary_captures) => {
-                            let expires =
-                                if let Some(expires_crop) = secondary_captures.name("expires") {
-                                    natural_parser(expires_crop.as_str(), &user_data.timezone).await
-                                } else {
-                                    None
-                                };
+    // guilds can replace the words commands are split on
+    let guild_regexes = match msg.guild_id {
+        Some(guild_id) => ctx.natural_regexes(guild_id).await?,
 
-                            let interval =
-                                if let Some(interval_crop) = secondary_captures.name("interval") {
-                                    humantime::parse_duration(interval_crop.as_str())
-                                        .or_else(|_| {
-                                            humantime::parse_duration(&format!(
-                                                "1 {}",
-                                                interval_crop.as_str()
-                                            ))
-                                        })
-                                        .map(|duration| duration.as_secs() as i64)
-                                        .ok()
-                                } else {
-                                    None
-                                };
+        None => None,
+    };
 
-                            (
-                                expires,
-                                interval,
-                                if interval.is_some() {
-                                    secondary_captures.name("msg").unwrap().as_str()
-                                } else {
-                                    rest_content
-                                },
-                            )
-                        }
+    let (command_regex, interval_regex) = match &guild_regexes {
+        Some(regexes) => (&regexes.0, &regexes.1),
 
-                        None => (None, None, rest_content),
-                    }
-                } else {
-                    (None, None, captures.name("msg").unwrap().as_str())
-                };
+        None => (&*REGEX_NATURAL_COMMAND_1, &*REGEX_NATURAL_COMMAND_2),
+    };
+
+    // `natural again +1h` repeats the last input with its time moved by the offset. Offsets are
+    // always from the original input, so repeating twice doesn't add them up
+    let again = args
+        .strip_prefix("again")
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .filter(|_| !command_regex.is_match(&args))
+        .map(|rest| rest.trim().to_string());
+
+    let (args, offset) = match again {
+        Some(offset) => {
+            let offset = if offset.is_empty() {
+                Ok(0)
+            } else if offset.contains(|c: char| c == ':' || c == '/') {
+                Err(())
+            } else {
+                TimeParser::new(offset.trim_start_matches('+'), user_data.timezone())
+                    .displacement()
+                    .map_err(|_| ())
+            };
+
+            match (last_natural(ctx, msg.author.id).await, offset) {
+                (Some(last), Ok(offset)) => (last, offset),
+
+                (None, _) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&user_data.language, "natural/again_none"))
+                        .await;
+
+                    return Ok(());
+                }
+
+                (Some(_), Err(_)) => {
+                    let _ = msg
+                        .channel_id
+                        .say(&ctx, lm.get(&user_data.language, "natural/again_invalid"))
+                        .await;
+
+                    return Ok(());
+                }
+            }
+        }
+
+        None => (args, 0),
+    };
+
+    match command_regex.captures(&args) {
+        Some(captures) => {
+            let components = NaturalComponents::from_captures(
+                &captures,
+                interval_regex,
+                check_subscription_on_message(&ctx, msg).await,
+            );
+
+            let expires = if let Some(expires_crop) = components.expires {
+                natural_parser(expires_crop, &user_data.timezone).await
+            } else {
+                None
+            };
+
+            let natural_interval = components.interval.map(|(_, interval)| interval);
+            let interval = natural_interval.map(|interval| interval.seconds());
+            let understood = components.describe(&lm, &user_data.language);
 
             let location_ids = if let Some(mentions) = captures.name("mentions").map(|m| m.as_str())
             {
                 parse_mention_list(mentions)
             } else {
-                vec![ReminderScope::Channel(msg.channel_id.into())]
+                vec![default_scope(ctx, msg).await]
             };
 
-            if let Some(timestamp) =
-                natural_parser(captures.name("time").unwrap().as_str(), &user_data.timezone).await
+            // before anything that asks for confirmation, so a rejected command never prompts
+            if !check_target_count(ctx, msg, &lm, &user_data.language, &location_ids).await {
+                return Ok(());
+            }
+
+            if let Some(timestamp) = natural_parser(components.time, &user_data.timezone)
+                .await
+                .map(|timestamp| timestamp + offset)
+                .map(|timestamp| match natural_interval {
+                    // "every friday" starts on the next friday, not whenever the time said
+                    Some(interval) => interval.first_occurrence(timestamp, user_data.timezone()),
+
+                    None => timestamp,
+                })
             {
-                let content_res = Content::build(string_content, msg).await;
+                remember_natural(ctx, msg.author.id, &args).await;
+
+                let content_res = Content::build(components.content, msg).await;
 
                 match content_res {
                     Ok(mut content) => {
+                        if !check_everyone(ctx, msg, &lm, &user_data.language, &content).await {
+                            return Ok(());
+                        }
+
+                        if !check_duplicate(
+                            ctx,
+                            msg,
+                            &pool,
+                            &lm,
+                            &user_data.language,
+                            &location_ids,
+                            &[timestamp],
+                            &content,
+                        )
+                        .await
+                        {
+                            return Ok(());
+                        }
+
                         let mut ok_locations = vec![];
+                        // each failure is kept with its location, so the reply can say which
+                        // location failed for what reason
                         let mut err_locations = vec![];
-                        let mut err_types = HashSet::new();
 
                         for scope in location_ids {
                             let res = create_reminder(
@@ -1161,12 +4686,23 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
                                 expires,
                                 interval,
                                 &mut content,
+                                ReminderFlags {
+                                    monthly: match natural_interval {
+                                        Some(NaturalInterval::Monthly { weekday, nth }) => {
+                                            Some((weekday, nth))
+                                        }
+
+                                        _ => None,
+                                    },
+                                    ..Default::default()
+                                },
+                                msg.id,
+                                components.time,
                             )
                             .await;
 
                             if let Err(e) = res {
-                                err_locations.push(scope);
-                                err_types.insert(e);
+                                err_locations.push((scope, e));
                             } else {
                                 ok_locations.push(scope);
                             }
@@ -1192,13 +4728,17 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
                                 .replace("{offset}", &format!("<t:{}:R>", timestamp)),
                         };
 
+                        // only looked up when an error could need it
+                        let subscribed = !err_locations.is_empty()
+                            && guild_subscribed(ctx, *msg.author.id.as_u64(), msg.guild_id).await;
+
                         let error_part = format!(
                             "{}\n{}",
                             match err_locations.len() {
                                 0 => "".to_string(),
                                 1 => lm
                                     .get(&user_data.language, "remind/issue")
-                                    .replace("{location}", &err_locations[0].mention()),
+                                    .replace("{location}", &err_locations[0].0.mention()),
                                 n => lm
                                     .get(&user_data.language, "remind/issue_bulk")
                                     .replace("{number}", &n.to_string())
@@ -1206,21 +4746,25 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
                                         "{location}",
                                         &err_locations
                                             .iter()
-                                            .map(|l| l.mention())
+                                            .map(|(l, _)| l.mention())
                                             .collect::<Vec<String>>()
                                             .join(", "),
                                     ),
                             },
-                            err_types
+                            err_locations
                                 .iter()
-                                .map(|err| match err {
-                                    ReminderError::DiscordError(s) => lm
-                                        .get(&user_data.language, err.to_response_natural())
-                                        .replace("{error}", &s),
-
-                                    _ => lm
-                                        .get(&user_data.language, err.to_response_natural())
-                                        .to_string(),
+                                .map(|(location, err)| {
+                                    format!(
+                                        "{}: {}",
+                                        location.mention(),
+                                        describe_error(
+                                            &lm,
+                                            &user_data.language,
+                                            err.to_response_natural(),
+                                            err,
+                                            subscribed
+                                        )
+                                    )
                                 })
                                 .collect::<Vec<String>>()
                                 .join("\n")
@@ -1235,6 +4779,7 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
                                             .replace("{number}", &ok_locations.len().to_string()),
                                     )
                                     .description(format!("{}\n\n{}", success_part, error_part))
+                                    .footer(|f| f.text(understood))
                                     .color(*THEME_COLOR)
                                 })
                             })
@@ -1269,6 +4814,7 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
                                     .replace("{number}", "0"),
                             )
                             .description(lm.get(&user_data.language, "natural/invalid_time"))
+                            .footer(|f| f.text(understood))
                             .color(*THEME_COLOR)
                         })
                     })
@@ -1288,8 +4834,131 @@ async fn natural(ctx: &Context, msg: &Message, args: String) {
             .await;
         }
     }
+
+    Ok(())
+}
+
+/// Check an interval against `MIN_INTERVAL` and `MAX_TIME`.
+///
+/// The channel nudge only shifts the first send: it is added to `utc_time` once, and each repeat
+/// is `interval` after the previous one. The time between sends is therefore always exactly
+/// `interval`, so the interval alone decides whether a reminder repeats too often.
+fn check_interval(interval: Option<i64>) -> Result<(), ReminderError> {
+    match interval {
+        Some(interval) if interval < *MIN_INTERVAL => Err(ReminderError::ShortInterval),
+        Some(interval) if interval > *MAX_TIME => Err(ReminderError::LongInterval),
+        _ => Ok(()),
+    }
+}
+
+enum RequestClaim {
+    /// The request is new, and this call must create its reminder
+    Claimed,
+    /// The request was seen before and its reminder already exists
+    Existing(Reminder),
+    /// The request can't be told apart from others, so isn't tracked
+    Untracked,
+}
+
+/// Claim a request, so that it creates its reminder only once within `IDEMPOTENCY_WINDOW`. Discord
+/// can deliver a message again after a reconnect, and creating its reminders a second time would
+/// duplicate them. The claim is an insert into a unique key, so only one delivery can win it
+async fn claim_request(
+    ctx: &Context,
+    pool: &MySqlPool,
+    source: MessageId,
+    request: &str,
+) -> Result<RequestClaim, ReminderError> {
+    // messages made up by the bot, such as from aliases, all share the ID 0
+    if *IDEMPOTENCY_WINDOW == 0 || source == MessageId(0) {
+        return Ok(RequestClaim::Untracked);
+    }
+
+    let _ = sqlx::query!(
+        "
+DELETE FROM reminder_requests WHERE created_at < DATE_SUB(NOW(), INTERVAL ? SECOND)
+        ",
+        *IDEMPOTENCY_WINDOW
+    )
+    .execute(pool)
+    .timed("reminder_requests expire")
+    .await;
+
+    // hashed, since the location and time given can make the request longer than the column
+    let claimed = sqlx::query!(
+        "
+INSERT IGNORE INTO reminder_requests (request) VALUES (SHA2(?, 256))
+        ",
+        request
+    )
+    .execute(pool)
+    .timed("reminder_requests claim")
+    .await
+    .map_err(|e| ReminderError::DatabaseError(e.to_string()))?
+    .rows_affected()
+        > 0;
+
+    if claimed {
+        return Ok(RequestClaim::Claimed);
+    }
+
+    let uid = sqlx::query!(
+        "
+SELECT reminders.uid
+FROM reminder_requests
+INNER JOIN reminders ON reminders.id = reminder_requests.reminder_id
+WHERE reminder_requests.request = SHA2(?, 256)
+        ",
+        request
+    )
+    .fetch_one(pool)
+    .timed("reminder_requests lookup")
+    .await
+    .ok()
+    .map(|row| row.uid);
+
+    // without a reminder yet, the other delivery is still creating it
+    match uid {
+        Some(uid) => Reminder::from_uid(ctx, uid)
+            .await
+            .map(RequestClaim::Existing)
+            .ok_or(ReminderError::DuplicateRequest),
+
+        None => Err(ReminderError::DuplicateRequest),
+    }
+}
+
+/// Record the reminder a claimed request created, or release the claim if it failed so that the
+/// request can be tried again
+async fn complete_request(pool: &MySqlPool, request: &str, reminder_id: Option<u32>) {
+    match reminder_id {
+        Some(reminder_id) => {
+            let _ = sqlx::query!(
+                "
+UPDATE reminder_requests SET reminder_id = ? WHERE request = SHA2(?, 256)
+                ",
+                reminder_id,
+                request
+            )
+            .execute(pool)
+            .await;
+        }
+
+        None => {
+            let _ = sqlx::query!(
+                "
+DELETE FROM reminder_requests WHERE request = SHA2(?, 256)
+                ",
+                request
+            )
+            .execute(pool)
+            .await;
+        }
+    }
 }
 
+/// Create a reminder, unless `source` already created one for the same location and `time_arg`,
+/// the time as it was given in the message. The existing reminder is returned in that case
 async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
     ctx: &Context,
     pool: &MySqlPool,
@@ -1300,17 +4969,170 @@ async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
     expires_parser: Option<T>,
     interval: Option<i64>,
     content: &mut Content,
+    flags: ReminderFlags,
+    source: MessageId,
+    time_arg: &str,
+) -> Result<Reminder, ReminderError> {
+    // one message can set reminders for several locations and times, so the request is
+    // identified by all three
+    let request = format!("{}:{}:{}", source, scope_id.mention(), time_arg);
+
+    let claimed = match claim_request(ctx, pool, source, &request).await? {
+        RequestClaim::Claimed => true,
+
+        RequestClaim::Existing(reminder) => return Ok(reminder),
+
+        RequestClaim::Untracked => false,
+    };
+
+    let res = insert_reminder(
+        ctx,
+        pool,
+        user_id,
+        guild_id,
+        scope_id,
+        time_parser,
+        expires_parser,
+        interval,
+        content,
+        flags,
+    )
+    .await;
+
+    if claimed {
+        complete_request(
+            pool,
+            &request,
+            res.as_ref().ok().map(|reminder| reminder.id),
+        )
+        .await;
+    }
+
+    res
+}
+
+/// When a reminder set for `time_pre` is first sent, once moved by the channel's `nudge`. Repeats
+/// keep the interval's cadence, so only the first send is moved. Times up to `grace` seconds
+/// before `now` are sent straight away rather than rejected
+fn send_time(time_pre: i64, nudge: i64, now: i64, grace: i64) -> Result<i64, ReminderError> {
+    let time = time_pre + nudge;
+
+    if time >= now - grace {
+        Ok(time.max(now))
+    } else if time < 0 {
+        // case required for if python returns -1
+        Err(ReminderError::InvalidTime)
+    } else if time_pre >= now - grace {
+        // the time given was fine, but the channel's negative nudge moved it into the past
+        Err(ReminderError::NudgedPastTime)
+    } else {
+        Err(ReminderError::PastTime)
+    }
+}
+
+async fn insert_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
+    ctx: &Context,
+    pool: &MySqlPool,
+    user_id: U,
+    guild_id: Option<GuildId>,
+    scope_id: &ReminderScope,
+    time_parser: T,
+    expires_parser: Option<T>,
+    interval: Option<i64>,
+    content: &mut Content,
+    flags: ReminderFlags,
 ) -> Result<Reminder, ReminderError> {
     let user_id = user_id.into();
 
+    if let Some(max_reminders) = *MAX_USER_REMINDERS {
+        let count = sqlx::query!(
+            "
+SELECT COUNT(1) AS count FROM reminders WHERE set_by = (SELECT id FROM users WHERE user = ?)
+            ",
+            user_id
+        )
+        .fetch_one(pool)
+        .timed("user reminder count")
+        .await
+        .map_err(|e| ReminderError::DatabaseError(e.to_string()))?
+        .count;
+
+        if count >= max_reminders && !check_subscription(&ctx, user_id).await {
+            return Err(ReminderError::UserLimit);
+        }
+    }
+
+    // repeating reminders never finish, so a guild's channels can only hold so many
+    if let (Some(g_id), Some(_), ReminderScope::Channel(_))
+    | (Some(g_id), Some(_), ReminderScope::Ping(..)) = (guild_id, interval, scope_id)
+    {
+        let subscribed = guild_subscribed(ctx, user_id, guild_id).await;
+
+        if let Some(max_intervals) = max_guild_intervals(subscribed) {
+            let count = sqlx::query!(
+                "
+SELECT COUNT(1) AS count
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+WHERE
+    channels.guild_id = (SELECT id FROM guilds WHERE guild = ?) AND
+    reminders.`interval` IS NOT NULL AND
+    reminders.enabled = 1
+                ",
+                g_id.as_u64()
+            )
+            .fetch_one(pool)
+            .timed("guild interval count")
+            .await
+            .map_err(|e| ReminderError::DatabaseError(e.to_string()))?
+            .count;
+
+            if count >= max_intervals {
+                return Err(ReminderError::GuildIntervalLimit);
+            }
+        }
+    }
+
     if let Some(g_id) = guild_id {
         if let Some(guild) = g_id.to_guild_cached(&ctx) {
             content.substitute(guild);
         }
     }
 
+    // the content is shared by every location of the command, so the ping is only added here
+    let ping = match scope_id {
+        ReminderScope::Ping(user_id, _) => Some(format!("<@{}>", user_id)),
+
+        _ => None,
+    };
+
+    let text = match &ping {
+        Some(ping) => format!("{} {}", ping, content.content),
+
+        None => content.content.clone(),
+    };
+
+    // checked after substitution, since mentions and times can make the content longer
+    if text.chars().count() > *MAX_CONTENT_LENGTH {
+        return Err(ReminderError::ContentTooLong);
+    }
+
+    // content too long for a plain message is delivered as an embed, which has its own limit.
+    // Mentions in an embed don't notify anyone, so a `--here` ping stays in the message
+    let (message_content, embed_description) = if text.chars().count() > MESSAGE_CONTENT_LIMIT {
+        if content.content.chars().count() > EMBED_DESCRIPTION_LIMIT {
+            return Err(ReminderError::ContentTooLong);
+        }
+
+        (ping.as_deref().unwrap_or(""), content.content.as_str())
+    } else {
+        (text.as_str(), "")
+    };
+
     let mut nudge = 0;
 
+    let author_id = user_id;
+
     let db_channel_id = match scope_id {
         ReminderScope::User(user_id) => {
             if let Ok(user) = UserId(*user_id).to_user(&ctx).await {
@@ -1322,13 +5144,37 @@ async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
                     }
                 }
 
+                // users can always remind themselves, but others need their and the guild's consent
+                if *user_id != author_id {
+                    let guild_opt_out = match guild_id {
+                        Some(guild_id) => match ctx.guild_data(guild_id).await {
+                            Ok(guild_data) => guild_data.read().await.dm_opt_out,
+
+                            Err(_) => false,
+                        },
+
+                        None => false,
+                    };
+
+                    if user_data.dm_opt_out || guild_opt_out {
+                        return Err(ReminderError::DmOptOut);
+                    }
+                }
+
                 user_data.dm_channel
             } else {
                 return Err(ReminderError::InvalidTag);
             }
         }
 
-        ReminderScope::Channel(channel_id) => {
+        ReminderScope::Channel(channel_id) | ReminderScope::Ping(_, channel_id) => {
+            // pinging someone is only allowed where they could see it
+            if let (ReminderScope::Ping(user_id, _), Some(guild_id)) = (scope_id, guild_id) {
+                if guild_id.member(&ctx, UserId(*user_id)).await.is_err() {
+                    return Err(ReminderError::InvalidTag);
+                }
+            }
+
             let channel = ChannelId(*channel_id).to_channel(&ctx).await.unwrap();
 
             if channel.clone().guild().map(|gc| gc.guild_id) != guild_id {
@@ -1342,20 +5188,7 @@ async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
             nudge = channel_data.nudge;
 
             if let Some(guild_channel) = channel.guild() {
-                if channel_data.webhook_token.is_none() || channel_data.webhook_id.is_none() {
-                    match create_webhook(&ctx, guild_channel, "Reminder").await {
-                        Ok(webhook) => {
-                            channel_data.webhook_id = Some(webhook.id.as_u64().to_owned());
-                            channel_data.webhook_token = webhook.token;
-
-                            channel_data.commit_changes(&pool).await;
-                        }
-
-                        Err(e) => {
-                            return Err(ReminderError::DiscordError(e.to_string()));
-                        }
-                    }
-                }
+                ensure_webhook(ctx, pool, &mut channel_data, guild_channel).await?;
             }
 
             channel_data.id
@@ -1363,23 +5196,19 @@ async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
     };
 
     // validate time, channel
-    if interval.map_or(false, |inner| inner < *MIN_INTERVAL) {
-        Err(ReminderError::ShortInterval)
-    } else if interval.map_or(false, |inner| inner > *MAX_TIME) {
-        Err(ReminderError::LongInterval)
+    if let Err(e) = check_interval(interval) {
+        Err(e)
     } else {
         match time_parser.try_into() {
-            Ok(time_pre) => {
-                match expires_parser.map(|t| t.try_into()).transpose() {
-                    Ok(expires) => {
-                        let time = time_pre + nudge as i64;
-
-                        let unix_time = SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs() as i64;
-
-                        if time >= unix_time - 10 {
+            Ok(time_pre) => match expires_parser.map(|t| t.try_into()).transpose() {
+                Ok(expires) => {
+                    let unix_time = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs() as i64;
+
+                    match send_time(time_pre, nudge as i64, unix_time, *PAST_TIME_GRACE) {
+                        Ok(time) => {
                             let uid = generate_uid();
 
                             sqlx::query!(
@@ -1387,6 +5216,7 @@ async fn create_reminder<'a, U: Into<u64>, T: TryInto<i64>>(
 INSERT INTO reminders (
     uid,
     content,
+    embed_description,
     tts,
     attachment,
     attachment_name,
@@ -1394,6 +5224,14 @@ INSERT INTO reminders (
     `utc_time`,
     expires,
     `interval`,
+    acknowledge,
+    silent,
+    crosspost,
+    reply_to,
+    heads_up_for,
+    edit_in_place,
+    interval_weekday,
+    interval_nth,
     set_by
 ) VALUES (
     ?,
@@ -1402,14 +5240,24 @@ INSERT INTO reminders (
     ?,
     ?,
     ?,
+    ?,
     DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND),
     DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND),
     ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
     (SELECT id FROM users WHERE user = ? LIMIT 1)
 )
                             ",
                                 uid,
-                                content.content,
+                                message_content,
+                                embed_description,
                                 content.tts,
                                 content.attachment,
                                 content.attachment_name,
@@ -1417,28 +5265,131 @@ INSERT INTO reminders (
                                 time,
                                 expires,
                                 interval,
+                                flags.acknowledge,
+                                flags.silent,
+                                flags.crosspost,
+                                flags.reply_to.map(|message_id| *message_id.as_u64()),
+                                flags.heads_up_for,
+                                flags.edit_in_place,
+                                flags
+                                    .monthly
+                                    .map(|(weekday, _)| weekday.num_days_from_monday()),
+                                flags.monthly.map(|(_, nth)| nth),
                                 user_id
                             )
                             .execute(pool)
                             .await
-                            .unwrap();
+                            .map_err(|e| {
+                                warn!("Couldn't insert reminder: {:?}", e);
+
+                                ReminderError::DatabaseError(e.to_string())
+                            })?;
 
-                            let reminder = Reminder::from_uid(ctx, uid).await.unwrap();
+                            let reminder = Reminder::from_uid(ctx, uid).await.ok_or_else(|| {
+                                ReminderError::DatabaseError("inserted reminder not found".into())
+                            })?;
+
+                            if let Some(metrics) = ctx.data.read().await.get::<Metrics>() {
+                                metrics.reminder_created(ctx.shard_id);
+                            }
 
                             Ok(reminder)
-                        } else if time < 0 {
-                            // case required for if python returns -1
-                            Err(ReminderError::InvalidTime)
-                        } else {
-                            Err(ReminderError::PastTime)
                         }
-                    }
 
-                    Err(_) => Err(ReminderError::InvalidExpiration),
+                        Err(e) => Err(e),
+                    }
                 }
-            }
+
+                Err(_) => Err(ReminderError::InvalidExpiration),
+            },
 
             Err(_) => Err(ReminderError::InvalidTime),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_618_953_630;
+
+    #[test]
+    fn check_interval_limits() {
+        assert_eq!(check_interval(None), Ok(()));
+        assert_eq!(check_interval(Some(*MIN_INTERVAL)), Ok(()));
+        assert_eq!(check_interval(Some(*MAX_TIME)), Ok(()));
+        assert_eq!(
+            check_interval(Some(*MIN_INTERVAL - 1)),
+            Err(ReminderError::ShortInterval)
+        );
+        assert_eq!(
+            check_interval(Some(*MAX_TIME + 1)),
+            Err(ReminderError::LongInterval)
+        );
+    }
+
+    #[test]
+    fn nudge_moves_first_send_only() {
+        // even the largest nudges move just the first send, so the shortest interval is still
+        // accepted and repeats stay that far apart
+        for nudge in &[i16::MIN as i64, -1, 1, i16::MAX as i64] {
+            assert_eq!(
+                send_time(NOW + 86400, *nudge, NOW, 10),
+                Ok(NOW + 86400 + nudge)
+            );
+            assert_eq!(check_interval(Some(*MIN_INTERVAL)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn send_time_nudged() {
+        assert_eq!(send_time(NOW + 3600, -600, NOW, 10), Ok(NOW + 3000));
+        assert_eq!(send_time(NOW + 3600, 600, NOW, 10), Ok(NOW + 4200));
+    }
+
+    #[test]
+    fn send_time_large_negative_nudge() {
+        assert_eq!(
+            send_time(NOW + 60, -3600, NOW, 10),
+            Err(ReminderError::NudgedPastTime)
+        );
+        assert_eq!(
+            send_time(NOW + 60, i16::MIN as i64, NOW, 10),
+            Err(ReminderError::NudgedPastTime)
+        );
+    }
+
+    #[test]
+    fn send_time_past() {
+        assert_eq!(
+            send_time(NOW - 3600, -600, NOW, 10),
+            Err(ReminderError::PastTime)
+        );
+        assert_eq!(send_time(-1, 0, NOW, 10), Err(ReminderError::InvalidTime));
+    }
+
+    #[test]
+    fn send_time_grace_boundary() {
+        // slightly past times are sent straight away
+        assert_eq!(send_time(NOW - 9, 0, NOW, 10), Ok(NOW));
+        assert_eq!(send_time(NOW - 10, 0, NOW, 10), Ok(NOW));
+        assert_eq!(
+            send_time(NOW - 11, 0, NOW, 10),
+            Err(ReminderError::PastTime)
+        );
+    }
+
+    #[test]
+    fn send_time_configured_grace() {
+        assert_eq!(send_time(NOW - 60, 0, NOW, 60), Ok(NOW));
+        assert_eq!(
+            send_time(NOW - 61, 0, NOW, 60),
+            Err(ReminderError::PastTime)
+        );
+
+        // no grace at all
+        assert_eq!(send_time(NOW, 0, NOW, 0), Ok(NOW));
+        assert_eq!(send_time(NOW - 1, 0, NOW, 0), Err(ReminderError::PastTime));
+    }
+}
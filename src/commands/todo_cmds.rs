@@ -14,6 +14,7 @@ use std::fmt;
 
 use crate::{
     command_help, get_ctx_data,
+    guild_context::{guild_context, guild_member},
     models::{user_data::UserData, CtxGuildData},
 };
 use sqlx::MySqlPool;
@@ -193,6 +194,112 @@ DELETE FROM todos WHERE id = ?
         }
     }
 
+    /// The scope `todo <scope> move` or `promote` would send a todo to, relative to the message
+    fn destination(&self, scope: &str, msg: &Message) -> Option<TodoTarget> {
+        match scope {
+            "user" => Some(TodoTarget {
+                user: msg.author.id,
+                guild: None,
+                channel: None,
+            }),
+
+            "channel" => msg.guild_id.map(|guild_id| TodoTarget {
+                user: msg.author.id,
+                guild: Some(guild_id),
+                channel: Some(msg.channel_id),
+            }),
+
+            "guild" | "server" => msg.guild_id.map(|guild_id| TodoTarget {
+                user: msg.author.id,
+                guild: Some(guild_id),
+                channel: None,
+            }),
+
+            _ => None,
+        }
+    }
+
+    /// Whether the author may edit the destination's list, checked the same way as `todoc` and
+    /// `todos` themselves
+    async fn can_manage(&self, ctx: &Context, msg: &Message) -> bool {
+        let command = if self.channel.is_some() {
+            &TODO_CHANNEL_COMMAND
+        } else if self.guild.is_some() {
+            &TODO_GUILD_COMMAND
+        } else {
+            return true;
+        };
+
+        match msg.guild_id {
+            Some(guild_id) => match (
+                guild_context(ctx, guild_id).await,
+                guild_member(ctx, guild_id, msg.author.id).await,
+            ) {
+                (Ok(guild), Ok(member)) => command.check_permissions(ctx, &guild, &member).await,
+
+                _ => false,
+            },
+
+            None => false,
+        }
+    }
+
+    /// Move a todo from this list to another, keeping the same row
+    pub async fn move_to(
+        &self,
+        num: usize,
+        destination: &TodoTarget,
+        pool: &MySqlPool,
+    ) -> Result<Todo, Box<dyn std::error::Error + Sync + Send>> {
+        let todos = self.view(pool.clone()).await?;
+
+        if let Some(todo) = todos.into_iter().nth(num) {
+            if let (Some(cid), Some(gid)) = (destination.channel, destination.guild) {
+                sqlx::query!(
+                    "
+UPDATE todos SET
+    guild_id = (SELECT id FROM guilds WHERE guild = ?),
+    channel_id = (SELECT id FROM channels WHERE channel = ?)
+WHERE id = ?
+                    ",
+                    gid.as_u64(),
+                    cid.as_u64(),
+                    todo.id
+                )
+                .execute(pool)
+                .await?;
+            } else if let Some(gid) = destination.guild {
+                sqlx::query!(
+                    "
+UPDATE todos SET guild_id = (SELECT id FROM guilds WHERE guild = ?), channel_id = NULL WHERE id = ?
+                    ",
+                    gid.as_u64(),
+                    todo.id
+                )
+                .execute(pool)
+                .await?;
+            } else {
+                sqlx::query!(
+                    "
+UPDATE todos SET
+    user_id = (SELECT id FROM users WHERE user = ?),
+    guild_id = NULL,
+    channel_id = NULL
+WHERE id = ?
+                    ",
+                    destination.user.as_u64(),
+                    todo.id
+                )
+                .execute(pool)
+                .await?;
+            }
+
+            Ok(todo)
+        } else {
+            Err(Box::new(TodoNotFound))
+        }
+    }
+
     pub async fn clear(
         &self,
         pool: &MySqlPool,
@@ -313,6 +420,72 @@ DELETE FROM todos WHERE user_id = (SELECT id FROM users WHERE user = ?) AND guil
                 }
             }
 
+            SubCommand::Move | SubCommand::Promote => {
+                let mut args = extra.split_whitespace();
+
+                let num = args
+                    .next()
+                    .map(|num| num.parse::<usize>().ok())
+                    .flatten()
+                    .filter(|num| *num > 0);
+
+                let scope = match subcommand {
+                    // promoting moves a todo one step towards more shared lists
+                    SubCommand::Promote => {
+                        if self.channel.is_some() {
+                            Some("guild")
+                        } else if self.guild.is_some() {
+                            None
+                        } else {
+                            Some("channel")
+                        }
+                    }
+
+                    _ => args.next(),
+                };
+
+                let destination = scope
+                    .map(|scope| self.destination(&scope.to_lowercase(), msg))
+                    .flatten();
+
+                match (num, destination) {
+                    (Some(num), Some(destination)) => {
+                        if !destination.can_manage(ctx, msg).await {
+                            let _ = msg
+                                .channel_id
+                                .say(&ctx, lm.get(&user_data.language, "todo/error_permission"))
+                                .await;
+                        } else if let Ok(todo) = self.move_to(num - 1, &destination, &pool).await {
+                            let content = lm
+                                .get(&user_data.language, "todo/moved")
+                                .replacen("{}", &todo.value, 1)
+                                .replacen("{name}", &destination.name(), 1);
+
+                            let _ = msg
+                                .channel_id
+                                .send_message(&ctx, |m| {
+                                    m.content(content).allowed_mentions(|m| m.empty_parse())
+                                })
+                                .await;
+                        } else {
+                            let _ = msg
+                                .channel_id
+                                .say(&ctx, lm.get(&user_data.language, "todo/error_index"))
+                                .await;
+                        }
+                    }
+
+                    _ => {
+                        let content = lm
+                            .get(&user_data.language, "todo/error_value")
+                            .replacen("{prefix}", &prefix, 1)
+                            .replacen("{command}", &self.command(Some(subcommand)), 1);
+
+                        let _ = msg.channel_id.say(&ctx, content).await;
+                    }
+                }
+            }
+
             SubCommand::Clear => {
                 self.clear(&pool).await.unwrap();
 
@@ -328,6 +501,8 @@ enum SubCommand {
     View,
     Add,
     Remove,
+    Move,
+    Promote,
     Clear,
 }
 
@@ -340,6 +515,10 @@ impl TryFrom<Option<&str>> for SubCommand {
 
             Some("remove") => Ok(SubCommand::Remove),
 
+            Some("move") => Ok(SubCommand::Move),
+
+            Some("promote") => Ok(SubCommand::Promote),
+
             Some("clear") => Ok(SubCommand::Clear),
 
             None | Some("") => Ok(SubCommand::View),
@@ -355,6 +534,8 @@ impl ToString for SubCommand {
             SubCommand::View => "",
             SubCommand::Add => "add",
             SubCommand::Remove => "remove",
+            SubCommand::Move => "move",
+            SubCommand::Promote => "promote",
             SubCommand::Clear => "clear",
         }
         .to_string()
@@ -453,3 +634,209 @@ async fn show_help(ctx: &Context, msg: &Message, target: Option<TodoTarget>) {
 
     command_help(ctx, msg, lm, &prefix.await, &language.await, command).await;
 }
+
+/// These run against the database in `DATABASE_URL`, which must have `create.sql` and every file
+/// in `migration/` applied. Run them with `cargo test -- --ignored`. The rows they create use IDs
+/// no real user, guild or channel has, and are deleted again afterwards
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    struct Fixture {
+        pool: MySqlPool,
+        user: u64,
+        guild: u64,
+        channel: u64,
+    }
+
+    impl Fixture {
+        /// `base` keeps the IDs of tests running at the same time apart
+        async fn new(base: u64) -> Self {
+            let pool = MySqlPool::connect(
+                &env::var("DATABASE_URL").expect("Missing DATABASE_URL from environment"),
+            )
+            .await
+            .unwrap();
+
+            let fixture = Self {
+                pool,
+                user: base,
+                guild: base + 1,
+                channel: base + 2,
+            };
+
+            fixture.cleanup().await;
+
+            sqlx::query!("INSERT INTO guilds (guild) VALUES (?)", fixture.guild)
+                .execute(&fixture.pool)
+                .await
+                .unwrap();
+
+            sqlx::query!(
+                "
+INSERT INTO channels (channel, guild_id) VALUES (?, (SELECT id FROM guilds WHERE guild = ?))
+                ",
+                fixture.channel,
+                fixture.guild
+            )
+            .execute(&fixture.pool)
+            .await
+            .unwrap();
+
+            // a user's DM channel shares their ID
+            sqlx::query!("INSERT INTO channels (channel) VALUES (?)", fixture.user)
+                .execute(&fixture.pool)
+                .await
+                .unwrap();
+
+            sqlx::query!(
+                "
+INSERT INTO users (user, name, dm_channel) VALUES (?, 'test', (SELECT id FROM channels WHERE channel = ?))
+                ",
+                fixture.user,
+                fixture.user
+            )
+            .execute(&fixture.pool)
+            .await
+            .unwrap();
+
+            fixture
+        }
+
+        fn target(&self, guild: bool, channel: bool) -> TodoTarget {
+            TodoTarget {
+                user: UserId(self.user),
+                guild: if guild {
+                    Some(GuildId(self.guild))
+                } else {
+                    None
+                },
+                channel: if channel {
+                    Some(ChannelId(self.channel))
+                } else {
+                    None
+                },
+            }
+        }
+
+        async fn values(&self, target: &TodoTarget) -> Vec<String> {
+            target
+                .view(self.pool.clone())
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|todo| todo.value)
+                .collect()
+        }
+
+        async fn cleanup(&self) {
+            sqlx::query!(
+                "
+DELETE FROM todos WHERE user_id = (SELECT id FROM users WHERE user = ?)
+                ",
+                self.user
+            )
+            .execute(&self.pool)
+            .await
+            .unwrap();
+
+            sqlx::query!("DELETE FROM users WHERE user = ?", self.user)
+                .execute(&self.pool)
+                .await
+                .unwrap();
+
+            sqlx::query!(
+                "DELETE FROM channels WHERE channel IN (?, ?)",
+                self.user,
+                self.channel
+            )
+            .execute(&self.pool)
+            .await
+            .unwrap();
+
+            sqlx::query!("DELETE FROM guilds WHERE guild = ?", self.guild)
+                .execute(&self.pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn todo_add_remove_clear() {
+        let fixture = Fixture::new(u64::MAX - 10).await;
+
+        let user = fixture.target(false, false);
+        let guild = fixture.target(true, false);
+        let channel = fixture.target(true, true);
+
+        for (target, value) in [(&user, "user"), (&guild, "guild"), (&channel, "channel")].iter() {
+            target
+                .add(format!("{} 1", value), fixture.pool.clone())
+                .await
+                .unwrap();
+            target
+                .add(format!("{} 2", value), fixture.pool.clone())
+                .await
+                .unwrap();
+        }
+
+        // each list only holds its own todos, not those of narrower scopes
+        assert_eq!(fixture.values(&user).await, vec!["user 1", "user 2"]);
+        assert_eq!(fixture.values(&guild).await, vec!["guild 1", "guild 2"]);
+        assert_eq!(
+            fixture.values(&channel).await,
+            vec!["channel 1", "channel 2"]
+        );
+
+        let removed = guild.remove(0, &fixture.pool).await.unwrap();
+
+        assert_eq!(removed.value, "guild 1");
+        assert_eq!(fixture.values(&guild).await, vec!["guild 2"]);
+        assert!(guild.remove(1, &fixture.pool).await.is_err());
+
+        channel.clear(&fixture.pool).await.unwrap();
+
+        assert!(fixture.values(&channel).await.is_empty());
+        assert_eq!(fixture.values(&guild).await, vec!["guild 2"]);
+        assert_eq!(fixture.values(&user).await, vec!["user 1", "user 2"]);
+
+        fixture.cleanup().await;
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn todo_move_keeps_row() {
+        let fixture = Fixture::new(u64::MAX - 20).await;
+
+        let user = fixture.target(false, false);
+        let guild = fixture.target(true, false);
+        let channel = fixture.target(true, true);
+
+        user.add("moving".to_string(), fixture.pool.clone())
+            .await
+            .unwrap();
+
+        let id = user.view(fixture.pool.clone()).await.unwrap()[0].id;
+
+        let moved = user.move_to(0, &channel, &fixture.pool).await.unwrap();
+
+        assert_eq!(moved.id, id);
+        assert!(fixture.values(&user).await.is_empty());
+        assert_eq!(fixture.values(&channel).await, vec!["moving"]);
+
+        channel.move_to(0, &guild, &fixture.pool).await.unwrap();
+
+        assert!(fixture.values(&channel).await.is_empty());
+        assert_eq!(guild.view(fixture.pool.clone()).await.unwrap()[0].id, id);
+
+        guild.move_to(0, &user, &fixture.pool).await.unwrap();
+
+        assert_eq!(fixture.values(&user).await, vec!["moving"]);
+        assert!(user.move_to(1, &guild, &fixture.pool).await.is_err());
+
+        fixture.cleanup().await;
+    }
+}
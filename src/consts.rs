@@ -2,10 +2,30 @@ pub const DAY: u64 = 86_400;
 pub const HOUR: u64 = 3_600;
 pub const MINUTE: u64 = 60;
 
+// longest plain message Discord will deliver. Longer reminder content is sent as an embed
+pub const MESSAGE_CONTENT_LIMIT: usize = 2000;
+
+// longest embed description Discord will deliver, and so the longest any reminder can be
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+
+// longest embed footer Discord will deliver
+pub const EMBED_FOOTER_LIMIT: usize = 2048;
+
+// the schema version this build expects. Bumped by every migration after migration/schema_version.sql
+pub const SCHEMA_VERSION: u32 = 23;
+
 pub const CHARACTERS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
 
 const THEME_COLOR_FALLBACK: u32 = 0x8fb677;
 
+// the keywords `natural` splits commands on, as regex alternatives, unless a guild sets its own
+pub const DEFAULT_NATURAL_SEND: &str = "send|say";
+pub const DEFAULT_NATURAL_TO: &str = "to";
+pub const DEFAULT_NATURAL_EVERY: &str = "every";
+
+// words the interval regex already uses, which can't be taken by a guild's keywords
+pub const RESERVED_NATURAL_WORDS: [&str; 2] = ["until", "for"];
+
 use std::{collections::HashSet, env, iter::FromIterator};
 
 use regex::{Regex, RegexBuilder};
@@ -15,6 +35,8 @@ lazy_static! {
 
     pub static ref REGEX_ROLE: Regex = Regex::new(r#"<@&(\d+)>"#).unwrap();
 
+    pub static ref REGEX_USER: Regex = Regex::new(r#"<@!?(\d+)>"#).unwrap();
+
     pub static ref REGEX_COMMANDS: Regex = Regex::new(r#"([a-z]+)"#).unwrap();
 
     pub static ref REGEX_ALIAS: Regex =
@@ -24,27 +46,41 @@ lazy_static! {
 
     pub static ref REGEX_CHANNEL_USER: Regex = Regex::new(r#"\s*<(#|@)(?:!)?(\d+)>\s*"#).unwrap();
 
+    pub static ref REGEX_MESSAGE_LINK: Regex = Regex::new(r#"^https://(?:\w+\.)?discord(?:app)?\.com/channels/(?:\d+|@me)/(?P<channel>\d+)/(?P<message>\d+)$"#).unwrap();
+
     pub static ref REGEX_REMIND_COMMAND: Regex = RegexBuilder::new(
-    r#"(?P<mentions>(?:<@\d+>\s+|<@!\d+>\s+|<#\d+>\s+)*)(?P<time>(?:(?:\d+)(?:s|m|h|d|:|/|-|))+)(?:\s+(?P<interval>(?:(?:\d+)(?:s|m|h|d|))+))?(?:\s+(?P<expires>(?:(?:\d+)(?:s|m|h|d|:|/|-|))+))?\s+(?P<content>.*)"#
+    r#"(?P<mentions>(?:<@\d+>\s+|<@!\d+>\s+|<#\d+>\s+)*)(?:tz:(?P<timezone>\S+)\s+)?(?P<time>(?:<t:\d+(?::[tTdDfFR])?>|(?:(?:\d+)(?:s|m|h|d|:|/|-|))+|(?i:(?:end|start)[\s_-]+of[\s_-]+(?:month|week)))(?:,\s*(?:<t:\d+(?::[tTdDfFR])?>|(?:(?:\d+)(?:s|m|h|d|:|/|-|))+|(?i:(?:end|start)[\s_-]+of[\s_-]+(?:month|week))))*)(?:\s+(?P<interval>(?:(?:\d+)(?:s|m|h|d|))+))?(?:\s+(?P<expires>(?:(?:\d+)(?:s|m|h|d|:|/|-|))+))?\s+(?P<content>.*)"#
+    )
+        .dot_matches_new_line(true)
+        .build()
+        .unwrap();
+
+    pub static ref REGEX_TEMPLATE_COMMAND: Regex = RegexBuilder::new(
+    r#"(?P<name>\S+)(?:\s+(?P<interval>(?:\d+[smhd])+))?\s+(?P<content>.*)"#
     )
         .dot_matches_new_line(true)
         .build()
         .unwrap();
 
-    pub static ref REGEX_NATURAL_COMMAND_1: Regex = RegexBuilder::new(
-    r#"(?P<time>.*?)(?:\s+)(?:send|say)(?:\s+)(?P<msg>.*?)(?:(?:\s+)to(?:\s+)(?P<mentions>((?:<@\d+>)|(?:<@!\d+>)|(?:<#\d+>)|(?:\s+))+))?$"#
+    pub static ref REGEX_TEMPLATE_EMBED_COMMAND: Regex = RegexBuilder::new(
+    r#"^(?P<name>\S+)\s+(?:#(?P<color>[0-9a-fA-F]{6})\s+)?(?P<title>[^|]*?)\s*(?:\|\s*(?P<description>.*))?$"#
     )
         .dot_matches_new_line(true)
         .build()
         .unwrap();
 
-    pub static ref REGEX_NATURAL_COMMAND_2: Regex = RegexBuilder::new(
-    r#"(?P<msg>.*)(?:\s+)every(?:\s+)(?P<interval>.*?)(?:(?:\s+)(?:until|for)(?:\s+)(?P<expires>.*?))?$"#
+    pub static ref REGEX_ONLINE_COMMAND: Regex = RegexBuilder::new(
+    r#"^<@!?(?P<user>\d+)>\s+(?P<content>.+)$"#
     )
         .dot_matches_new_line(true)
         .build()
         .unwrap();
 
+    pub static ref REGEX_NATURAL_COMMAND_1: Regex =
+        natural_command_regex(DEFAULT_NATURAL_SEND, DEFAULT_NATURAL_TO);
+
+    pub static ref REGEX_NATURAL_COMMAND_2: Regex = natural_interval_regex(DEFAULT_NATURAL_EVERY);
+
     pub static ref SUBSCRIPTION_ROLES: HashSet<u64> = HashSet::from_iter(
         env::var("SUBSCRIPTION_ROLES")
             .map(|var| var
@@ -54,6 +90,21 @@ lazy_static! {
             .unwrap_or_else(|_| Vec::new())
     );
 
+    pub static ref OWNER_IDS: HashSet<u64> = HashSet::from_iter(
+        env::var("OWNER_IDS")
+            .map(|var| var
+                .split(',')
+                .filter_map(|item| { item.parse::<u64>().ok() })
+                .collect::<Vec<u64>>())
+            .unwrap_or_else(|_| Vec::new())
+    );
+
+    pub static ref BROADCAST_DELAY: u64 = env::var("BROADCAST_DELAY")
+        .ok()
+        .map(|inner| inner.parse::<u64>().ok())
+        .flatten()
+        .unwrap_or(1000);
+
     pub static ref CNC_GUILD: Option<u64> = env::var("CNC_GUILD")
         .map(|var| var.parse::<u64>().ok())
         .ok()
@@ -71,14 +122,139 @@ lazy_static! {
         .flatten()
         .unwrap_or(60 * 60 * 24 * 365 * 50);
 
+    pub static ref MAX_USER_REMINDERS: Option<i64> = env::var("MAX_USER_REMINDERS")
+        .map(|var| var.parse::<i64>().ok())
+        .ok()
+        .flatten();
+
+    pub static ref MAX_GUILD_INTERVALS: Option<i64> = env::var("MAX_GUILD_INTERVALS")
+        .map(|var| var.parse::<i64>().ok())
+        .ok()
+        .flatten();
+
+    pub static ref MAX_GUILD_INTERVALS_SUBSCRIBED: Option<i64> =
+        env::var("MAX_GUILD_INTERVALS_SUBSCRIBED")
+            .map(|var| var.parse::<i64>().ok())
+            .ok()
+            .flatten();
+
+    pub static ref MAX_CONTENT_LENGTH: usize = env::var("MAX_CONTENT_LENGTH")
+        .ok()
+        .map(|inner| inner.parse::<usize>().ok())
+        .flatten()
+        .unwrap_or(EMBED_DESCRIPTION_LIMIT)
+        .min(EMBED_DESCRIPTION_LIMIT);
+
+    pub static ref DISCORD_RETRY_ATTEMPTS: u32 = env::var("DISCORD_RETRY_ATTEMPTS")
+        .ok()
+        .map(|inner| inner.parse::<u32>().ok())
+        .flatten()
+        .unwrap_or(5)
+        .max(1);
+
+    pub static ref DISCORD_RETRY_BASE_DELAY: u64 = env::var("DISCORD_RETRY_BASE_DELAY")
+        .ok()
+        .map(|inner| inner.parse::<u64>().ok())
+        .flatten()
+        .unwrap_or(500)
+        .max(1);
+
+    pub static ref MAX_PREFIX_LENGTH: usize = env::var("MAX_PREFIX_LENGTH")
+        .ok()
+        .map(|inner| inner.parse::<usize>().ok())
+        .flatten()
+        .unwrap_or(5)
+        .min(32);
+
+    pub static ref DUPLICATE_WINDOW: i64 = env::var("DUPLICATE_WINDOW")
+        .ok()
+        .map(|inner| inner.parse::<i64>().ok())
+        .flatten()
+        .unwrap_or(60)
+        .max(0);
+    pub static ref IDEMPOTENCY_WINDOW: i64 = env::var("IDEMPOTENCY_WINDOW")
+        .ok()
+        .map(|inner| inner.parse::<i64>().ok())
+        .flatten()
+        .unwrap_or(300)
+        .max(0);
+    pub static ref PAST_TIME_GRACE: i64 = env::var("PAST_TIME_GRACE")
+        .ok()
+        .map(|inner| inner.parse::<i64>().ok())
+        .flatten()
+        .unwrap_or(10)
+        .max(0);
+    pub static ref MAX_REMINDER_TARGETS: usize = env::var("MAX_REMINDER_TARGETS")
+        .ok()
+        .map(|inner| inner.parse::<usize>().ok())
+        .flatten()
+        .unwrap_or(20);
+
+    pub static ref MAX_TEMPLATES: u32 = env::var("MAX_TEMPLATES")
+        .ok()
+        .map(|inner| inner.parse::<u32>().ok())
+        .flatten()
+        .unwrap_or(25);
+
+    pub static ref MAX_KEYWORDS: usize = env::var("MAX_KEYWORDS")
+        .ok()
+        .map(|inner| inner.parse::<usize>().ok())
+        .flatten()
+        .unwrap_or(25);
+
+    pub static ref SLOW_QUERY_THRESHOLD: u64 = env::var("SLOW_QUERY_THRESHOLD")
+        .ok()
+        .map(|inner| inner.parse::<u64>().ok())
+        .flatten()
+        .unwrap_or(0);
+    pub static ref SLOW_COMMAND_THRESHOLD: u64 = env::var("SLOW_COMMAND_THRESHOLD")
+        .ok()
+        .map(|inner| inner.parse::<u64>().ok())
+        .flatten()
+        .unwrap_or(0);
+
+    pub static ref SKIP_SCHEMA_CHECK: bool =
+        env::var("SKIP_SCHEMA_CHECK").map_or(false, |var| var == "1");
+
+    pub static ref DELETION_LOG_SIZE: usize = env::var("DELETION_LOG_SIZE")
+        .ok()
+        .map(|inner| inner.parse::<usize>().ok())
+        .flatten()
+        .unwrap_or(1000);
+
+    pub static ref DELETION_LOG_RETENTION: i64 = env::var("DELETION_LOG_RETENTION")
+        .ok()
+        .map(|inner| inner.parse::<i64>().ok())
+        .flatten()
+        .unwrap_or(60 * 60 * 24 * 7)
+        .max(0);
+
+    pub static ref PRESENCE_ENABLED: bool =
+        env::var("PRESENCE_ENABLED").map_or(false, |var| var == "1");
+
+    pub static ref KEYWORDS_ENABLED: bool =
+        env::var("KEYWORDS_ENABLED").map_or(false, |var| var == "1");
+
+    pub static ref KEYWORD_COOLDOWN: i64 = env::var("KEYWORD_COOLDOWN")
+        .ok()
+        .map(|inner| inner.parse::<i64>().ok())
+        .flatten()
+        .unwrap_or(300);
+
     pub static ref LOCAL_TIMEZONE: String =
         env::var("LOCAL_TIMEZONE").unwrap_or_else(|_| "UTC".to_string());
 
     pub static ref LOCAL_LANGUAGE: String =
         env::var("LOCAL_LANGUAGE").unwrap_or_else(|_| "EN".to_string());
 
-    pub static ref DEFAULT_PREFIX: String =
-        env::var("DEFAULT_PREFIX").unwrap_or_else(|_| "$".to_string());
+    pub static ref DEFAULT_PREFIXES: Vec<String> = env::var("DEFAULT_PREFIX")
+        .map(|var| parse_prefixes(&var))
+        .ok()
+        .filter(|prefixes| !prefixes.is_empty())
+        .unwrap_or_else(|| vec!["$".to_string()]);
+
+    // the first default is the one new guilds are given
+    pub static ref DEFAULT_PREFIX: String = DEFAULT_PREFIXES[0].clone();
 
     pub static ref THEME_COLOR: u32 = env::var("THEME_COLOR").map_or(
         THEME_COLOR_FALLBACK,
@@ -87,4 +263,60 @@ lazy_static! {
 
     pub static ref PYTHON_LOCATION: String =
         env::var("PYTHON_LOCATION").unwrap_or_else(|_| "venv/bin/python3".to_string());
+
+    pub static ref HEALTH_PORT: u16 = env::var("HEALTH_PORT")
+        .ok()
+        .map(|inner| inner.parse::<u16>().ok())
+        .flatten()
+        .unwrap_or(8080);
+}
+
+/// Split a comma-separated list of prefixes, so `!, ?` is read as `!` and `?`
+pub fn parse_prefixes(var: &str) -> Vec<String> {
+    var.split(',')
+        .map(|prefix| prefix.trim())
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| prefix.to_string())
+        .collect()
+}
+
+/// The regex splitting a `natural` command into its time, content and mentions. `send` and `to`
+/// are regex fragments, so words from users need escaping first
+pub fn natural_command_regex(send: &str, to: &str) -> Regex {
+    RegexBuilder::new(&format!(
+        r#"(?P<time>.*?)(?:\s+)(?:{})(?:\s+)(?P<msg>.*?)(?:(?:\s+)(?:{})(?:\s+)(?P<mentions>((?:<@\d+>)|(?:<@!\d+>)|(?:<#\d+>)|(?:\s+))+))?$"#,
+        send, to
+    ))
+    .dot_matches_new_line(true)
+    .build()
+    .unwrap()
+}
+
+/// The regex splitting the content of a `natural` command into the content and an interval
+pub fn natural_interval_regex(every: &str) -> Regex {
+    RegexBuilder::new(&format!(
+        r#"(?P<msg>.*)(?:\s+)(?:{})(?:\s+)(?P<interval>.*?)(?:(?:\s+)(?:until|for)(?:\s+)(?P<expires>.*?))?$"#,
+        every
+    ))
+    .dot_matches_new_line(true)
+    .build()
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_prefixes_list() {
+        assert_eq!(parse_prefixes("$,!"), vec!["$", "!"]);
+        assert_eq!(parse_prefixes("!, ?"), vec!["!", "?"]);
+        assert_eq!(parse_prefixes(" $ ,, ! "), vec!["$", "!"]);
+    }
+
+    #[test]
+    fn parse_prefixes_empty() {
+        assert!(parse_prefixes("").is_empty());
+        assert!(parse_prefixes(" , ").is_empty());
+    }
 }
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Duration, Utc};
+
+use serenity::{model::id::UserId, prelude::TypeMapKey};
+
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+use crate::consts::{DELETION_LOG_RETENTION, DELETION_LOG_SIZE};
+
+/// A trace of a deleted reminder. The content is kept only as a hash, so that a user's
+/// description of a reminder can be checked without storing what it said
+#[derive(Clone)]
+pub struct DeletedReminder {
+    pub uid: String,
+    pub content_hash: u64,
+    pub deleted_by: UserId,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// The most recent reminder deletions, kept in memory only for support. Entries are dropped once
+/// there are more than `DELETION_LOG_SIZE` or they are older than `DELETION_LOG_RETENTION`
+/// seconds, and everything is lost on shutdown
+#[derive(Default)]
+pub struct DeletionLog {
+    entries: Mutex<VecDeque<DeletedReminder>>,
+}
+
+pub fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+
+    hasher.finish()
+}
+
+impl DeletionLog {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn prune(entries: &mut VecDeque<DeletedReminder>) {
+        let cutoff = Utc::now() - Duration::seconds(*DELETION_LOG_RETENTION);
+
+        while entries.len() > *DELETION_LOG_SIZE
+            || entries
+                .front()
+                .map_or(false, |entry| entry.deleted_at < cutoff)
+        {
+            entries.pop_front();
+        }
+    }
+
+    pub fn record(&self, uid: &str, content: &str, deleted_by: UserId) {
+        if *DELETION_LOG_SIZE == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+
+        entries.push_back(DeletedReminder {
+            uid: uid.to_string(),
+            content_hash: content_hash(content),
+            deleted_by,
+            deleted_at: Utc::now(),
+        });
+
+        Self::prune(&mut entries);
+    }
+
+    /// Up to `limit` of the retained deletions accepted by `filter`, newest first
+    pub fn search(
+        &self,
+        filter: impl Fn(&DeletedReminder) -> bool,
+        limit: usize,
+    ) -> Vec<DeletedReminder> {
+        let mut entries = self.entries.lock().unwrap();
+
+        Self::prune(&mut entries);
+
+        entries
+            .iter()
+            .rev()
+            .filter(|entry| filter(entry))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+impl TypeMapKey for DeletionLog {
+    type Value = Arc<Self>;
+}
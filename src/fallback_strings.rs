@@ -0,0 +1,674 @@
+// the options shared by `remind`, `interval` and `nag`
+const REMIND_OPTIONS: &str = "__Options__ (given before the reminder)\n`--silent` - send without a notification\n`--publish` - publish to following servers when sent in an announcement channel\n`--now` - send a repeating reminder once straight away as well\n`--edit` - edit a repeating reminder's last message each time instead of sending a new one\n`--here` - ping users in the default channel instead of sending them a DM. The ping stays in the message when long content is sent as an embed\n`--reply <message>` - send as a reply to a message, given by link or ID\n`--before <times>` - send heads-ups this long beforehand, e.g. `--before 1h,10m`\n`--fallback <channels>` - channels to try in order when the reminder can't be sent in its own";
+
+/// English for every string the bot uses, for when the compiled strings couldn't be loaded or
+/// don't have a string yet. New strings should be added here as well as to the languages repo
+pub const FALLBACK_STRINGS: &[(&str, &str)] = &[
+    ("flag", "🇬🇧"),
+    (
+        "command_error",
+        "Something went wrong running that command. Please try again later",
+    ),
+    (
+        "command_disabled",
+        "That command has been disabled in this server",
+    ),
+    (
+        "guild_unavailable",
+        "Couldn't get this server's details from Discord to check that command. Please try again in a moment",
+    ),
+    (
+        "too_fast",
+        "Please wait for your last command to finish before sending another",
+    ),
+    (
+        "no_perms_restricted",
+        "You need the `Manage Server` permission to use this command",
+    ),
+    (
+        "no_perms_managed",
+        "You need the `Manage Messages` permission, or a role allowed with `{prefix}restrict`",
+    ),
+    (
+        "help/desc",
+        "Use `{prefix}help <command>` to find out more about a command",
+    ),
+    ("help/remind_options", REMIND_OPTIONS),
+    ("help/interval_options", REMIND_OPTIONS),
+    ("help/nag_options", REMIND_OPTIONS),
+    ("help/setup_title", "Setup"),
+    ("help/mod_title", "Server Admin"),
+    ("help/reminder_title", "Reminders"),
+    ("help/reminder_mod_title", "Reminder Management"),
+    ("help/info_title", "Info"),
+    (
+        "help/after",
+        "Set a reminder for a time after another reminder in this channel.\n\n__Usage__\n`{prefix}after <position|word> <offset> <content>`\nThe other reminder is picked by its number in `{prefix}look`, or by a word from its content",
+    ),
+    (
+        "help/agenda",
+        "List the reminders due between two times, soonest first.\n\n__Usage__\n`{prefix}agenda <start> <end>`",
+    ),
+    (
+        "help/announcements",
+        "Choose where announcements from the bot's operators are posted in this server.\n\n__Usage__\n`{prefix}announcements #channel`\n`{prefix}announcements on|off`",
+    ),
+    (
+        "help/audit",
+        "Show recent changes to this server's reminders and restrictions, newest first.\n\n__Usage__\n`{prefix}audit [page]`",
+    ),
+    (
+        "help/bump",
+        "Move one reminder later by a duration.\n\n__Usage__\n`{prefix}bump <position> <duration>`\nThe position is the number shown by `{prefix}del`",
+    ),
+    (
+        "help/channeltz",
+        "Set the timezone that `{prefix}look` shows times in for this channel.\n\n__Usage__\n`{prefix}channeltz [timezone]`\n`{prefix}channeltz off`",
+    ),
+    (
+        "help/convert",
+        "Show a time in other timezones.\n\n__Usage__\n`{prefix}convert <time> <from timezone> [to timezones...]`",
+    ),
+    (
+        "help/count",
+        "Count the enabled and disabled reminders in each channel of this server.\n\n__Usage__\n`{prefix}count`",
+    ),
+    (
+        "help/defaultchannel",
+        "Set the channel reminders go to when no channel is given.\n\n__Usage__\n`{prefix}defaultchannel #channel`\n`{prefix}defaultchannel off`",
+    ),
+    (
+        "help/del-uid",
+        "Delete a reminder by its UID.\n\n__Usage__\n`{prefix}del-uid <uid>`",
+    ),
+    (
+        "help/delivery",
+        "Choose whether reminders are sent through webhooks or by the bot itself.\n\n__Usage__\n`{prefix}delivery webhook|bot`",
+    ),
+    (
+        "help/digest",
+        "Post this channel's reminders in a thread for each day.\n\n__Usage__\n`{prefix}digest on|off`",
+    ),
+    (
+        "help/disable",
+        "Turn commands off and on for everyone in this server. With no commands, lists the disabled ones.\n\n__Usage__\n`{prefix}disable [commands...]`",
+    ),
+    (
+        "help/dms",
+        "Choose whether other people can set reminders for your DMs.\n\n__Usage__\n`{prefix}dms on|off`\n`{prefix}dms server on|off` to choose for everyone in this server",
+    ),
+    (
+        "help/everyone",
+        "Choose how reminders that mention @everyone or @here are handled.\n\n__Usage__\n`{prefix}everyone off|confirm|restrict`\n`confirm` asks before setting them. `restrict` also needs the Mention Everyone permission",
+    ),
+    (
+        "help/inspect",
+        "Show the stored values of a reminder.\n\n__Usage__\n`{prefix}inspect <position> [look options]`",
+    ),
+    (
+        "help/keyword",
+        "Send a message when a word is said in this channel.\n\n__Usage__\n`{prefix}keyword` to list\n`{prefix}keyword add <word> <message>`\n`{prefix}keyword remove <word>`\n`{prefix}keyword on|off`",
+    ),
+    (
+        "help/list-channels",
+        "List the channels in this server with enabled reminders.\n\n__Usage__\n`{prefix}list-channels`",
+    ),
+    (
+        "help/logchannel",
+        "Send a copy of every reminder in this server to a channel.\n\n__Usage__\n`{prefix}logchannel #channel`\n`{prefix}logchannel off`",
+    ),
+    (
+        "help/mute",
+        "Send a reminder without pinging anyone until a time.\n\n__Usage__\n`{prefix}mute <uid> <time>`\n`{prefix}mute <uid> off`",
+    ),
+    (
+        "help/mychannel",
+        "Set the channel your reminders go to in this server when no channel is given.\n\n__Usage__\n`{prefix}mychannel [#channel]`\n`{prefix}mychannel off`",
+    ),
+    (
+        "help/nag",
+        "Set a repeating reminder that continues until someone presses its acknowledge button.\n\n__Usage__\n`{prefix}nag [channels/users] <time> <interval> [expires] <content>`",
+    ),
+    (
+        "help/naturalwords",
+        "Change the words `{prefix}natural` looks for in this server.\n\n__Usage__\n`{prefix}naturalwords` to show them\n`{prefix}naturalwords send|to|every <word>`\n`{prefix}naturalwords reset`",
+    ),
+    (
+        "help/next",
+        "Show the next reminder due in this channel.\n\n__Usage__\n`{prefix}next`",
+    ),
+    (
+        "help/online",
+        "Send a message when a user next comes online.\n\n__Usage__\n`{prefix}online @user <message>`\n`{prefix}online list` - show this channel's online reminders\n`{prefix}online cancel <id>` - cancel one from the list",
+    ),
+    (
+        "help/pause-mine",
+        "Pause every reminder you've set, everywhere.\n\n__Usage__\n`{prefix}pause-mine [time]`",
+    ),
+    (
+        "help/preview",
+        "DM yourself this channel's upcoming reminders as they'll be sent.\n\n__Usage__\n`{prefix}preview [look options]`",
+    ),
+    (
+        "help/quiet",
+        "Hold back reminders to your DMs during a daily window.\n\n__Usage__\n`{prefix}quiet <start> <end> [silent]`\n`{prefix}quiet off`\nWith `silent`, reminders are sent during the window without a notification",
+    ),
+    (
+        "help/resume-mine",
+        "Resume the reminders paused with `{prefix}pause-mine`.\n\n__Usage__\n`{prefix}resume-mine`",
+    ),
+    (
+        "help/serverdms",
+        "Choose whether reminders can be set for the DMs of this server's members.\n\n__Usage__\n`{prefix}serverdms on|off`",
+    ),
+    (
+        "help/settings",
+        "Copy this server's settings to a file, or apply a file of settings.\n\n__Usage__\n`{prefix}settings export`\n`{prefix}settings import` with the file attached",
+    ),
+    (
+        "help/snooze-all",
+        "Move every overdue reminder in this channel to a time from now.\n\n__Usage__\n`{prefix}snooze-all <duration>`",
+    ),
+    (
+        "help/stats",
+        "Show how many reminders are due in this server soon.\n\n__Usage__\n`{prefix}stats`",
+    ),
+    (
+        "help/template",
+        "Save reminder content to reuse with `{prefix}remind template <name> <time>`.\n\n__Usage__\n`{prefix}template list`\n`{prefix}template create <name> [interval] <content>`\n`{prefix}template embed <name> [#color] <title> [| description]`\n`{prefix}template embed <name> off`\n`{prefix}template delete <name>`",
+    ),
+    (
+        "help/webhook",
+        "Check this channel's webhook, and recreate it if needed.\n\n__Usage__\n`{prefix}webhook`",
+    ),
+    ("acknowledge/done", "Reminder acknowledged"),
+    (
+        "acknowledge/already_done",
+        "That reminder has already been acknowledged",
+    ),
+    ("after/not_found", "Couldn't find that reminder in this channel"),
+    ("after/invalid_offset", "Please give an offset like `1h` or `30m`"),
+    ("agenda/title", "Reminders from {start} to {end}"),
+    ("agenda/no_reminders", "No reminders are due in that time"),
+    (
+        "agenda/invalid_time",
+        "Couldn't understand those times. Please give a start and an end",
+    ),
+    ("agenda/backwards", "The end must be after the start"),
+    ("alias/suggest", "No alias `{name}`. Did you mean `{suggestion}`?"),
+    (
+        "announcements/channel",
+        "Announcements will be posted in {channel}",
+    ),
+    (
+        "announcements/on",
+        "Announcements will be posted in this server's system channel",
+    ),
+    ("announcements/off", "Announcements won't be posted in this server"),
+    (
+        "announcements/invalid",
+        "Please give a channel in this server, `on` or `off`",
+    ),
+    ("audit/title", "Recent changes"),
+    ("audit/empty", "No changes have been recorded in this server"),
+    ("audit/page", "Page {page} of {pages}"),
+    ("audit/invalid_page", "Please give a page from 1 to {pages}"),
+    ("audit/unknown_user", "Someone"),
+    ("audit/edit", "edited {count} reminder(s)"),
+    ("audit/enable", "enabled {count} reminder(s)"),
+    ("audit/disable", "disabled {count} reminder(s)"),
+    ("audit/delete", "deleted {count} reminder(s)"),
+    ("audit/offset", "moved {count} reminder(s)"),
+    ("audit/snooze", "snoozed {count} reminder(s)"),
+    ("audit/restrict", "changed {count} restriction(s)"),
+    (
+        "bump/invalid_index",
+        "Please give a reminder's number from `del` and a duration",
+    ),
+    ("bump/invalid_time", "Please give a duration like `1h` or `30m`"),
+    ("bump/past", "That would move the reminder into the past"),
+    ("bump/success", "Moved \"{content}\" to {time}"),
+    (
+        "channeltz/current",
+        "Times in this channel are shown in {timezone}",
+    ),
+    (
+        "channeltz/none",
+        "This channel has no timezone, so times are shown in each viewer's own",
+    ),
+    (
+        "channeltz/removed",
+        "Times in this channel will be shown in each viewer's own timezone",
+    ),
+    (
+        "channeltz/set",
+        "Times in this channel will be shown in {timezone}",
+    ),
+    ("channeltz/invalid", "That isn't a timezone I know"),
+    ("confirm/confirm_button", "Confirm"),
+    ("confirm/cancel_button", "Cancel"),
+    ("convert/title", "Time conversion"),
+    ("convert/invalid_time", "Couldn't understand that time"),
+    ("convert/unknown_timezone", "Unknown timezone `{timezone}`"),
+    ("count/title", "{enabled} enabled, {disabled} disabled"),
+    ("count/row", "{channel}: {enabled} enabled, {disabled} disabled"),
+    ("count/no_reminders", "There are no reminders in this server"),
+    (
+        "defaultchannel/set",
+        "Reminders without a channel will go to {channel}",
+    ),
+    (
+        "defaultchannel/removed",
+        "Reminders without a channel will go to the channel they're set in",
+    ),
+    (
+        "defaultchannel/invalid",
+        "Please give a channel in this server, or `off`",
+    ),
+    (
+        "defaultchannel/no_perms",
+        "I can't send reminders to that channel. Please check my permissions there",
+    ),
+    (
+        "del/heads_up_prompt",
+        "{count} heads-up reminder(s) belong to the reminders you're deleting. Delete them as well?",
+    ),
+    ("del_uid/deleted", "Deleted reminder `{uid}`"),
+    ("del_uid/not_found", "No reminder `{uid}` here"),
+    (
+        "delivery/webhook",
+        "Reminders will be sent through webhooks",
+    ),
+    ("delivery/bot", "Reminders will be sent by the bot itself"),
+    (
+        "digest/enabled",
+        "Reminders in this channel will be posted in a thread for each day",
+    ),
+    (
+        "digest/disabled",
+        "Reminders in this channel will be posted in the channel",
+    ),
+    (
+        "digest/unsupported",
+        "Only text and announcement channels have threads",
+    ),
+    ("disable/disabled", "`{command}` is now disabled"),
+    ("disable/enabled", "`{command}` is enabled again"),
+    ("disable/list", "Disabled commands: {commands}"),
+    ("disable/none", "No commands are disabled in this server"),
+    ("disable/not_allowed", "`{command}` can't be disabled"),
+    ("disable/unknown", "There's no command `{command}`"),
+    ("dms/off", "Other people can no longer set reminders for your DMs"),
+    ("dms/on", "Other people can set reminders for your DMs again"),
+    (
+        "dms/server_off",
+        "Reminders can no longer be set for members' DMs in this server",
+    ),
+    (
+        "dms/server_on",
+        "Reminders can be set for members' DMs in this server again",
+    ),
+    (
+        "everyone/confirm",
+        "This reminder mentions @everyone or @here. Set it anyway?",
+    ),
+    (
+        "everyone/no_permission",
+        "You need the Mention Everyone permission to set reminders that mention @everyone or @here",
+    ),
+    (
+        "everyone/success",
+        "Reminders mentioning @everyone or @here: `{setting}`",
+    ),
+    ("inspect/title", "Reminder details"),
+    ("inspect/not_found", "There's no reminder at that position"),
+    (
+        "interval/guild_limit",
+        "This server already has the most repeating reminders it can have ({max_intervals})",
+    ),
+    ("keyword/disabled", "Keyword triggers aren't enabled on this bot"),
+    ("keyword/none", "This channel has no keyword triggers"),
+    ("keyword/title", "Keyword triggers"),
+    ("keyword/on", "Keyword triggers are now on in this server"),
+    ("keyword/off", "Keyword triggers are now off in this server"),
+    (
+        "keyword/invalid_keyword",
+        "Keywords must be a single word of letters, numbers, `-` and `_`",
+    ),
+    ("keyword/too_long", "That message is too long"),
+    (
+        "keyword/limit",
+        "This channel already has the most keyword triggers it can have",
+    ),
+    ("keyword/added", "Added a trigger for `{keyword}`"),
+    ("keyword/removed", "Removed the trigger for `{keyword}`"),
+    ("keyword/not_found", "There's no trigger for that word here"),
+    ("list_channels/title", "Channels with reminders"),
+    ("list_channels/none", "No channels in this server have reminders"),
+    ("list_channels/line", "{channel}: {number}"),
+    ("list_channels/deleted", "deleted channel `{id}`"),
+    ("list_channels/total", "{number} reminders in total."),
+    ("list_channels/more", "{number} more channels not shown."),
+    (
+        "logchannel/set",
+        "A copy of each reminder will be sent to {channel}",
+    ),
+    ("logchannel/removed", "Reminders will no longer be copied"),
+    (
+        "logchannel/invalid",
+        "Please give a channel in this server, or `off`",
+    ),
+    (
+        "logchannel/no_perms",
+        "I can't send embeds to that channel. Please check my permissions there",
+    ),
+    (
+        "look/role_hidden",
+        "Reminders in channels that {role} can't see are hidden",
+    ),
+    (
+        "look/role_unknown",
+        "Couldn't check that role's permissions, so every reminder is shown",
+    ),
+    ("mute/set", "Reminder `{uid}` won't ping anyone until {time}"),
+    ("mute/off", "Reminder `{uid}` will ping again"),
+    ("mute/invalid_time", "Please give a time, or `off`"),
+    (
+        "mychannel/current",
+        "Your reminders in this server go to {channel}",
+    ),
+    ("mychannel/none", "You haven't set a channel for your reminders"),
+    (
+        "mychannel/removed",
+        "Your reminders will go to the server's default channel",
+    ),
+    (
+        "mychannel/invalid",
+        "Please give a channel in this server, or `off`",
+    ),
+    (
+        "mychannel/no_perms",
+        "Reminders can't be sent to that channel. Please check that you and I can both post there",
+    ),
+    ("mychannel/set", "Your reminders in this server will go to {channel}"),
+    (
+        "natural/understood",
+        "Time: {time}\nMessage: {message}\nRepeating: {interval}\nExpires: {expires}",
+    ),
+    ("natural/again_none", "You haven't set a reminder to repeat"),
+    (
+        "natural/again_invalid",
+        "Couldn't understand that time",
+    ),
+    (
+        "naturalwords/current",
+        "Words used here: send `{send}`, to `{to}`, every `{every}`",
+    ),
+    ("naturalwords/set", "`{keyword}` is now `{word}`"),
+    ("naturalwords/reset", "The default words will be used again"),
+    (
+        "naturalwords/invalid",
+        "Please give a single word of letters and numbers, up to 16 long",
+    ),
+    ("naturalwords/conflict", "`{word}` is already used for something else"),
+    ("next/next", "Next: \"{content}\" in {time}"),
+    ("next/repeating", "Repeats every {interval}"),
+    ("next/overdue", "Next: \"{content}\", due {time} ago"),
+    ("next/no_reminders", "There are no reminders due in this channel"),
+    (
+        "next/blacklisted",
+        "This channel is blacklisted, so its reminders aren't listed",
+    ),
+    ("next/paused", "This channel is paused, so no reminders are due"),
+    ("nudge/reset", "Reminders in this channel will no longer be nudged"),
+    (
+        "nudge/example",
+        "For example, a reminder for {time} will be sent at {nudged}",
+    ),
+    (
+        "offset/preview",
+        "{count} reminder(s) would move by {offset} seconds:",
+    ),
+    ("offset/preview_more", "...and {count} more"),
+    ("online/disabled", "Online reminders aren't enabled on this bot"),
+    ("online/too_long", "That message is too long"),
+    ("online/none", "There are no online reminders in this channel"),
+    ("online/cancelled", "Cancelled the online reminder"),
+    (
+        "online/not_found",
+        "Couldn't find that online reminder in this channel",
+    ),
+    (
+        "online/success",
+        "I'll send your message when {user} next comes online",
+    ),
+    ("pause_mine/paused", "Paused {count} of your reminders"),
+    (
+        "pause_mine/paused_until",
+        "Paused {count} of your reminders until {time}",
+    ),
+    ("pause_mine/resumed", "Resumed {count} of your reminders"),
+    ("pause_mine/not_paused", "None of your reminders are paused"),
+    (
+        "preview/intro",
+        "Here are the next {count} reminder(s) for {channel}:",
+    ),
+    ("preview/sent", "Sent you {count} reminder(s)"),
+    ("preview/none", "There are no reminders to preview"),
+    (
+        "preview/dm_failed",
+        "I couldn't DM you. Please check your privacy settings",
+    ),
+    (
+        "preview/dms_closed",
+        "I couldn't DM you because your DMs are closed. Until you use `{prefix}dms on`, other people can't set reminders for your DMs",
+    ),
+    (
+        "quiet/current",
+        "Your quiet hours are {start} to {end}",
+    ),
+    (
+        "quiet/current_active",
+        "Your quiet hours are {start} to {end}, and they're on now",
+    ),
+    ("quiet/none", "You haven't set quiet hours"),
+    ("quiet/off", "Removed your quiet hours"),
+    (
+        "quiet/set",
+        "Reminders to your DMs will be held back from {start} to {end}",
+    ),
+    (
+        "quiet/set_silent",
+        "Reminders to your DMs will be sent silently from {start} to {end}",
+    ),
+    (
+        "quiet/invalid_time",
+        "Please give a start and end time like `22:00 07:00`, or `off`",
+    ),
+    (
+        "remind/generic_error",
+        "The reminder couldn't be set because of an error: {error}",
+    ),
+    (
+        "remind/user_limit",
+        "You already have the most reminders you can have ({max_reminders})",
+    ),
+    (
+        "remind/dm_opt_out",
+        "That user doesn't accept reminders from others in their DMs",
+    ),
+    (
+        "remind/content_too_long",
+        "Reminder content can be at most {max_length} characters",
+    ),
+    ("remind/more_errors", "…and {count} more"),
+    (
+        "remind/duplicate_request",
+        "This reminder is already being set from your message",
+    ),
+    (
+        "remind/nudged_past_time",
+        "This channel's nudge would move the reminder into the past",
+    ),
+    (
+        "remind/heads_up_past_time",
+        "A heads-up would be in the past, so it wasn't set",
+    ),
+    ("remind/heads_up_content", "Coming up: {content}"),
+    (
+        "remind/invalid_heads_up",
+        "Please give heads-ups as durations, like `--heads-up 1h,10m`",
+    ),
+    (
+        "remind/invalid_fallback",
+        "Please give up to {max} fallback channels in this server",
+    ),
+    (
+        "remind/duplicate",
+        "A reminder with the same content was just set in {location}. Set another?",
+    ),
+    (
+        "remind/publish_no_permission",
+        "I need Manage Messages in that channel to publish reminders",
+    ),
+    (
+        "remind/publish_not_announcement",
+        "Only reminders in announcement channels can be published",
+    ),
+    (
+        "remind/reply_not_found",
+        "Couldn't find the message to reply to",
+    ),
+    (
+        "remind/reply_wrong_channel",
+        "The message to reply to isn't in {location}",
+    ),
+    (
+        "remind/too_many_targets",
+        "That's {number} places. Reminders can be set for at most {max} at once",
+    ),
+    (
+        "restrict/enabled_until",
+        "Enabled the commands for this role until {time}",
+    ),
+    (
+        "restrict/invalid_duration",
+        "Please give a duration like `1h` or `7d`",
+    ),
+    ("restrict/menu_role", "Pick a role to restrict"),
+    ("restrict/menu_commands", "Pick the commands {role} can use"),
+    (
+        "restrict/menu_no_roles",
+        "This server has no roles to restrict",
+    ),
+    (
+        "restrict/permission_invalid",
+        "`{permission}` isn't a permission. Use one of: {permissions}",
+    ),
+    (
+        "restrict/permission_set",
+        "`{command}` now needs {permissions}",
+    ),
+    (
+        "restrict/permission_removed",
+        "`{command}` no longer needs extra permissions",
+    ),
+    ("restrict/role_not_found", "Couldn't find that role"),
+    ("restrict/show_title", "Restrictions"),
+    ("restrict/show_none", "{role} has no commands allowed"),
+    ("settings/exported", "Here are this server's settings"),
+    (
+        "settings/no_attachment",
+        "Please attach a settings file from `settings export`",
+    ),
+    ("settings/download_failed", "Couldn't download that file"),
+    ("settings/invalid", "That isn't a settings file: {error}"),
+    (
+        "settings/confirm",
+        "This will add the file's settings to this server's. Continue?",
+    ),
+    ("settings/imported", "Imported the settings"),
+    (
+        "settings/import_failed",
+        "Couldn't import the settings. Nothing was changed",
+    ),
+    ("settings/conflict_prefix", "Skipped the prefix `{name}`"),
+    ("settings/conflict_role", "Skipped the missing role `{name}`"),
+    (
+        "settings/conflict_restriction",
+        "Skipped the restriction on `{name}`",
+    ),
+    ("settings/conflict_alias", "Skipped the alias `{name}`"),
+    ("settings/conflict_template", "Skipped the template `{name}`"),
+    ("settings/conflict_command", "Skipped disabling `{name}`"),
+    (
+        "snooze/confirm",
+        "Snooze {count} overdue reminder(s) in this channel?",
+    ),
+    ("snooze/none", "There are no overdue reminders in this channel"),
+    ("snooze/success", "Snoozed {count} reminder(s)"),
+    ("stats/title", "Upcoming reminders"),
+    ("stats/hour", "Next hour"),
+    ("stats/day", "Next 24 hours"),
+    ("stats/week", "Next 7 days"),
+    (
+        "template/help",
+        "Use `template list`, `template create`, `template embed` or `template delete`",
+    ),
+    ("template/none", "This server has no templates"),
+    ("template/created", "Saved the template `{name}`"),
+    ("template/deleted", "Deleted the template"),
+    (
+        "template/too_long",
+        "Template content can be up to {max_length} characters",
+    ),
+    (
+        "template/embed_set",
+        "Reminders from the template `{name}` will be sent with that embed",
+    ),
+    (
+        "template/embed_removed",
+        "Reminders from the template `{name}` will be sent without an embed",
+    ),
+    (
+        "template/embed_too_long",
+        "Embed titles can be up to {max_title} characters, and descriptions up to {max_description}",
+    ),
+    ("template/not_found", "There's no template with that name"),
+    (
+        "template/invalid_name",
+        "Template names can be up to 24 letters, numbers, `-` and `_`",
+    ),
+    (
+        "template/limit",
+        "This server already has the most templates it can have",
+    ),
+    ("time/days", "days"),
+    ("time/hours", "hours"),
+    ("time/minutes", "minutes"),
+    ("time/seconds", "seconds"),
+    ("timer/name_taken", "You already have a timer with that name"),
+    ("timer/none", "You have no timers"),
+    ("timer/renamed", "Renamed the timer to `{name}`"),
+    (
+        "timezone/shifted",
+        "Moved {count} reminder(s) to keep their local time",
+    ),
+    (
+        "timezone/shift_failed",
+        "Couldn't move your reminders, so they keep their old times",
+    ),
+    (
+        "todo/error_permission",
+        "You can't change that todo list",
+    ),
+    ("todo/moved", "Moved `{}` to {name}"),
+    ("webhook/ok", "This channel's webhook `{id}` is working"),
+    (
+        "webhook/unreachable",
+        "This channel's webhook `{id}` can't be reached",
+    ),
+    ("webhook/none", "This channel has no webhook"),
+    ("webhook/recreate", "Make a new webhook for this channel?"),
+    ("webhook/recreated", "Made a new webhook `{id}`"),
+];
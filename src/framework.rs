@@ -7,25 +7,72 @@ use serenity::{
     http::Http,
     model::{
         channel::{Channel, GuildChannel, Message},
-        guild::{Guild, Member},
+        guild::Member,
         id::{ChannelId, MessageId},
+        permissions::Permissions,
     },
-    Result as SerenityResult,
+    Error as SerenityError, Result as SerenityResult,
 };
 
-use log::{error, info, warn};
+use log::{debug, error, info, warn};
 
-use regex::{Match, Regex, RegexBuilder};
+use regex::{Regex, RegexBuilder};
 
-use std::{collections::HashMap, fmt};
+use std::{collections::HashMap, fmt, time::Instant};
 
 use crate::{
+    consts::SLOW_COMMAND_THRESHOLD,
+    get_ctx_data,
+    guild_context::{guild_channel, guild_context, guild_member, GuildContext},
     language_manager::LanguageManager,
-    models::{channel_data::ChannelData, guild_data::GuildData, user_data::UserData, CtxGuildData},
+    metrics::Metrics,
+    models::{channel_data::ChannelData, user_data::UserData, CtxGuildData},
     LimitExecutors, SQLPool,
 };
 
-type CommandFn = for<'fut> fn(&'fut Context, &'fut Message, String) -> BoxFuture<'fut, ()>;
+type CommandFn =
+    for<'fut> fn(&'fut Context, &'fut Message, String) -> BoxFuture<'fut, CommandResult>;
+
+pub type CommandResult = Result<(), CommandError>;
+
+/// An error a command returns instead of panicking. It is logged, and the user is told that
+/// something went wrong
+#[derive(Debug)]
+pub enum CommandError {
+    Database(sqlx::Error),
+    Discord(SerenityError),
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Database(e) => write!(f, "database error: {}", e),
+            Self::Discord(e) => write!(f, "Discord error: {}", e),
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl From<sqlx::Error> for CommandError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
+}
+
+impl From<SerenityError> for CommandError {
+    fn from(e: SerenityError) -> Self {
+        Self::Discord(e)
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for CommandError {
+    fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        Self::Other(e)
+    }
+}
 
 #[derive(Debug, PartialEq)]
 pub enum PermissionLevel {
@@ -39,15 +86,87 @@ pub struct Command {
     pub required_perms: PermissionLevel,
     pub supports_dm: bool,
     pub can_blacklist: bool,
+    pub can_disable: bool,
     pub func: CommandFn,
 }
 
 impl Command {
-    async fn check_permissions(&self, ctx: &Context, guild: &Guild, member: &Member) -> bool {
+    /// Run the command, logging how long it took. Commands taking at least
+    /// `SLOW_COMMAND_THRESHOLD` milliseconds are logged as warnings
+    async fn execute(
+        &self,
+        ctx: &Context,
+        msg: &Message,
+        args: String,
+        in_dm: bool,
+    ) -> CommandResult {
+        let start = Instant::now();
+        let res = (self.func)(ctx, msg, args).await;
+        let elapsed = start.elapsed().as_millis();
+
+        if *SLOW_COMMAND_THRESHOLD != 0 && elapsed >= *SLOW_COMMAND_THRESHOLD as u128 {
+            warn!(
+                "Slow command {} (dm: {}): took {}ms",
+                self.name, in_dm, elapsed
+            );
+        } else {
+            debug!("Command {} (dm: {}) took {}ms", self.name, in_dm, elapsed);
+        }
+
+        res
+    }
+
+    /// Whether the guild has turned this command off for everyone
+    pub async fn is_disabled(&self, ctx: &Context, guild: &GuildContext) -> bool {
+        if !self.can_disable {
+            return false;
+        }
+
+        let pool = ctx
+            .data
+            .read()
+            .await
+            .get::<SQLPool>()
+            .cloned()
+            .expect("Could not get SQLPool from data");
+
+        match sqlx::query!(
+            "
+SELECT COUNT(1) AS count
+FROM disabled_commands
+WHERE
+    command = ? AND
+    guild_id = (SELECT id FROM guilds WHERE guild = ?)
+            ",
+            self.name,
+            guild.id.as_u64()
+        )
+        .fetch_one(&pool)
+        .await
+        {
+            Ok(row) => row.count > 0,
+
+            Err(e) => {
+                warn!(
+                    "Unexpected error occurred querying disabled_commands: {:?}",
+                    e
+                );
+
+                false
+            }
+        }
+    }
+
+    pub async fn check_permissions(
+        &self,
+        ctx: &Context,
+        guild: &GuildContext,
+        member: &Member,
+    ) -> bool {
         if self.required_perms == PermissionLevel::Unrestricted {
             true
         } else {
-            let permissions = guild.member_permissions(&ctx, &member.user).await.unwrap();
+            let permissions = guild.member_permissions(member);
 
             if permissions.manage_guild()
                 || (permissions.manage_messages()
@@ -65,6 +184,40 @@ impl Command {
                     .cloned()
                     .expect("Could not get SQLPool from data");
 
+                // a guild can also allow the command to anyone holding a Discord permission
+                match sqlx::query!(
+                    "
+SELECT permissions
+FROM command_permissions
+WHERE
+    command = ? AND
+    guild_id = (SELECT id FROM guilds WHERE guild = ?)
+                    ",
+                    self.name,
+                    guild.id.as_u64()
+                )
+                .fetch_optional(&pool)
+                .await
+                {
+                    Ok(Some(row)) => {
+                        let required = Permissions::from_bits_truncate(row.permissions);
+
+                        // bits that truncate to nothing grant nothing, rather than everything
+                        if !required.is_empty() && permissions.contains(required) {
+                            return true;
+                        }
+                    }
+
+                    Ok(None) => {}
+
+                    Err(e) => {
+                        warn!(
+                            "Unexpected error occurred querying command_permissions: {:?}",
+                            e
+                        );
+                    }
+                }
+
                 match sqlx::query!(
                     "
 SELECT
@@ -75,6 +228,7 @@ INNER JOIN
     command_restrictions ON roles.id = command_restrictions.role_id
 WHERE
     command_restrictions.command = ? AND
+    (command_restrictions.expires IS NULL OR command_restrictions.expires > NOW()) AND
     roles.guild_id = (
         SELECT
             id
@@ -130,6 +284,7 @@ impl fmt::Debug for Command {
             .field("required_perms", &self.required_perms)
             .field("supports_dm", &self.supports_dm)
             .field("can_blacklist", &self.can_blacklist)
+            .field("can_disable", &self.can_disable)
             .finish()
     }
 }
@@ -181,11 +336,12 @@ pub struct RegexFramework {
     pub commands: HashMap<String, &'static Command>,
     command_matcher: Regex,
     dm_regex_matcher: Regex,
-    default_prefix: String,
+    default_prefixes: Vec<String>,
     client_id: u64,
     ignore_bots: bool,
     case_insensitive: bool,
     dm_enabled: bool,
+    executing_notice: bool,
 }
 
 impl RegexFramework {
@@ -194,11 +350,12 @@ impl RegexFramework {
             commands: HashMap::new(),
             command_matcher: Regex::new(r#"^$"#).unwrap(),
             dm_regex_matcher: Regex::new(r#"^$"#).unwrap(),
-            default_prefix: "".to_string(),
+            default_prefixes: vec![],
             client_id: client_id.into(),
             ignore_bots: true,
             case_insensitive: true,
             dm_enabled: true,
+            executing_notice: true,
         }
     }
 
@@ -208,8 +365,15 @@ impl RegexFramework {
         self
     }
 
-    pub fn default_prefix<T: ToString>(mut self, new_prefix: T) -> Self {
-        self.default_prefix = new_prefix.to_string();
+    /// Prefixes that guilds without their own respond to. The first is the one guilds are given
+    pub fn default_prefixes<T: ToString>(
+        mut self,
+        new_prefixes: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.default_prefixes = new_prefixes
+            .into_iter()
+            .map(|prefix| prefix.to_string())
+            .collect();
 
         self
     }
@@ -226,78 +390,96 @@ impl RegexFramework {
         self
     }
 
-    pub fn add_command<S: ToString>(mut self, name: S, command: &'static Command) -> Self {
-        self.commands.insert(name.to_string(), command);
+    /// Whether to tell users their command was ignored because another of theirs is running
+    pub fn executing_notice(mut self, executing_notice: bool) -> Self {
+        self.executing_notice = executing_notice;
 
         self
     }
 
-    pub fn build(mut self) -> Self {
-        {
-            let command_names;
+    pub fn add_command<S: ToString>(mut self, name: S, command: &'static Command) -> Self {
+        self.commands.insert(name.to_string(), command);
 
-            {
-                let mut command_names_vec =
-                    self.commands.keys().map(|k| &k[..]).collect::<Vec<&str>>();
+        self
+    }
 
-                command_names_vec.sort_unstable_by_key(|a| a.len());
+    /// Join command names into a regex alternation, escaping any metacharacters in the names.
+    fn command_names_pattern<'a>(names: impl Iterator<Item = &'a str>) -> String {
+        let mut command_names_vec = names.map(regex::escape).collect::<Vec<String>>();
 
-                command_names = command_names_vec.join("|");
-            }
+        command_names_vec.sort_unstable_by_key(|a| a.len());
 
-            info!("Command names: {}", command_names);
+        command_names_vec.join("|")
+    }
 
-            {
-                let match_string = r#"^(?:(?:<@ID>\s*)|(?:<@!ID>\s*)|(?P<prefix>\S{1,5}?))(?P<cmd>COMMANDS)(?:$|\s+(?P<args>.*))$"#
-                    .replace("COMMANDS", command_names.as_str())
-                    .replace("ID", self.client_id.to_string().as_str());
-
-                self.command_matcher = RegexBuilder::new(match_string.as_str())
-                    .case_insensitive(self.case_insensitive)
-                    .dot_matches_new_line(true)
-                    .build()
-                    .unwrap();
-            }
-        }
+    fn build_matcher(&self, template: &str, command_names: &str) -> Regex {
+        // the longest prefixes come first, so `!!` isn't matched as `!` when both are configured
+        let mut prefixes = self
+            .default_prefixes
+            .iter()
+            .map(|prefix| regex::escape(prefix))
+            .collect::<Vec<String>>();
+
+        prefixes.sort_unstable_by_key(|prefix| std::cmp::Reverse(prefix.len()));
+
+        // substitute the ID first so that the prefix or command names can't be mistaken for the
+        // placeholder
+        let match_string = template
+            .replace("ID", self.client_id.to_string().as_str())
+            .replace("PREFIX", &prefixes.join("|"))
+            .replace("COMMANDS", command_names);
+
+        RegexBuilder::new(match_string.as_str())
+            .case_insensitive(self.case_insensitive)
+            .dot_matches_new_line(true)
+            .build()
+            .unwrap()
+    }
 
-        {
-            let dm_command_names;
+    pub fn build(mut self) -> Self {
+        let command_names = Self::command_names_pattern(self.commands.keys().map(|k| &k[..]));
 
-            {
-                let mut command_names_vec = self
-                    .commands
-                    .iter()
-                    .filter_map(|(key, command)| {
-                        if command.supports_dm {
-                            Some(&key[..])
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<&str>>();
+        info!("Command names: {}", command_names);
 
-                command_names_vec.sort_unstable_by_key(|a| a.len());
+        self.command_matcher = self.build_matcher(
+            r#"^(?P<mention>(?:<@ID>\s*)|(?:<@!ID>\s*))?(?P<cmd>COMMANDS)(?:$|\s+(?P<args>.*))$"#,
+            &command_names,
+        );
 
-                dm_command_names = command_names_vec.join("|");
-            }
+        let dm_command_names = Self::command_names_pattern(
+            self.commands
+                .iter()
+                .filter(|(_, command)| command.supports_dm)
+                .map(|(key, _)| &key[..]),
+        );
 
-            {
-                let match_string = r#"^(?:(?:<@ID>\s+)|(?:<@!ID>\s+)|(\$)|())(?P<cmd>COMMANDS)(?:$|\s+(?P<args>.*))$"#
-                    .replace("COMMANDS", dm_command_names.as_str())
-                    .replace("ID", self.client_id.to_string().as_str());
-
-                self.dm_regex_matcher = RegexBuilder::new(match_string.as_str())
-                    .case_insensitive(self.case_insensitive)
-                    .dot_matches_new_line(true)
-                    .build()
-                    .unwrap();
-            }
-        }
+        self.dm_regex_matcher = self.build_matcher(
+            r#"^(?:(?:<@ID>\s+)|(?:<@!ID>\s+)|(PREFIX)|())(?P<cmd>COMMANDS)(?:$|\s+(?P<args>.*))$"#,
+            &dm_command_names,
+        );
 
         self
     }
 }
 
+/// Strip the longest of `prefixes` from the start of `content`. Prefixes are matched literally,
+/// so they're stripped before looking for a command
+fn strip_prefix<'a>(content: &'a str, prefixes: &[String]) -> Option<&'a str> {
+    prefixes
+        .iter()
+        .filter(|prefix| content.starts_with(prefix.as_str()))
+        .max_by_key(|prefix| prefix.len())
+        .map(|prefix| {
+            // word prefixes like `hey bot` are separated from the command by a space. symbol
+            // prefixes keep it, so `$ in 5 minutes...` still reaches natural
+            if prefix.ends_with(char::is_alphanumeric) {
+                content[prefix.len()..].trim_start()
+            } else {
+                &content[prefix.len()..]
+            }
+        })
+}
+
 enum PermissionCheck {
     None,              // No permissions
     Basic(bool, bool), // Send + Embed permissions (sufficient to reply)
@@ -309,47 +491,42 @@ impl Framework for RegexFramework {
     async fn dispatch(&self, ctx: Context, msg: Message) {
         async fn check_self_permissions(
             ctx: &Context,
-            guild: &Guild,
+            guild: &GuildContext,
             channel: &GuildChannel,
+            direct_delivery: bool,
         ) -> SerenityResult<PermissionCheck> {
-            let user_id = ctx.cache.current_user_id();
+            let member = guild_member(ctx, guild.id, ctx.cache.current_user_id()).await?;
 
-            let guild_perms = guild.member_permissions(&ctx, user_id).await?;
-            let channel_perms = channel.permissions_for_user(ctx, user_id)?;
+            let guild_perms = guild.member_permissions(&member);
+            let channel_perms = guild.channel_permissions(channel, &member);
 
             let basic_perms = channel_perms.send_messages();
+            // webhooks aren't needed when reminders are sent as the bot itself
+            let manage_webhooks = guild_perms.manage_webhooks() || direct_delivery;
 
             Ok(
-                if basic_perms && guild_perms.manage_webhooks() && channel_perms.embed_links() {
+                if basic_perms && manage_webhooks && channel_perms.embed_links() {
                     PermissionCheck::All
                 } else if basic_perms {
-                    PermissionCheck::Basic(
-                        guild_perms.manage_webhooks(),
-                        channel_perms.embed_links(),
-                    )
+                    PermissionCheck::Basic(manage_webhooks, channel_perms.embed_links())
                 } else {
                     PermissionCheck::None
                 },
             )
         }
 
-        async fn check_prefix(ctx: &Context, guild: &Guild, prefix_opt: Option<Match<'_>>) -> bool {
-            if let Some(prefix) = prefix_opt {
-                let guild_prefix = ctx.prefix(Some(guild.id)).await;
-
-                guild_prefix.as_str() == prefix.as_str()
-            } else {
-                true
-            }
-        }
-
         // gate to prevent analysing messages unnecessarily
         if (msg.author.bot && self.ignore_bots) || msg.content.is_empty() {
         } else {
             // Guild Command
-            if let (Some(guild), Ok(Channel::Guild(channel))) =
-                (msg.guild(&ctx), msg.channel(&ctx).await)
-            {
+            if let Some(guild_id) = msg.guild_id {
+                let guild_prefix = ctx.prefix(Some(guild_id)).await;
+                let direct_delivery = match ctx.guild_data(guild_id).await {
+                    Ok(guild_data) => guild_data.read().await.direct_delivery,
+
+                    Err(_) => false,
+                };
+
                 let data = ctx.data.read().await;
 
                 let pool = data
@@ -357,13 +534,51 @@ impl Framework for RegexFramework {
                     .cloned()
                     .expect("Could not get SQLPool from data");
 
-                if let Some(full_match) = self.command_matcher.captures(&msg.content) {
-                    if check_prefix(&ctx, &guild, full_match.name("prefix")).await {
+                // guilds that haven't set a prefix of their own respond to any of the defaults
+                let prefixes = if self.default_prefixes.first() == Some(&guild_prefix) {
+                    &self.default_prefixes[..]
+                } else {
+                    std::slice::from_ref(&guild_prefix)
+                };
+
+                // without a prefix, the command must be addressed with a mention instead
+                let (content, prefixed) = match strip_prefix(&msg.content, prefixes) {
+                    Some(content) => (content, true),
+                    None => (msg.content.as_str(), false),
+                };
+
+                if let Some(full_match) = self.command_matcher.captures(content) {
+                    if prefixed || full_match.name("mention").is_some() {
                         let lm = data.get::<LanguageManager>().unwrap();
 
                         let language = UserData::language_of(&msg.author, &pool);
 
-                        match check_self_permissions(&ctx, &guild, &channel).await {
+                        // the guild may not be cached yet, such as just after startup. Its
+                        // details are then fetched instead
+                        let (guild, channel, member) = match (
+                            guild_context(&ctx, guild_id).await,
+                            guild_channel(&ctx, guild_id, msg.channel_id).await,
+                            guild_member(&ctx, guild_id, msg.author.id).await,
+                        ) {
+                            (Ok(guild), Ok(channel), Ok(member)) => (guild, channel, member),
+
+                            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+                                warn!(
+                                    "Couldn't get guild {} to check command {}: {:?}",
+                                    guild_id, msg.id, e
+                                );
+
+                                let _ = msg
+                                    .channel_id
+                                    .say(&ctx, lm.get(&language.await, "guild_unavailable"))
+                                    .await;
+
+                                return;
+                            }
+                        };
+
+                        match check_self_permissions(&ctx, &guild, &channel, direct_delivery).await
+                        {
                             Ok(perms) => match perms {
                                 PermissionCheck::All => {
                                     let command = self
@@ -378,43 +593,66 @@ impl Framework for RegexFramework {
                                         .unwrap();
 
                                     let channel_data = ChannelData::from_channel(
-                                        msg.channel(&ctx).await.unwrap(),
+                                        Channel::Guild(channel.clone()),
                                         &pool,
                                     )
                                     .await
                                     .unwrap();
 
-                                    if !command.can_blacklist || !channel_data.blacklisted {
+                                    if command.is_disabled(&ctx, &guild).await {
+                                        let _ = msg
+                                            .channel_id
+                                            .say(&ctx, lm.get(&language.await, "command_disabled"))
+                                            .await;
+                                    } else if !command.can_blacklist || !channel_data.blacklisted {
                                         let args = full_match
                                             .name("args")
                                             .map(|m| m.as_str())
                                             .unwrap_or("")
                                             .to_string();
 
-                                        let member = guild.member(&ctx, &msg.author).await.unwrap();
-
                                         if command.check_permissions(&ctx, &guild, &member).await {
                                             dbg!(command.name);
 
-                                            {
-                                                let guild_id = guild.id.as_u64().to_owned();
-
-                                                GuildData::from_guild(guild, &pool)
-                                                    .await
-                                                    .unwrap_or_else(|_| {
-                                                        panic!(
-                                                        "Failed to create new guild object for {}",
-                                                        guild_id
-                                                    )
-                                                    });
-                                            }
-
                                             if msg.id == MessageId(0)
                                                 || !ctx.check_executing(msg.author.id).await
                                             {
+                                                if let Some(metrics) = data.get::<Metrics>() {
+                                                    metrics.command_executed(
+                                                        command.name,
+                                                        ctx.shard_id,
+                                                    );
+                                                }
+
                                                 ctx.set_executing(msg.author.id).await;
-                                                (command.func)(&ctx, &msg, args).await;
+                                                let res =
+                                                    command.execute(&ctx, &msg, args, false).await;
                                                 ctx.drop_executing(msg.author.id).await;
+
+                                                if let Err(e) = res {
+                                                    error!(
+                                                        "Error in command {}: {}",
+                                                        command.name, e
+                                                    );
+
+                                                    let _ = msg
+                                                        .channel_id
+                                                        .say(
+                                                            &ctx,
+                                                            lm.get(
+                                                                &language.await,
+                                                                "command_error",
+                                                            ),
+                                                        )
+                                                        .await;
+                                                }
+                                            } else if self.executing_notice
+                                                && ctx.claim_executing_notice(msg.author.id).await
+                                            {
+                                                let _ = msg
+                                                    .channel_id
+                                                    .say(&ctx, lm.get(&language.await, "too_fast"))
+                                                    .await;
                                             }
                                         } else if command.required_perms
                                             == PermissionLevel::Restricted
@@ -489,12 +727,202 @@ impl Framework for RegexFramework {
                     dbg!(command.name);
 
                     if msg.id == MessageId(0) || !ctx.check_executing(msg.author.id).await {
+                        if let Some(metrics) = ctx.data.read().await.get::<Metrics>() {
+                            metrics.command_executed(command.name, ctx.shard_id);
+                        }
+
                         ctx.set_executing(msg.author.id).await;
-                        (command.func)(&ctx, &msg, args).await;
+                        let res = command.execute(&ctx, &msg, args, true).await;
                         ctx.drop_executing(msg.author.id).await;
+
+                        if let Err(e) = res {
+                            error!("Error in command {}: {}", command.name, e);
+
+                            let (pool, lm) = get_ctx_data(&&ctx).await;
+
+                            let language = UserData::language_of(&msg.author, &pool).await;
+
+                            let _ = msg
+                                .channel_id
+                                .say(&ctx, lm.get(&language, "command_error"))
+                                .await;
+                        }
+                    } else if self.executing_notice
+                        && ctx.claim_executing_notice(msg.author.id).await
+                    {
+                        let (pool, lm) = get_ctx_data(&&ctx).await;
+
+                        let language = UserData::language_of(&msg.author, &pool).await;
+
+                        let _ = msg
+                            .channel_id
+                            .say(&ctx, lm.get(&language, "too_fast"))
+                            .await;
                     }
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::commands::{info_cmds, reminder_cmds};
+
+    fn framework() -> RegexFramework {
+        RegexFramework::new(1u64)
+            .default_prefixes(vec!["$"])
+            .add_command("ping", &info_cmds::PING_COMMAND)
+            .add_command("remind", &reminder_cmds::REMIND_COMMAND)
+            .add_command("webhook", &reminder_cmds::WEBHOOK_COMMAND)
+            .build()
+    }
+
+    fn command_args<'a>(matcher: &Regex, content: &'a str) -> Option<(&'a str, Option<&'a str>)> {
+        matcher.captures(content).map(|captures| {
+            (
+                captures.name("cmd").unwrap().as_str(),
+                captures.name("args").map(|args| args.as_str()),
+            )
+        })
+    }
+
+    #[test]
+    fn command_matcher_args() {
+        let framework = framework();
+
+        assert_eq!(
+            command_args(&framework.command_matcher, "ping"),
+            Some(("ping", None))
+        );
+        assert_eq!(
+            command_args(&framework.command_matcher, "remind 10m hello\nthere"),
+            Some(("remind", Some("10m hello\nthere")))
+        );
+        assert_eq!(
+            command_args(&framework.command_matcher, "PING"),
+            Some(("PING", None))
+        );
+        assert_eq!(command_args(&framework.command_matcher, "pingu"), None);
+    }
+
+    #[test]
+    fn command_matcher_mention() {
+        let framework = framework();
+
+        for content in &["<@1> remind 10m hello", "<@!1>remind 10m hello"] {
+            let captures = framework.command_matcher.captures(content).unwrap();
+
+            assert!(captures.name("mention").is_some());
+            assert_eq!(captures.name("cmd").unwrap().as_str(), "remind");
+            assert_eq!(captures.name("args").unwrap().as_str(), "10m hello");
+        }
+
+        // another user's mention isn't the bot's
+        assert!(framework.command_matcher.captures("<@2> ping").is_none());
+    }
+
+    #[test]
+    fn dm_matcher_args() {
+        let framework = framework();
+
+        for content in &[
+            "$remind 10m hello",
+            "remind 10m hello",
+            "<@1> remind 10m hello",
+        ] {
+            assert_eq!(
+                command_args(&framework.dm_regex_matcher, content),
+                Some(("remind", Some("10m hello")))
+            );
+        }
+
+        // commands that don't support DMs can't be matched there
+        assert!(framework.command_matcher.is_match("webhook"));
+        assert!(!framework.dm_regex_matcher.is_match("webhook"));
+        assert!(!framework.dm_regex_matcher.is_match("$webhook"));
+    }
+
+    #[test]
+    fn command_names_escaped() {
+        let framework = RegexFramework::new(1u64)
+            .default_prefixes(vec!["$"])
+            .add_command("t+", &info_cmds::PING_COMMAND)
+            .build();
+
+        assert!(framework.command_matcher.is_match("t+"));
+        assert!(!framework.command_matcher.is_match("tt"));
+        assert!(framework.dm_regex_matcher.is_match("$t+ now"));
+    }
+
+    #[test]
+    fn prefix_escaped() {
+        for prefix in &[".", "+", "a|b"] {
+            let framework = RegexFramework::new(1u64)
+                .default_prefixes(vec![*prefix])
+                .add_command("ping", &info_cmds::PING_COMMAND)
+                .build();
+
+            assert_eq!(
+                command_args(&framework.dm_regex_matcher, &format!("{}ping now", prefix)),
+                Some(("ping", Some("now")))
+            );
+            assert_eq!(
+                strip_prefix(&format!("{}ping", prefix), &[prefix.to_string()]),
+                Some("ping")
+            );
+        }
+
+        // `.` is matched literally rather than as any character
+        let framework = RegexFramework::new(1u64)
+            .default_prefixes(vec!["."])
+            .add_command("ping", &info_cmds::PING_COMMAND)
+            .build();
+
+        assert!(!framework.dm_regex_matcher.is_match("xping"));
+        assert_eq!(strip_prefix("xping", &[".".to_string()]), None);
+    }
+
+    fn prefixes() -> Vec<String> {
+        vec!["$".to_string(), "!".to_string()]
+    }
+
+    #[test]
+    fn strip_prefix_each_default() {
+        assert_eq!(strip_prefix("$ping", &prefixes()), Some("ping"));
+        assert_eq!(strip_prefix("!ping", &prefixes()), Some("ping"));
+        assert_eq!(strip_prefix("?ping", &prefixes()), None);
+    }
+
+    #[test]
+    fn strip_prefix_longest() {
+        let prefixes = vec!["!".to_string(), "!!".to_string()];
+
+        assert_eq!(strip_prefix("!!ping", &prefixes), Some("ping"));
+    }
+
+    #[test]
+    fn strip_prefix_word() {
+        let prefixes = vec!["hey bot".to_string()];
+
+        assert_eq!(strip_prefix("hey bot  ping", &prefixes), Some("ping"));
+    }
+
+    #[test]
+    fn dm_matcher_each_default() {
+        let framework = RegexFramework::new(1u64)
+            .default_prefixes(prefixes())
+            .add_command("ping", &info_cmds::PING_COMMAND)
+            .build();
+
+        for content in &["$ping", "!ping", "ping"] {
+            let captures = framework.dm_regex_matcher.captures(content).unwrap();
+
+            assert_eq!(captures.name("cmd").unwrap().as_str(), "ping");
+        }
+
+        assert!(framework.dm_regex_matcher.captures("?ping").is_none());
+    }
+}
@@ -0,0 +1,367 @@
+use serenity::{
+    client::Context,
+    model::{
+        channel::{Channel, GuildChannel, PermissionOverwrite, PermissionOverwriteType},
+        guild::{Guild, Member, PartialGuild},
+        id::{ChannelId, GuildId, RoleId, UserId},
+        permissions::Permissions,
+    },
+    prelude::TypeMapKey,
+    Error as SerenityError, Result as SerenityResult,
+};
+
+use dashmap::DashMap;
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// How long details fetched for an uncached guild are used before they're fetched again
+const FETCHED_GUILD_TTL: Duration = Duration::from_secs(60);
+
+/// What the framework's checks read from a guild: its owner and the permissions of its roles.
+/// Built from the cache, or from the guild fetched over HTTP while it isn't cached, such as just
+/// after startup
+pub struct GuildContext {
+    pub id: GuildId,
+    pub name: String,
+    owner_id: UserId,
+    roles: HashMap<RoleId, Permissions>,
+}
+
+impl GuildContext {
+    pub fn from_guild(guild: &Guild) -> Self {
+        Self {
+            id: guild.id,
+            name: guild.name.clone(),
+            owner_id: guild.owner_id,
+            roles: guild
+                .roles
+                .iter()
+                .map(|(id, role)| (*id, role.permissions))
+                .collect(),
+        }
+    }
+
+    fn from_partial_guild(guild: PartialGuild) -> Self {
+        Self {
+            id: guild.id,
+            name: guild.name,
+            owner_id: guild.owner_id,
+            roles: guild
+                .roles
+                .iter()
+                .map(|(id, role)| (*id, role.permissions))
+                .collect(),
+        }
+    }
+
+    /// The member's permissions across the guild
+    pub fn member_permissions(&self, member: &Member) -> Permissions {
+        self.permissions(member.user.id, &member.roles, &[])
+    }
+
+    /// The member's permissions in a channel, once its overwrites are applied
+    pub fn channel_permissions(&self, channel: &GuildChannel, member: &Member) -> Permissions {
+        let permissions = self.permissions(
+            member.user.id,
+            &member.roles,
+            &channel.permission_overwrites,
+        );
+
+        // nothing can be done in a channel that can't be seen
+        if permissions.view_channel() {
+            permissions
+        } else {
+            Permissions::empty()
+        }
+    }
+
+    fn permissions(
+        &self,
+        user_id: UserId,
+        member_roles: &[RoleId],
+        overwrites: &[PermissionOverwrite],
+    ) -> Permissions {
+        if user_id == self.owner_id {
+            return Permissions::all();
+        }
+
+        // the @everyone role shares the guild's ID
+        let everyone = RoleId(self.id.0);
+
+        let mut permissions = member_roles
+            .iter()
+            .chain(std::iter::once(&everyone))
+            .filter_map(|role| self.roles.get(role))
+            .fold(Permissions::empty(), |permissions, role| {
+                permissions | *role
+            });
+
+        if permissions.administrator() {
+            return Permissions::all();
+        }
+
+        // overwrites for @everyone apply first, then those for all of the member's roles
+        // together, then the member's own
+        let mut role_allow = Permissions::empty();
+        let mut role_deny = Permissions::empty();
+
+        for overwrite in overwrites {
+            match overwrite.kind {
+                PermissionOverwriteType::Role(role) if role == everyone => {
+                    permissions = (permissions & !overwrite.deny) | overwrite.allow;
+                }
+
+                PermissionOverwriteType::Role(role) if member_roles.contains(&role) => {
+                    role_allow |= overwrite.allow;
+                    role_deny |= overwrite.deny;
+                }
+
+                _ => {}
+            }
+        }
+
+        permissions = (permissions & !role_deny) | role_allow;
+
+        for overwrite in overwrites {
+            if overwrite.kind == PermissionOverwriteType::Member(user_id) {
+                permissions = (permissions & !overwrite.deny) | overwrite.allow;
+            }
+        }
+
+        permissions
+    }
+}
+
+/// Details fetched over HTTP for guilds that aren't cached, kept for `FETCHED_GUILD_TTL` so that
+/// each command doesn't fetch them again
+#[derive(Default)]
+pub struct FetchedGuilds {
+    guilds: DashMap<GuildId, (Arc<GuildContext>, Instant)>,
+    members: DashMap<(GuildId, UserId), (Member, Instant)>,
+    channels: DashMap<ChannelId, (GuildChannel, Instant)>,
+}
+
+impl FetchedGuilds {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Forget what was fetched for a guild, once it's cached or has been left
+    pub fn remove(&self, guild_id: GuildId) {
+        self.guilds.remove(&guild_id);
+        self.members.retain(|(guild, _), _| *guild != guild_id);
+        self.channels
+            .retain(|_, (channel, _)| channel.guild_id != guild_id);
+    }
+
+    fn get<K: Eq + Hash, V: Clone>(map: &DashMap<K, (V, Instant)>, key: &K) -> Option<V> {
+        map.get(key)
+            .filter(|entry| entry.1.elapsed() < FETCHED_GUILD_TTL)
+            .map(|entry| entry.0.clone())
+    }
+
+    fn insert<K: Eq + Hash, V>(map: &DashMap<K, (V, Instant)>, key: K, value: V) {
+        map.retain(|_, (_, fetched)| fetched.elapsed() < FETCHED_GUILD_TTL);
+        map.insert(key, (value, Instant::now()));
+    }
+}
+
+impl TypeMapKey for FetchedGuilds {
+    type Value = Arc<Self>;
+}
+
+async fn fetched_guilds(ctx: &Context) -> Arc<FetchedGuilds> {
+    ctx.data
+        .read()
+        .await
+        .get::<FetchedGuilds>()
+        .cloned()
+        .expect("Could not get FetchedGuilds from data")
+}
+
+/// The guild a command was sent in, fetched over HTTP if it isn't cached
+pub async fn guild_context(ctx: &Context, guild_id: GuildId) -> SerenityResult<Arc<GuildContext>> {
+    if let Some(guild) = guild_id.to_guild_cached(&ctx.cache) {
+        return Ok(Arc::new(GuildContext::from_guild(&guild)));
+    }
+
+    let fetched = fetched_guilds(ctx).await;
+
+    if let Some(guild) = FetchedGuilds::get(&fetched.guilds, &guild_id) {
+        return Ok(guild);
+    }
+
+    let guild = Arc::new(GuildContext::from_partial_guild(
+        ctx.http.get_guild(guild_id.0).await?,
+    ));
+
+    FetchedGuilds::insert(&fetched.guilds, guild_id, guild.clone());
+
+    Ok(guild)
+}
+
+/// A member of a guild, fetched over HTTP if it isn't cached
+pub async fn guild_member(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> SerenityResult<Member> {
+    if let Some(member) = ctx.cache.member(guild_id, user_id) {
+        return Ok(member);
+    }
+
+    let fetched = fetched_guilds(ctx).await;
+
+    if let Some(member) = FetchedGuilds::get(&fetched.members, &(guild_id, user_id)) {
+        return Ok(member);
+    }
+
+    let member = ctx.http.get_member(guild_id.0, user_id.0).await?;
+
+    FetchedGuilds::insert(&fetched.members, (guild_id, user_id), member.clone());
+
+    Ok(member)
+}
+
+/// A channel of a guild, fetched over HTTP if the guild isn't cached
+pub async fn guild_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> SerenityResult<GuildChannel> {
+    let channel = if guild_id.to_guild_cached(&ctx.cache).is_some() {
+        channel_id.to_channel(ctx).await?
+    } else {
+        let fetched = fetched_guilds(ctx).await;
+
+        if let Some(channel) = FetchedGuilds::get(&fetched.channels, &channel_id) {
+            return Ok(channel);
+        }
+
+        match ctx.http.get_channel(channel_id.0).await? {
+            Channel::Guild(channel) => {
+                FetchedGuilds::insert(&fetched.channels, channel_id, channel.clone());
+
+                Channel::Guild(channel)
+            }
+
+            channel => channel,
+        }
+    };
+
+    match channel {
+        Channel::Guild(channel) => Ok(channel),
+
+        _ => Err(SerenityError::Other("not a guild channel")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GUILD: u64 = 1;
+    const OWNER: u64 = 2;
+    const USER: u64 = 3;
+    const ROLE: u64 = 4;
+    const OTHER_ROLE: u64 = 5;
+
+    fn guild(everyone: Permissions, role: Permissions) -> GuildContext {
+        GuildContext {
+            id: GuildId(GUILD),
+            name: String::new(),
+            owner_id: UserId(OWNER),
+            roles: vec![
+                (RoleId(GUILD), everyone),
+                (RoleId(ROLE), role),
+                (RoleId(OTHER_ROLE), Permissions::MANAGE_GUILD),
+            ]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn overwrite(
+        kind: PermissionOverwriteType,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> PermissionOverwrite {
+        PermissionOverwrite { allow, deny, kind }
+    }
+
+    #[test]
+    fn permissions_from_roles() {
+        let guild = guild(Permissions::SEND_MESSAGES, Permissions::MANAGE_MESSAGES);
+
+        assert_eq!(
+            guild.permissions(UserId(USER), &[], &[]),
+            Permissions::SEND_MESSAGES
+        );
+        assert_eq!(
+            guild.permissions(UserId(USER), &[RoleId(ROLE)], &[]),
+            Permissions::SEND_MESSAGES | Permissions::MANAGE_MESSAGES
+        );
+    }
+
+    #[test]
+    fn permissions_owner_and_administrator() {
+        let guild = guild(Permissions::empty(), Permissions::ADMINISTRATOR);
+
+        assert_eq!(
+            guild.permissions(UserId(OWNER), &[], &[]),
+            Permissions::all()
+        );
+        assert_eq!(
+            guild.permissions(UserId(USER), &[RoleId(ROLE)], &[]),
+            Permissions::all()
+        );
+    }
+
+    #[test]
+    fn permissions_overwrite_order() {
+        let guild = guild(
+            Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS,
+            Permissions::empty(),
+        );
+
+        let overwrites = [
+            // the member's own overwrite wins over their roles'
+            overwrite(
+                PermissionOverwriteType::Member(UserId(USER)),
+                Permissions::ATTACH_FILES,
+                Permissions::empty(),
+            ),
+            // roles win over @everyone
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(ROLE)),
+                Permissions::SEND_MESSAGES,
+                Permissions::ATTACH_FILES,
+            ),
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(GUILD)),
+                Permissions::empty(),
+                Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS,
+            ),
+            // a role the member doesn't have
+            overwrite(
+                PermissionOverwriteType::Role(RoleId(OTHER_ROLE)),
+                Permissions::EMBED_LINKS,
+                Permissions::empty(),
+            ),
+        ];
+
+        assert_eq!(
+            guild.permissions(UserId(USER), &[RoleId(ROLE)], &overwrites),
+            Permissions::SEND_MESSAGES | Permissions::ATTACH_FILES
+        );
+        assert_eq!(
+            guild.permissions(UserId(USER), &[], &overwrites),
+            Permissions::ATTACH_FILES
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+
+use serenity::{client::bridge::gateway::ShardManager, gateway::ConnectionStage, prelude::Mutex};
+
+use sqlx::MySqlPool;
+
+use log::{error, info};
+
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+
+use crate::metrics::Metrics;
+
+#[derive(Clone)]
+struct HealthState {
+    pool: MySqlPool,
+    shard_manager: Arc<Mutex<ShardManager>>,
+    metrics: Arc<Metrics>,
+}
+
+impl HealthState {
+    async fn database_ready(&self) -> bool {
+        sqlx::query("SELECT 1").execute(&self.pool).await.is_ok()
+    }
+
+    async fn shard_stages(&self) -> Vec<(u64, ConnectionStage)> {
+        let runners = self.shard_manager.lock().await.runners.clone();
+
+        let mut stages = runners
+            .lock()
+            .await
+            .iter()
+            .map(|(id, runner)| (id.0, runner.stage))
+            .collect::<Vec<(u64, ConnectionStage)>>();
+
+        stages.sort_unstable_by_key(|(id, _)| *id);
+
+        stages
+    }
+
+    async fn reminders_pending(&self) -> Option<i64> {
+        sqlx::query!(
+            "
+SELECT COUNT(1) AS count FROM reminders WHERE enabled = 1
+            "
+        )
+        .fetch_one(&self.pool)
+        .await
+        .ok()
+        .map(|row| row.count)
+    }
+}
+
+async fn route(req: Request<Body>, state: HealthState) -> Result<Response<Body>, Infallible> {
+    let (status, body) = match req.uri().path() {
+        // liveness: if we can answer, the process is up
+        "/health" => (StatusCode::OK, "OK".to_string()),
+
+        // readiness: database reachable and every shard connected
+        "/ready" => {
+            let database_ready = state.database_ready().await;
+            let shard_stages = state.shard_stages().await;
+
+            let shards_ready = !shard_stages.is_empty()
+                && shard_stages
+                    .iter()
+                    .all(|(_, stage)| *stage == ConnectionStage::Connected);
+
+            let mut lines = vec![format!(
+                "database: {}",
+                if database_ready { "ok" } else { "unreachable" }
+            )];
+
+            lines.extend(
+                shard_stages
+                    .iter()
+                    .map(|(id, stage)| format!("shard {}: {:?}", id, stage)),
+            );
+
+            (
+                if database_ready && shards_ready {
+                    StatusCode::OK
+                } else {
+                    StatusCode::SERVICE_UNAVAILABLE
+                },
+                lines.join("\n"),
+            )
+        }
+
+        "/metrics" => (
+            StatusCode::OK,
+            state.metrics.render(state.reminders_pending().await),
+        ),
+
+        _ => (StatusCode::NOT_FOUND, "Not Found".to_string()),
+    };
+
+    Ok(Response::builder()
+        .status(status)
+        .header("Content-Type", "text/plain; charset=utf-8")
+        .body(Body::from(body))
+        .unwrap())
+}
+
+pub async fn serve(
+    port: u16,
+    pool: MySqlPool,
+    shard_manager: Arc<Mutex<ShardManager>>,
+    metrics: Arc<Metrics>,
+) {
+    let state = HealthState {
+        pool,
+        shard_manager,
+        metrics,
+    };
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+    let make_service = make_service_fn(move |_| {
+        let state = state.clone();
+
+        async move { Ok::<_, Infallible>(service_fn(move |req| route(req, state.clone()))) }
+    });
+
+    let server = match Server::try_bind(&addr) {
+        Ok(builder) => builder,
+
+        Err(e) => {
+            error!("Couldn't bind health server to {}: {:?}", addr, e);
+
+            return;
+        }
+    };
+
+    info!("Starting health server on {}", addr);
+
+    if let Err(e) = server.serve(make_service).await {
+        error!("Health server stopped: {:?}", e);
+    }
+}
@@ -2,9 +2,14 @@ use serde::Deserialize;
 use serde_json::from_str;
 use serenity::prelude::TypeMapKey;
 
+use log::error;
+
 use std::{collections::HashMap, error::Error, sync::Arc};
 
-use crate::consts::LOCAL_LANGUAGE;
+use crate::{consts::LOCAL_LANGUAGE, fallback_strings::FALLBACK_STRINGS};
+
+/// Shown in place of a string that exists nowhere, rather than failing the command
+const MISSING_STRING: &str = "(missing text)";
 
 #[derive(Deserialize)]
 pub struct LanguageManager {
@@ -19,19 +24,52 @@ impl LanguageManager {
         Ok(new)
     }
 
+    /// Only the built-in English strings, for when the compiled strings can't be loaded
+    pub fn fallback() -> Self {
+        let strings = FALLBACK_STRINGS
+            .iter()
+            .map(|(name, string)| (name.to_string(), string.to_string()))
+            .collect();
+
+        Self {
+            languages: vec![(LOCAL_LANGUAGE.clone(), "English".to_string())]
+                .into_iter()
+                .collect(),
+            strings: vec![(LOCAL_LANGUAGE.clone(), strings)]
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// The string in the language, falling back to `LOCAL_LANGUAGE` and then the built-in
+    /// English. A string that exists nowhere is logged and shown as a placeholder
     pub fn get(&self, language: &str, name: &str) -> &str {
+        self.try_get(language, name).unwrap_or_else(|| {
+            error!(r#"String does not exist: "{}""#, name);
+
+            MISSING_STRING
+        })
+    }
+
+    /// Like `get`, but returns `None` if the string doesn't exist in the language,
+    /// `LOCAL_LANGUAGE` or the built-in English
+    pub fn try_get(&self, language: &str, name: &str) -> Option<&str> {
         self.strings
             .get(language)
             .map(|sm| sm.get(name))
-            .unwrap_or_else(|| panic!(r#"Language does not exist: "{}""#, language))
-            .unwrap_or_else(|| {
+            .flatten()
+            .or_else(|| {
                 self.strings
                     .get(&*LOCAL_LANGUAGE)
-                    .map(|sm| {
-                        sm.get(name)
-                            .unwrap_or_else(|| panic!(r#"String does not exist: "{}""#, name))
-                    })
-                    .expect("LOCAL_LANGUAGE is not available")
+                    .map(|sm| sm.get(name))
+                    .flatten()
+            })
+            .map(|s| s.as_str())
+            .or_else(|| {
+                FALLBACK_STRINGS
+                    .iter()
+                    .find(|(fallback_name, _)| *fallback_name == name)
+                    .map(|(_, string)| *string)
             })
     }
 
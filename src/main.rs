@@ -1,15 +1,24 @@
 #[macro_use]
 extern crate lazy_static;
 
+mod backoff;
 mod commands;
 mod consts;
+mod deletion_log;
+mod fallback_strings;
 mod framework;
+mod guild_context;
+#[cfg(feature = "health")]
+mod health;
 mod language_manager;
+mod metrics;
 mod models;
+mod slow_query;
 mod time_parser;
 
 use serenity::{
     async_trait,
+    builder::ParseValue,
     cache::Cache,
     client::{bridge::gateway::GatewayIntents, Client},
     futures::TryFutureExt,
@@ -17,9 +26,11 @@ use serenity::{
     model::{
         channel::GuildChannel,
         channel::Message,
+        gateway::Presence,
         guild::{Guild, GuildUnavailable},
-        id::{GuildId, UserId},
+        id::{ChannelId, GuildId, UserId},
         interactions::Interaction,
+        user::OnlineStatus,
     },
     prelude::{Context, EventHandler, TypeMapKey},
     utils::shard_id,
@@ -29,21 +40,42 @@ use sqlx::mysql::MySqlPool;
 
 use dotenv::dotenv;
 
-use std::{collections::HashMap, env, sync::Arc, time::Instant};
+use std::{
+    collections::{HashMap, HashSet},
+    env, process,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    commands::{info_cmds, moderation_cmds, reminder_cmds, todo_cmds},
-    consts::{CNC_GUILD, DEFAULT_PREFIX, SUBSCRIPTION_ROLES, THEME_COLOR},
+    backoff::with_backoff,
+    commands::{info_cmds, moderation_cmds, owner_cmds, reminder_cmds, todo_cmds},
+    consts::{
+        CNC_GUILD, DEFAULT_PREFIXES, KEYWORDS_ENABLED, PRESENCE_ENABLED, SCHEMA_VERSION,
+        SKIP_SCHEMA_CHECK, SUBSCRIPTION_ROLES, THEME_COLOR,
+    },
+    deletion_log::DeletionLog,
     framework::RegexFramework,
+    guild_context::FetchedGuilds,
     language_manager::LanguageManager,
-    models::{guild_data::GuildData, user_data::UserData},
+    metrics::Metrics,
+    models::{
+        guild_data::{GuildData, NaturalKeywords},
+        keyword_trigger::KeywordTrigger,
+        presence_reminder::PresenceReminder,
+        reminder::Reminder,
+        user_data::UserData,
+        CtxGuildData,
+    },
 };
 
 use inflector::Inflector;
-use log::info;
+use log::{error, info, warn};
 
 use dashmap::DashMap;
 
+use regex::Regex;
+
 use tokio::sync::RwLock;
 
 use chrono::Utc;
@@ -58,6 +90,14 @@ impl TypeMapKey for GuildDataCache {
     type Value = Arc<DashMap<GuildId, Arc<RwLock<GuildData>>>>;
 }
 
+// compiled `natural` regexes for guilds with their own keywords, with the keywords they were
+// compiled from so that changed keywords are noticed
+struct NaturalRegexCache;
+
+impl TypeMapKey for NaturalRegexCache {
+    type Value = Arc<DashMap<GuildId, (NaturalKeywords, Arc<(Regex, Regex)>)>>;
+}
+
 struct SQLPool;
 
 impl TypeMapKey for SQLPool {
@@ -88,11 +128,26 @@ impl TypeMapKey for CurrentlyExecuting {
     type Value = Arc<RwLock<HashMap<UserId, Instant>>>;
 }
 
+// users already told their last command is still running, so each is told once per command
+struct ExecutingNoticeSent;
+
+impl TypeMapKey for ExecutingNoticeSent {
+    type Value = Arc<RwLock<HashSet<UserId>>>;
+}
+
+// each user's last `natural` input, for `natural again`
+struct LastNatural;
+
+impl TypeMapKey for LastNatural {
+    type Value = Arc<RwLock<HashMap<UserId, (String, Instant)>>>;
+}
+
 #[async_trait]
 trait LimitExecutors {
     async fn check_executing(&self, user: UserId) -> bool;
     async fn set_executing(&self, user: UserId);
     async fn drop_executing(&self, user: UserId);
+    async fn claim_executing_notice(&self, user: UserId) -> bool;
 }
 
 #[async_trait]
@@ -124,6 +179,8 @@ impl LimitExecutors for Context {
         let mut lock = currently_executing.write().await;
 
         lock.insert(user, Instant::now());
+
+        clear_executing_notice(self, user).await;
     }
 
     async fn drop_executing(&self, user: UserId) {
@@ -138,9 +195,42 @@ impl LimitExecutors for Context {
         let mut lock = currently_executing.write().await;
 
         lock.remove(&user);
+
+        clear_executing_notice(self, user).await;
+    }
+
+    /// Whether the user should be told their command is still running. True only the first time
+    /// for each command
+    async fn claim_executing_notice(&self, user: UserId) -> bool {
+        let notice_sent = self
+            .data
+            .read()
+            .await
+            .get::<ExecutingNoticeSent>()
+            .cloned()
+            .unwrap();
+
+        let mut lock = notice_sent.write().await;
+
+        lock.insert(user)
     }
 }
 
+// a new command, or the end of one, starts a new window for the notice
+async fn clear_executing_notice(ctx: &Context, user: UserId) {
+    let notice_sent = ctx
+        .data
+        .read()
+        .await
+        .get::<ExecutingNoticeSent>()
+        .cloned()
+        .unwrap();
+
+    let mut lock = notice_sent.write().await;
+
+    lock.remove(&user);
+}
+
 struct Handler;
 
 #[async_trait]
@@ -166,6 +256,11 @@ DELETE FROM channels WHERE channel = ?
     }
 
     async fn guild_create(&self, ctx: Context, guild: Guild, is_new: bool) {
+        // anything fetched while the guild wasn't cached is now out of date
+        if let Some(fetched_guilds) = ctx.data.read().await.get::<FetchedGuilds>() {
+            fetched_guilds.remove(guild.id);
+        }
+
         if is_new {
             let guild_id = guild.id.as_u64().to_owned();
 
@@ -252,6 +347,19 @@ DELETE FROM channels WHERE channel = ?
             .unwrap();
         guild_data_cache.remove(&deleted_guild.id);
 
+        let natural_regex_cache = ctx
+            .data
+            .read()
+            .await
+            .get::<NaturalRegexCache>()
+            .cloned()
+            .unwrap();
+        natural_regex_cache.remove(&deleted_guild.id);
+
+        if let Some(fetched_guilds) = ctx.data.read().await.get::<FetchedGuilds>() {
+            fetched_guilds.remove(deleted_guild.id);
+        }
+
         sqlx::query!(
             "
 DELETE FROM guilds WHERE guild = ?
@@ -263,12 +371,128 @@ DELETE FROM guilds WHERE guild = ?
         .unwrap();
     }
 
+    async fn message(&self, ctx: Context, msg: Message) {
+        if !*KEYWORDS_ENABLED || msg.author.bot {
+            return;
+        }
+
+        let guild_id = match msg.guild_id {
+            Some(guild_id) => guild_id,
+
+            None => return,
+        };
+
+        // guild_data needs the guild itself, which may not be cached yet (e.g. during startup)
+        if guild_id.to_guild_cached(&ctx).is_none() {
+            return;
+        }
+
+        // the guild data is cached, so most messages are turned away without a query
+        match ctx.guild_data(guild_id).await {
+            Ok(guild_data) if guild_data.read().await.keyword_triggers => {}
+
+            _ => return,
+        }
+
+        let pool = ctx
+            .data
+            .read()
+            .await
+            .get::<SQLPool>()
+            .cloned()
+            .expect("Could not get SQLPool from data");
+
+        let triggers = KeywordTrigger::from_channel(*msg.channel_id.as_u64(), &pool).await;
+
+        if triggers.is_empty() {
+            return;
+        }
+
+        let content = msg.content.to_lowercase();
+        let words = content
+            .split(|c: char| !KeywordTrigger::is_word_char(c))
+            .collect::<Vec<&str>>();
+
+        for trigger in triggers {
+            if words.contains(&trigger.keyword.as_str()) && trigger.claim(&pool).await {
+                // anyone who can trigger a keyword can send it, so it may only ping users
+                let _ = with_backoff("Sending keyword trigger", || {
+                    msg.channel_id.send_message(&ctx, |m| {
+                        m.content(&trigger.content)
+                            .allowed_mentions(|am| am.parse(ParseValue::Users))
+                    })
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn presence_update(&self, ctx: Context, presence: Presence) {
+        if presence.status != OnlineStatus::Online {
+            return;
+        }
+
+        let pool = ctx
+            .data
+            .read()
+            .await
+            .get::<SQLPool>()
+            .cloned()
+            .expect("Could not get SQLPool from data");
+
+        for reminder in PresenceReminder::from_target(*presence.user.id.as_u64(), &pool).await {
+            if reminder.claim(&pool).await {
+                let channel_id = ChannelId(reminder.channel);
+
+                let _ = with_backoff("Sending presence reminder", || {
+                    channel_id.send_message(&ctx, |m| {
+                        m.content(&reminder.content)
+                            .allowed_mentions(|am| am.parse(ParseValue::Users))
+                    })
+                })
+                .await;
+            }
+        }
+    }
+
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         let (pool, lm) = get_ctx_data(&&ctx).await;
 
         match interaction {
             Interaction::MessageComponent(interaction) => {
-                if let Some(member) = interaction.clone().member {
+                if let Some(uid) = interaction.data.custom_id.strip_prefix("acknowledge:") {
+                    let language = UserData::language_of(&interaction.user, &pool).await;
+
+                    // looked up first, since acknowledging deletes it
+                    let reminder = Reminder::from_uid(&ctx, uid.to_string()).await;
+
+                    let response = if Reminder::acknowledge(&ctx, uid).await {
+                        if let (Some(reminder), Some(deletion_log)) =
+                            (reminder, ctx.data.read().await.get::<DeletionLog>())
+                        {
+                            deletion_log.record(
+                                &reminder.uid,
+                                reminder.display_content(),
+                                interaction.user.id,
+                            );
+                        }
+
+                        "acknowledge/done"
+                    } else {
+                        "acknowledge/already_done"
+                    };
+
+                    let _ = interaction
+                        .create_interaction_response(&ctx, |r| {
+                            r.kind(InteractionResponseType::ChannelMessageWithSource)
+                                .interaction_response_data(|d| {
+                                    d.content(lm.get(&language, response)).flags(
+                                        InteractionApplicationCommandCallbackDataFlags::EPHEMERAL,
+                                    )
+                                })
+                        })
+                        .await;
+                } else if let Some(member) = interaction.clone().member {
                     let data = interaction.data.clone();
 
                     if data.custom_id.starts_with("timezone:") {
@@ -366,10 +590,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let dm_enabled = env::var("DM_ENABLED").map_or(true, |var| var == "1");
 
     let framework = RegexFramework::new(logged_in_id)
-        .default_prefix(DEFAULT_PREFIX.clone())
+        .default_prefixes(DEFAULT_PREFIXES.iter())
         .case_insensitive(env::var("CASE_INSENSITIVE").map_or(true, |var| var == "1"))
         .ignore_bots(env::var("IGNORE_BOTS").map_or(true, |var| var == "1"))
         .dm_enabled(dm_enabled)
+        .executing_notice(env::var("EXECUTING_NOTICE").map_or(true, |var| var == "1"))
         // info commands
         .add_command("ping", &info_cmds::PING_COMMAND)
         .add_command("help", &info_cmds::HELP_COMMAND)
@@ -378,19 +603,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .add_command("donate", &info_cmds::DONATE_COMMAND)
         .add_command("dashboard", &info_cmds::DASHBOARD_COMMAND)
         .add_command("clock", &info_cmds::CLOCK_COMMAND)
+        .add_command("convert", &info_cmds::CONVERT_COMMAND)
+        .add_command("broadcast", &owner_cmds::BROADCAST_COMMAND)
+        .add_command("deleted", &owner_cmds::DELETED_COMMAND)
+        .add_command("migrate", &owner_cmds::MIGRATE_COMMAND)
+        .add_command("orphans", &owner_cmds::ORPHANS_COMMAND)
         // reminder commands
         .add_command("timer", &reminder_cmds::TIMER_COMMAND)
         .add_command("remind", &reminder_cmds::REMIND_COMMAND)
         .add_command("r", &reminder_cmds::REMIND_COMMAND)
         .add_command("interval", &reminder_cmds::INTERVAL_COMMAND)
         .add_command("i", &reminder_cmds::INTERVAL_COMMAND)
+        .add_command("nag", &reminder_cmds::NAG_COMMAND)
         .add_command("natural", &reminder_cmds::NATURAL_COMMAND)
         .add_command("n", &reminder_cmds::NATURAL_COMMAND)
         .add_command("", &reminder_cmds::NATURAL_COMMAND)
         .add_command("countdown", &reminder_cmds::COUNTDOWN_COMMAND)
         // management commands
         .add_command("look", &reminder_cmds::LOOK_COMMAND)
+        .add_command("next", &reminder_cmds::NEXT_COMMAND)
+        .add_command("agenda", &reminder_cmds::AGENDA_COMMAND)
+        .add_command("after", &reminder_cmds::AFTER_COMMAND)
+        .add_command("inspect", &reminder_cmds::INSPECT_COMMAND)
+        .add_command("preview", &reminder_cmds::PREVIEW_COMMAND)
+        .add_command("count", &reminder_cmds::COUNT_COMMAND)
+        .add_command("stats", &reminder_cmds::STATS_COMMAND)
+        .add_command("list-channels", &reminder_cmds::LIST_CHANNELS_COMMAND)
+        .add_command("online", &reminder_cmds::ONLINE_COMMAND)
+        .add_command("keyword", &reminder_cmds::KEYWORD_COMMAND)
         .add_command("del", &reminder_cmds::DELETE_COMMAND)
+        .add_command("del-uid", &reminder_cmds::DELETE_UID_COMMAND)
+        .add_command("mute", &reminder_cmds::MUTE_COMMAND)
+        .add_command("bump", &reminder_cmds::BUMP_COMMAND)
+        .add_command("template", &reminder_cmds::TEMPLATE_COMMAND)
         // to-do commands
         .add_command("todo", &todo_cmds::TODO_USER_COMMAND)
         .add_command("todo user", &todo_cmds::TODO_USER_COMMAND)
@@ -402,11 +647,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         // moderation commands
         .add_command("blacklist", &moderation_cmds::BLACKLIST_COMMAND)
         .add_command("restrict", &moderation_cmds::RESTRICT_COMMAND)
+        .add_command("audit", &moderation_cmds::AUDIT_COMMAND)
+        .add_command("disable", &moderation_cmds::DISABLE_COMMAND)
+        .add_command("settings", &moderation_cmds::SETTINGS_COMMAND)
         .add_command("timezone", &moderation_cmds::TIMEZONE_COMMAND)
+        .add_command("channeltz", &moderation_cmds::CHANNEL_TIMEZONE_COMMAND)
         .add_command("prefix", &moderation_cmds::PREFIX_COMMAND)
+        .add_command("everyone", &moderation_cmds::EVERYONE_COMMAND)
+        .add_command("defaultchannel", &moderation_cmds::DEFAULT_CHANNEL_COMMAND)
+        .add_command("announcements", &moderation_cmds::ANNOUNCEMENTS_COMMAND)
+        .add_command("logchannel", &moderation_cmds::LOG_CHANNEL_COMMAND)
+        .add_command("naturalwords", &moderation_cmds::NATURAL_WORDS_COMMAND)
+        .add_command("delivery", &moderation_cmds::DELIVERY_COMMAND)
+        .add_command("digest", &moderation_cmds::DIGEST_COMMAND)
+        .add_command("webhook", &reminder_cmds::WEBHOOK_COMMAND)
         .add_command("lang", &moderation_cmds::LANGUAGE_COMMAND)
+        .add_command("dms", &moderation_cmds::DMS_COMMAND)
+        .add_command("quiet", &moderation_cmds::QUIET_COMMAND)
+        .add_command("mychannel", &moderation_cmds::MY_CHANNEL_COMMAND)
+        .add_command("serverdms", &moderation_cmds::DMS_GUILD_COMMAND)
         .add_command("pause", &reminder_cmds::PAUSE_COMMAND)
+        .add_command("pause-mine", &reminder_cmds::PAUSE_MINE_COMMAND)
+        .add_command("resume-mine", &reminder_cmds::RESUME_MINE_COMMAND)
         .add_command("offset", &reminder_cmds::OFFSET_COMMAND)
+        .add_command("snooze-all", &reminder_cmds::SNOOZE_ALL_COMMAND)
         .add_command("nudge", &reminder_cmds::NUDGE_COMMAND)
         .add_command("alias", &moderation_cmds::ALIAS_COMMAND)
         .add_command("a", &moderation_cmds::ALIAS_COMMAND)
@@ -414,18 +678,28 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     let framework_arc = Arc::new(framework);
 
+    let mut intents = if dm_enabled {
+        GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MESSAGE_REACTIONS
+            | GatewayIntents::DIRECT_MESSAGES
+            | GatewayIntents::DIRECT_MESSAGE_REACTIONS
+    } else {
+        GatewayIntents::GUILD_MESSAGES
+            | GatewayIntents::GUILDS
+            | GatewayIntents::GUILD_MESSAGE_REACTIONS
+    };
+
+    if *PRESENCE_ENABLED {
+        intents |= GatewayIntents::GUILD_PRESENCES;
+    }
+
+    if *KEYWORDS_ENABLED {
+        intents |= GatewayIntents::MESSAGE_CONTENT;
+    }
+
     let mut client = Client::builder(&token)
-        .intents(if dm_enabled {
-            GatewayIntents::GUILD_MESSAGES
-                | GatewayIntents::GUILDS
-                | GatewayIntents::GUILD_MESSAGE_REACTIONS
-                | GatewayIntents::DIRECT_MESSAGES
-                | GatewayIntents::DIRECT_MESSAGE_REACTIONS
-        } else {
-            GatewayIntents::GUILD_MESSAGES
-                | GatewayIntents::GUILDS
-                | GatewayIntents::GUILD_MESSAGE_REACTIONS
-        })
+        .intents(intents)
         .application_id(application_id.0)
         .event_handler(Handler)
         .framework_arc(framework_arc.clone())
@@ -441,12 +715,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .await
         .unwrap();
 
+        if !*SKIP_SCHEMA_CHECK {
+            check_schema_version(&pool).await;
+        }
+
+        tokio::spawn(sweep_restrictions(pool.clone()));
+        tokio::spawn(sweep_paused_reminders(pool.clone()));
+        tokio::spawn(sweep_muted_reminders(pool.clone()));
+
         let language_manager = LanguageManager::from_compiled(include_str!(concat!(
             env!("CARGO_MANIFEST_DIR"),
             "/assets/",
             env!("STRINGS_FILE")
         )))
-        .unwrap();
+        .unwrap_or_else(|e| {
+            error!("Failed to load strings, using built-in English: {:?}", e);
+
+            LanguageManager::fallback()
+        });
 
         let popular_timezones = sqlx::query!(
             "SELECT timezone FROM users GROUP BY timezone ORDER BY COUNT(timezone) DESC LIMIT 21"
@@ -461,12 +747,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut data = client.data.write().await;
 
         data.insert::<GuildDataCache>(Arc::new(guild_data_cache));
+        data.insert::<NaturalRegexCache>(Arc::new(DashMap::new()));
         data.insert::<CurrentlyExecuting>(Arc::new(RwLock::new(HashMap::new())));
+        data.insert::<ExecutingNoticeSent>(Arc::new(RwLock::new(HashSet::new())));
+        data.insert::<LastNatural>(Arc::new(RwLock::new(HashMap::new())));
         data.insert::<SQLPool>(pool);
         data.insert::<PopularTimezones>(Arc::new(popular_timezones));
         data.insert::<ReqwestClient>(Arc::new(reqwest::Client::new()));
         data.insert::<FrameworkCtx>(framework_arc.clone());
-        data.insert::<LanguageManager>(Arc::new(language_manager))
+        data.insert::<LanguageManager>(Arc::new(language_manager));
+        data.insert::<Metrics>(Arc::new(Metrics::new()));
+        data.insert::<DeletionLog>(Arc::new(DeletionLog::new()));
+        data.insert::<FetchedGuilds>(Arc::new(FetchedGuilds::new()))
+    }
+
+    #[cfg(feature = "health")]
+    {
+        let (pool, metrics) = {
+            let data = client.data.read().await;
+
+            (
+                data.get::<SQLPool>().cloned().unwrap(),
+                data.get::<Metrics>().cloned().unwrap(),
+            )
+        };
+
+        tokio::spawn(health::serve(
+            *consts::HEALTH_PORT,
+            pool,
+            client.shard_manager.clone(),
+            metrics,
+        ));
     }
 
     if let Ok((Some(lower), Some(upper))) = env::var("SHARD_RANGE").map(|sr| {
@@ -565,6 +876,126 @@ pub async fn get_ctx_data(ctx: &&Context) -> (MySqlPool, Arc<LanguageManager>) {
     (pool, lm)
 }
 
+/// Exit if the database schema is older than this build expects. Queries against missing columns
+/// would otherwise fail confusingly at runtime
+async fn check_schema_version(pool: &MySqlPool) {
+    match sqlx::query!("SELECT version FROM schema_version")
+        .fetch_one(pool)
+        .await
+    {
+        Ok(row) if row.version < SCHEMA_VERSION => {
+            error!(
+                "Database schema is at version {}, but this build needs version {}. Apply the newer files in migration/ and restart",
+                row.version, SCHEMA_VERSION
+            );
+
+            process::exit(1);
+        }
+
+        Ok(row) if row.version > SCHEMA_VERSION => {
+            warn!(
+                "Database schema is at version {}, newer than this build's version {}",
+                row.version, SCHEMA_VERSION
+            );
+        }
+
+        Ok(_) => {}
+
+        Err(e) => {
+            error!(
+                "Could not read the schema version ({:?}). Apply the files in migration/ and restart",
+                e
+            );
+
+            process::exit(1);
+        }
+    }
+}
+
+/// Remove temporary `restrict` grants once they've expired. The permission check already ignores
+/// them, so this only keeps the table tidy
+async fn sweep_restrictions(pool: MySqlPool) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60 * 60));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = sqlx::query!(
+            "
+DELETE FROM command_restrictions WHERE expires < NOW()
+            "
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!("Failed to remove expired restrictions: {:?}", e);
+        }
+    }
+}
+
+/// Turn back on reminders paused by `pause-mine` once their pause has ended
+async fn sweep_paused_reminders(pool: MySqlPool) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        // both statements use the same cutoff, so a pause ending between them isn't dropped
+        // without its reminders being turned back on
+        let cutoff = Utc::now().timestamp();
+
+        if let Err(e) = sqlx::query!(
+            "
+UPDATE reminders
+INNER JOIN paused_reminders ON paused_reminders.reminder_id = reminders.id
+SET reminders.enabled = 1
+WHERE paused_reminders.resume_at <= FROM_UNIXTIME(?)
+            ",
+            cutoff
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!("Failed to resume paused reminders: {:?}", e);
+
+            continue;
+        }
+
+        if let Err(e) = sqlx::query!(
+            "
+DELETE FROM paused_reminders WHERE resume_at <= FROM_UNIXTIME(?)
+            ",
+            cutoff
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!("Failed to remove ended pauses: {:?}", e);
+        }
+    }
+}
+
+/// Clear `mute` windows that have passed. Delivery already ignores them, so this only keeps the
+/// column from showing stale mutes
+async fn sweep_muted_reminders(pool: MySqlPool) {
+    let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = sqlx::query!(
+            "
+UPDATE reminders SET muted_until = NULL WHERE muted_until <= UTC_TIMESTAMP()
+            "
+        )
+        .execute(&pool)
+        .await
+        {
+            warn!("Failed to clear ended mutes: {:?}", e);
+        }
+    }
+}
+
 async fn command_help(
     ctx: &Context,
     msg: &Message,
@@ -573,15 +1004,22 @@ async fn command_help(
     language: &str,
     command_name: &str,
 ) {
+    let mut description = lm
+        .get(&language, &format!("help/{}", command_name))
+        .replace("{prefix}", &prefix);
+
+    // options are kept apart from the usage, so commands sharing them can share the string
+    if let Some(options) = lm.try_get(&language, &format!("help/{}_options", command_name)) {
+        description.push_str("\n\n");
+        description.push_str(options);
+    }
+
     let _ = msg
         .channel_id
         .send_message(ctx, |m| {
             m.embed(move |e| {
                 e.title(format!("{} Help", command_name.to_title_case()))
-                    .description(
-                        lm.get(&language, &format!("help/{}", command_name))
-                            .replace("{prefix}", &prefix),
-                    )
+                    .description(description)
                     .footer(|f| {
                         f.text(concat!(
                             env!("CARGO_PKG_NAME"),
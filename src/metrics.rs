@@ -0,0 +1,74 @@
+use dashmap::DashMap;
+
+use serenity::prelude::TypeMapKey;
+
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct Metrics {
+    commands_executed: DashMap<(&'static str, u64), u64>,
+    reminders_created: DashMap<u64, u64>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn command_executed(&self, command: &'static str, shard: u64) {
+        *self.commands_executed.entry((command, shard)).or_insert(0) += 1;
+    }
+
+    pub fn reminder_created(&self, shard: u64) {
+        *self.reminders_created.entry(shard).or_insert(0) += 1;
+    }
+
+    /// Render all metrics in the Prometheus text exposition format. Gauges that require a database
+    /// query are computed by the caller and passed in.
+    #[cfg(feature = "health")]
+    pub fn render(&self, reminders_pending: Option<i64>) -> String {
+        let mut lines = vec![
+            "# HELP reminder_commands_executed_total Commands executed.".to_string(),
+            "# TYPE reminder_commands_executed_total counter".to_string(),
+        ];
+
+        lines.extend(self.commands_executed.iter().map(|entry| {
+            let (command, shard) = entry.key();
+
+            format!(
+                "reminder_commands_executed_total{{command=\"{}\",shard=\"{}\"}} {}",
+                command,
+                shard,
+                entry.value()
+            )
+        }));
+
+        lines.push("# HELP reminder_reminders_created_total Reminders created.".to_string());
+        lines.push("# TYPE reminder_reminders_created_total counter".to_string());
+
+        lines.extend(self.reminders_created.iter().map(|entry| {
+            format!(
+                "reminder_reminders_created_total{{shard=\"{}\"}} {}",
+                entry.key(),
+                entry.value()
+            )
+        }));
+
+        if let Some(pending) = reminders_pending {
+            lines.push(
+                "# HELP reminder_reminders_pending Enabled reminders awaiting delivery."
+                    .to_string(),
+            );
+            lines.push("# TYPE reminder_reminders_pending gauge".to_string());
+            lines.push(format!("reminder_reminders_pending {}", pending));
+        }
+
+        lines.push(String::new());
+
+        lines.join("\n")
+    }
+}
+
+impl TypeMapKey for Metrics {
+    type Value = Arc<Self>;
+}
@@ -3,6 +3,9 @@ use serenity::model::channel::Channel;
 use sqlx::MySqlPool;
 
 use chrono::NaiveDateTime;
+use chrono_tz::Tz;
+
+use crate::slow_query::TimedQuery;
 
 pub struct ChannelData {
     pub id: u32,
@@ -13,9 +16,18 @@ pub struct ChannelData {
     pub webhook_token: Option<String>,
     pub paused: bool,
     pub paused_until: Option<NaiveDateTime>,
+    pub display_timezone: Option<String>,
+    pub thread_digest: bool,
 }
 
 impl ChannelData {
+    pub fn display_timezone(&self) -> Option<Tz> {
+        self.display_timezone
+            .as_ref()
+            .map(|timezone| timezone.parse().ok())
+            .flatten()
+    }
+
     pub async fn from_channel(
         channel: Channel,
         pool: &MySqlPool,
@@ -24,9 +36,10 @@ impl ChannelData {
 
         if let Ok(c) = sqlx::query_as_unchecked!(Self,
             "
-SELECT id, name, nudge, blacklisted, webhook_id, webhook_token, paused, paused_until FROM channels WHERE channel = ?
+SELECT id, name, nudge, blacklisted, webhook_id, webhook_token, paused, paused_until, display_timezone, thread_digest FROM channels WHERE channel = ?
             ", channel_id)
             .fetch_one(pool)
+            .timed("ChannelData::from_channel")
             .await {
 
             Ok(c)
@@ -49,7 +62,7 @@ INSERT IGNORE INTO channels (channel, name, guild_id) VALUES (?, ?, (SELECT id F
 
             Ok(sqlx::query_as_unchecked!(Self,
                 "
-SELECT id, name, nudge, blacklisted, webhook_id, webhook_token, paused, paused_until FROM channels WHERE channel = ?
+SELECT id, name, nudge, blacklisted, webhook_id, webhook_token, paused, paused_until, display_timezone, thread_digest FROM channels WHERE channel = ?
                 ", channel_id)
                 .fetch_one(pool)
                 .await?)
@@ -59,8 +72,8 @@ SELECT id, name, nudge, blacklisted, webhook_id, webhook_token, paused, paused_u
     pub async fn commit_changes(&self, pool: &MySqlPool) {
         sqlx::query!(
             "
-UPDATE channels SET name = ?, nudge = ?, blacklisted = ?, webhook_id = ?, webhook_token = ?, paused = ?, paused_until = ? WHERE id = ?
-            ", self.name, self.nudge, self.blacklisted, self.webhook_id, self.webhook_token, self.paused, self.paused_until, self.id)
+UPDATE channels SET name = ?, nudge = ?, blacklisted = ?, webhook_id = ?, webhook_token = ?, paused = ?, paused_until = ?, display_timezone = ?, thread_digest = ? WHERE id = ?
+            ", self.name, self.nudge, self.blacklisted, self.webhook_id, self.webhook_token, self.paused, self.paused_until, self.display_timezone, self.thread_digest, self.id)
             .execute(pool)
             .await.unwrap();
     }
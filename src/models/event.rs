@@ -0,0 +1,57 @@
+use serenity::model::id::{GuildId, UserId};
+
+use sqlx::MySqlPool;
+
+use log::warn;
+
+/// Changes to many of a guild's reminders at once, or to its restrictions, recorded in the
+/// `events` table for auditing
+pub enum Event {
+    Delete,
+    Offset,
+    Snooze,
+    Enable,
+    Disable,
+    Restrict,
+}
+
+impl Event {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+            Self::Offset => "offset",
+            Self::Snooze => "snooze",
+            Self::Enable => "enable",
+            Self::Disable => "disable",
+            Self::Restrict => "restrict",
+        }
+    }
+
+    /// Record that `user_id` changed `count` of the guild's reminders or restrictions. The insert runs in the
+    /// background, so the command doesn't wait on it. Nothing is recorded when nothing changed
+    pub fn record(self, pool: &MySqlPool, count: u64, guild_id: GuildId, user_id: UserId) {
+        if count == 0 {
+            return;
+        }
+
+        let pool = pool.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = sqlx::query!(
+                "
+INSERT INTO events (event_name, bulk_count, guild_id, user_id)
+    VALUES (?, ?, (SELECT id FROM guilds WHERE guild = ?), (SELECT id FROM users WHERE user = ?))
+                ",
+                self.name(),
+                count,
+                guild_id.as_u64(),
+                user_id.as_u64()
+            )
+            .execute(&pool)
+            .await
+            {
+                warn!("Failed to record {} event: {:?}", self.name(), e);
+            }
+        });
+    }
+}
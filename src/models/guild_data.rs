@@ -1,33 +1,67 @@
-use serenity::model::guild::Guild;
+use serenity::model::{guild::Guild, id::GuildId};
 
 use sqlx::MySqlPool;
 
 use log::error;
 
-use crate::consts::DEFAULT_PREFIX;
+use regex::Regex;
+
+use crate::{
+    consts::{
+        natural_command_regex, natural_interval_regex, DEFAULT_NATURAL_EVERY, DEFAULT_NATURAL_SEND,
+        DEFAULT_NATURAL_TO, DEFAULT_PREFIX,
+    },
+    slow_query::TimedQuery,
+};
+
+/// A guild's `natural` keywords: the words for send, to and every. `None` uses the default
+pub type NaturalKeywords = (Option<String>, Option<String>, Option<String>);
 
 pub struct GuildData {
     pub id: u32,
     pub name: Option<String>,
     pub prefix: String,
+    pub confirm_everyone: bool,
+    pub restrict_everyone: bool,
+    pub default_channel: Option<u64>,
+    pub announcements: bool,
+    pub announcement_channel: Option<u64>,
+    pub dm_opt_out: bool,
+    pub direct_delivery: bool,
+    pub keyword_triggers: bool,
+    pub log_channel: Option<u64>,
+    pub natural_send: Option<String>,
+    pub natural_to: Option<String>,
+    pub natural_every: Option<String>,
 }
 
 impl GuildData {
     pub async fn from_guild(guild: Guild, pool: &MySqlPool) -> Result<Self, sqlx::Error> {
-        let guild_id = guild.id.as_u64().to_owned();
+        Self::from_id(guild.id, Some(guild.name), pool).await
+    }
+
+    /// The guild's settings, creating them if it's new. The name may be unknown when the guild
+    /// isn't cached
+    pub async fn from_id(
+        guild_id: GuildId,
+        name: Option<String>,
+        pool: &MySqlPool,
+    ) -> Result<Self, sqlx::Error> {
+        let guild_id = guild_id.as_u64().to_owned();
 
         match sqlx::query_as!(
             Self,
             "
-SELECT id, name, prefix FROM guilds WHERE guild = ?
+SELECT id, name, prefix, confirm_everyone, restrict_everyone, default_channel, announcements, announcement_channel, dm_opt_out, direct_delivery, keyword_triggers, log_channel, natural_send, natural_to, natural_every FROM guilds WHERE guild = ?
             ",
             guild_id
         )
         .fetch_one(pool)
+        .timed("GuildData::from_guild")
         .await
         {
             Ok(mut g) => {
-                g.name = Some(guild.name);
+                g.name = name.or(g.name);
 
                 Ok(g)
             }
@@ -38,7 +72,7 @@ SELECT id, name, prefix FROM guilds WHERE guild = ?
 INSERT INTO guilds (guild, name, prefix) VALUES (?, ?, ?)
                     ",
                     guild_id,
-                    guild.name,
+                    name,
                     *DEFAULT_PREFIX
                 )
                 .execute(&pool.clone())
@@ -47,7 +81,7 @@ INSERT INTO guilds (guild, name, prefix) VALUES (?, ?, ?)
                 Ok(sqlx::query_as!(
                     Self,
                     "
-SELECT id, name, prefix FROM guilds WHERE guild = ?
+SELECT id, name, prefix, confirm_everyone, restrict_everyone, default_channel, announcements, announcement_channel, dm_opt_out, direct_delivery, keyword_triggers, log_channel, natural_send, natural_to, natural_every FROM guilds WHERE guild = ?
                     ",
                     guild_id
                 )
@@ -63,13 +97,55 @@ SELECT id, name, prefix FROM guilds WHERE guild = ?
         }
     }
 
+    /// The guild's own `natural` keywords, or `None` if it uses only the defaults
+    pub fn natural_keywords(&self) -> Option<NaturalKeywords> {
+        if self.natural_send.is_none() && self.natural_to.is_none() && self.natural_every.is_none()
+        {
+            None
+        } else {
+            Some((
+                self.natural_send.clone(),
+                self.natural_to.clone(),
+                self.natural_every.clone(),
+            ))
+        }
+    }
+
+    /// Compile the regexes `natural` splits commands with, using the guild's keywords where set
+    pub fn natural_regexes((send, to, every): &NaturalKeywords) -> (Regex, Regex) {
+        let keyword = |word: &Option<String>, default| {
+            word.as_ref()
+                .map_or_else(|| String::from(default), |word| regex::escape(word))
+        };
+
+        (
+            natural_command_regex(
+                &keyword(send, DEFAULT_NATURAL_SEND),
+                &keyword(to, DEFAULT_NATURAL_TO),
+            ),
+            natural_interval_regex(&keyword(every, DEFAULT_NATURAL_EVERY)),
+        )
+    }
+
     pub async fn commit_changes(&self, pool: &MySqlPool) {
         sqlx::query!(
             "
-UPDATE guilds SET name = ?, prefix = ? WHERE id = ?
+UPDATE guilds SET name = ?, prefix = ?, confirm_everyone = ?, restrict_everyone = ?, default_channel = ?, announcements = ?, announcement_channel = ?, dm_opt_out = ?, direct_delivery = ?, keyword_triggers = ?, log_channel = ?, natural_send = ?, natural_to = ?, natural_every = ? WHERE id = ?
             ",
             self.name,
             self.prefix,
+            self.confirm_everyone,
+            self.restrict_everyone,
+            self.default_channel,
+            self.announcements,
+            self.announcement_channel,
+            self.dm_opt_out,
+            self.direct_delivery,
+            self.keyword_triggers,
+            self.log_channel,
+            self.natural_send,
+            self.natural_to,
+            self.natural_every,
             self.id
         )
         .execute(pool)
@@ -0,0 +1,321 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::{guild::Guild, id::RoleId};
+
+use sqlx::MySqlPool;
+
+use crate::{
+    consts::{MAX_PREFIX_LENGTH, MAX_TEMPLATES},
+    models::{
+        guild_data::GuildData,
+        template::{
+            Template, TEMPLATE_CONTENT_LIMIT, TEMPLATE_DESCRIPTION_LIMIT, TEMPLATE_TITLE_LIMIT,
+        },
+    },
+};
+
+/// A guild's configuration, portable between guilds for backups and server moves. Channels are
+/// left out, since they won't exist in another guild
+#[derive(Serialize, Deserialize)]
+pub struct GuildSettings {
+    pub prefix: String,
+    pub confirm_everyone: bool,
+    pub restrict_everyone: bool,
+    pub dm_opt_out: bool,
+    pub direct_delivery: bool,
+    #[serde(default)]
+    pub restrictions: Vec<RestrictionSettings>,
+    #[serde(default)]
+    pub aliases: Vec<AliasSettings>,
+    #[serde(default)]
+    pub templates: Vec<TemplateSettings>,
+    #[serde(default)]
+    pub disabled_commands: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RestrictionSettings {
+    pub role: u64,
+    /// Roles are matched by name when the ID doesn't exist in the guild being imported into
+    pub role_name: String,
+    pub command: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AliasSettings {
+    pub name: String,
+    pub command: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TemplateSettings {
+    pub name: String,
+    pub content: String,
+    pub interval: Option<u32>,
+    pub embed_title: String,
+    pub embed_description: String,
+    pub embed_color: u32,
+}
+
+/// Part of an import that was skipped, to be reported back
+pub enum SettingsConflict {
+    Prefix(String),
+    Role(String),
+    Restriction(String),
+    Alias(String),
+    Template(String),
+    Command(String),
+}
+
+impl SettingsConflict {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Prefix(_) => "settings/conflict_prefix",
+            Self::Role(_) => "settings/conflict_role",
+            Self::Restriction(_) => "settings/conflict_restriction",
+            Self::Alias(_) => "settings/conflict_alias",
+            Self::Template(_) => "settings/conflict_template",
+            Self::Command(_) => "settings/conflict_command",
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Prefix(name)
+            | Self::Role(name)
+            | Self::Restriction(name)
+            | Self::Alias(name)
+            | Self::Template(name)
+            | Self::Command(name) => name,
+        }
+    }
+}
+
+impl GuildSettings {
+    pub async fn from_guild(guild_data: &GuildData, pool: &MySqlPool) -> Result<Self, sqlx::Error> {
+        let restrictions = sqlx::query_as_unchecked!(
+            RestrictionSettings,
+            "
+SELECT roles.role, IFNULL(roles.name, '') AS role_name, command_restrictions.command
+FROM command_restrictions
+INNER JOIN roles ON roles.id = command_restrictions.role_id
+WHERE roles.guild_id = ? AND command_restrictions.expires IS NULL
+            ",
+            guild_data.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let aliases = sqlx::query_as!(
+            AliasSettings,
+            "
+SELECT name, command FROM command_aliases WHERE guild_id = ?
+            ",
+            guild_data.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let templates = sqlx::query_as_unchecked!(
+            TemplateSettings,
+            "
+SELECT name, content, `interval`, embed_title, embed_description, embed_color
+FROM reminder_templates
+WHERE guild_id = ?
+ORDER BY name
+            ",
+            guild_data.id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let disabled_commands = sqlx::query!(
+            "
+SELECT command FROM disabled_commands WHERE guild_id = ?
+            ",
+            guild_data.id
+        )
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| row.command)
+        .collect();
+
+        Ok(Self {
+            prefix: guild_data.prefix.clone(),
+            confirm_everyone: guild_data.confirm_everyone,
+            restrict_everyone: guild_data.restrict_everyone,
+            dm_opt_out: guild_data.dm_opt_out,
+            direct_delivery: guild_data.direct_delivery,
+            restrictions,
+            aliases,
+            templates,
+            disabled_commands,
+        })
+    }
+
+    /// Apply the settings on top of the guild's own. Existing aliases and templates are kept
+    /// rather than overwritten, and anything that can't be applied is returned as a conflict.
+    /// Everything is written in one transaction, and `guild_data` is only updated (but not
+    /// committed) once that succeeds. `can_disable` says whether a command name exists and may be
+    /// disabled
+    pub async fn apply(
+        &self,
+        guild: &Guild,
+        guild_data: &mut GuildData,
+        pool: &MySqlPool,
+        can_disable: impl Fn(&str) -> bool,
+    ) -> Result<Vec<SettingsConflict>, sqlx::Error> {
+        let mut conflicts = vec![];
+
+        let prefix_valid =
+            !self.prefix.is_empty() && self.prefix.chars().count() <= *MAX_PREFIX_LENGTH;
+
+        if !prefix_valid {
+            conflicts.push(SettingsConflict::Prefix(self.prefix.clone()));
+        }
+
+        let mut template_count = Template::count_from_guild(*guild.id.as_u64(), pool).await;
+
+        let mut transaction = pool.begin().await?;
+
+        for restriction in &self.restrictions {
+            let role = guild.roles.get(&RoleId(restriction.role)).or_else(|| {
+                guild
+                    .roles
+                    .values()
+                    .find(|role| role.name == restriction.role_name)
+            });
+
+            match role {
+                Some(role) => {
+                    sqlx::query!(
+                        "
+INSERT IGNORE INTO roles (role, name, guild_id) VALUES (?, ?, ?)
+                        ",
+                        role.id.as_u64(),
+                        role.name,
+                        guild_data.id
+                    )
+                    .execute(&mut transaction)
+                    .await?;
+
+                    // the command column only accepts commands that can be restricted, so anything
+                    // else fails to insert
+                    let inserted = sqlx::query!(
+                        "
+INSERT INTO command_restrictions (role_id, command) VALUES ((SELECT id FROM roles WHERE role = ?), ?)
+    ON DUPLICATE KEY UPDATE command = command
+                        ",
+                        role.id.as_u64(),
+                        restriction.command
+                    )
+                    .execute(&mut transaction)
+                    .await;
+
+                    if inserted.is_err() {
+                        conflicts.push(SettingsConflict::Restriction(restriction.command.clone()));
+                    }
+                }
+
+                None => conflicts.push(SettingsConflict::Role(restriction.role_name.clone())),
+            }
+        }
+
+        for alias in &self.aliases {
+            // the same limits as the alias columns
+            if alias.name.is_empty()
+                || alias.name.chars().count() > 12
+                || alias.command.chars().count() > 2048
+            {
+                conflicts.push(SettingsConflict::Alias(alias.name.clone()));
+
+                continue;
+            }
+
+            let inserted = sqlx::query!(
+                "
+INSERT IGNORE INTO command_aliases (guild_id, name, command) VALUES (?, ?, ?)
+                ",
+                guild_data.id,
+                alias.name,
+                alias.command
+            )
+            .execute(&mut transaction)
+            .await?
+            .rows_affected();
+
+            if inserted == 0 {
+                conflicts.push(SettingsConflict::Alias(alias.name.clone()));
+            }
+        }
+
+        for template in &self.templates {
+            // the same limit as the template command, and the lengths of the template columns
+            if !Template::valid_name(&template.name)
+                || template_count >= *MAX_TEMPLATES
+                || template.content.chars().count() > TEMPLATE_CONTENT_LIMIT
+                || template.embed_title.chars().count() > TEMPLATE_TITLE_LIMIT
+                || template.embed_description.chars().count() > TEMPLATE_DESCRIPTION_LIMIT
+            {
+                conflicts.push(SettingsConflict::Template(template.name.clone()));
+
+                continue;
+            }
+
+            let inserted = sqlx::query!(
+                "
+INSERT IGNORE INTO reminder_templates
+    (guild_id, name, content, `interval`, embed_title, embed_description, embed_color)
+    VALUES (?, ?, ?, ?, ?, ?, ?)
+                ",
+                guild_data.id,
+                template.name,
+                template.content,
+                template.interval,
+                template.embed_title,
+                template.embed_description,
+                template.embed_color
+            )
+            .execute(&mut transaction)
+            .await?
+            .rows_affected();
+
+            if inserted == 0 {
+                conflicts.push(SettingsConflict::Template(template.name.clone()));
+            } else {
+                template_count += 1;
+            }
+        }
+
+        for command in &self.disabled_commands {
+            if !can_disable(command) {
+                conflicts.push(SettingsConflict::Command(command.clone()));
+
+                continue;
+            }
+
+            sqlx::query!(
+                "
+INSERT IGNORE INTO disabled_commands (guild_id, command) VALUES (?, ?)
+                ",
+                guild_data.id,
+                command
+            )
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        if prefix_valid {
+            guild_data.prefix = self.prefix.clone();
+        }
+
+        guild_data.confirm_everyone = self.confirm_everyone;
+        guild_data.restrict_everyone = self.restrict_everyone;
+        guild_data.dm_opt_out = self.dm_opt_out;
+        guild_data.direct_delivery = self.direct_delivery;
+
+        Ok(conflicts)
+    }
+}
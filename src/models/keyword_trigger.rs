@@ -0,0 +1,95 @@
+use sqlx::MySqlPool;
+
+use crate::consts::KEYWORD_COOLDOWN;
+
+pub struct KeywordTrigger {
+    pub id: u32,
+    pub keyword: String,
+    pub content: String,
+}
+
+impl KeywordTrigger {
+    /// Keywords are single words of letters, numbers, `-` and `_`, so they can be found by
+    /// splitting messages on everything else
+    pub fn valid_keyword(keyword: &str) -> bool {
+        !keyword.is_empty()
+            && keyword.chars().count() <= 32
+            && keyword.chars().all(Self::is_word_char)
+    }
+
+    pub fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '-' || c == '_'
+    }
+
+    /// Add a trigger, or replace the content of the channel's trigger with the same keyword
+    pub async fn create(
+        channel: u64,
+        keyword: &str,
+        content: &str,
+        set_by: u64,
+        pool: &MySqlPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "
+INSERT INTO keyword_triggers (channel, keyword, content, set_by) VALUES (?, ?, ?, ?)
+    ON DUPLICATE KEY UPDATE content = ?, set_by = ?
+            ",
+            channel,
+            keyword,
+            content,
+            set_by,
+            content,
+            set_by
+        )
+        .execute(pool)
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn delete(channel: u64, keyword: &str, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+DELETE FROM keyword_triggers WHERE channel = ? AND keyword = ?
+            ",
+            channel,
+            keyword
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .unwrap_or(false)
+    }
+
+    pub async fn from_channel(channel: u64, pool: &MySqlPool) -> Vec<Self> {
+        sqlx::query_as_unchecked!(
+            KeywordTrigger,
+            "
+SELECT id, keyword, content FROM keyword_triggers WHERE channel = ? ORDER BY keyword
+            ",
+            channel
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Mark the trigger as fired, unless it already fired within `KEYWORD_COOLDOWN` seconds.
+    /// Only the caller that marked it should send it
+    pub async fn claim(&self, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+UPDATE keyword_triggers
+SET last_triggered = UTC_TIMESTAMP()
+WHERE
+    id = ? AND
+    (last_triggered IS NULL OR last_triggered <= DATE_SUB(UTC_TIMESTAMP(), INTERVAL ? SECOND))
+            ",
+            self.id,
+            *KEYWORD_COOLDOWN
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .unwrap_or(false)
+    }
+}
@@ -1,15 +1,26 @@
 pub mod channel_data;
+pub mod event;
 pub mod guild_data;
+pub mod guild_settings;
+pub mod keyword_trigger;
+pub mod presence_reminder;
 pub mod reminder;
+pub mod reminder_dump;
+pub mod template;
 pub mod timer;
 pub mod user_data;
 
 use serenity::{async_trait, model::id::GuildId, prelude::Context};
 
-use crate::{consts::DEFAULT_PREFIX, GuildDataCache, SQLPool};
+use crate::{
+    consts::DEFAULT_PREFIX, guild_context::guild_context, GuildDataCache, NaturalRegexCache,
+    SQLPool,
+};
 
 use guild_data::GuildData;
 
+use regex::Regex;
+
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -21,6 +32,11 @@ pub trait CtxGuildData {
     ) -> Result<Arc<RwLock<GuildData>>, sqlx::Error>;
 
     async fn prefix<G: Into<GuildId> + Send + Sync>(&self, guild_id: Option<G>) -> String;
+
+    async fn natural_regexes<G: Into<GuildId> + Send + Sync>(
+        &self,
+        guild_id: G,
+    ) -> Result<Option<Arc<(Regex, Regex)>>, sqlx::Error>;
 }
 
 #[async_trait]
@@ -31,8 +47,6 @@ impl CtxGuildData for Context {
     ) -> Result<Arc<RwLock<GuildData>>, sqlx::Error> {
         let guild_id = guild_id.into();
 
-        let guild = guild_id.to_guild_cached(&self.cache).unwrap();
-
         let guild_cache = self
             .data
             .read()
@@ -46,7 +60,17 @@ impl CtxGuildData for Context {
         } else {
             let pool = self.data.read().await.get::<SQLPool>().cloned().unwrap();
 
-            match GuildData::from_guild(guild, &pool).await {
+            // the guild may not be cached yet, such as just after startup
+            let name = match guild_id.to_guild_cached(&self.cache) {
+                Some(guild) => Some(guild.name),
+
+                None => guild_context(self, guild_id)
+                    .await
+                    .ok()
+                    .map(|guild| guild.name.clone()),
+            };
+
+            match GuildData::from_id(guild_id, name, &pool).await {
                 Ok(d) => {
                     let lock = Arc::new(RwLock::new(d));
 
@@ -75,4 +99,45 @@ impl CtxGuildData for Context {
             DEFAULT_PREFIX.clone()
         }
     }
+
+    /// The guild's `natural` regexes if it has its own keywords, compiled once for each change of
+    /// keywords. `None` means the default regexes apply
+    async fn natural_regexes<G: Into<GuildId> + Send + Sync>(
+        &self,
+        guild_id: G,
+    ) -> Result<Option<Arc<(Regex, Regex)>>, sqlx::Error> {
+        let guild_id = guild_id.into();
+
+        let keywords = match self
+            .guild_data(guild_id)
+            .await?
+            .read()
+            .await
+            .natural_keywords()
+        {
+            Some(keywords) => keywords,
+
+            None => return Ok(None),
+        };
+
+        let regex_cache = self
+            .data
+            .read()
+            .await
+            .get::<NaturalRegexCache>()
+            .cloned()
+            .unwrap();
+
+        if let Some(cached) = regex_cache.get(&guild_id) {
+            if cached.0 == keywords {
+                return Ok(Some(cached.1.clone()));
+            }
+        }
+
+        let regexes = Arc::new(GuildData::natural_regexes(&keywords));
+
+        regex_cache.insert(guild_id, (keywords, regexes.clone()));
+
+        Ok(Some(regexes))
+    }
 }
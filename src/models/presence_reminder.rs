@@ -0,0 +1,87 @@
+use sqlx::MySqlPool;
+
+pub struct PresenceReminder {
+    pub id: u32,
+    pub target_user: u64,
+    pub channel: u64,
+    pub content: String,
+}
+
+impl PresenceReminder {
+    pub async fn create(
+        target_user: u64,
+        channel: u64,
+        content: &str,
+        set_by: u64,
+        pool: &MySqlPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "
+INSERT INTO presence_reminders (target_user, channel, content, set_by) VALUES (?, ?, ?, ?)
+            ",
+            target_user,
+            channel,
+            content,
+            set_by
+        )
+        .execute(pool)
+        .await
+        .map(|_| ())
+    }
+
+    pub async fn from_target(target_user: u64, pool: &MySqlPool) -> Vec<Self> {
+        sqlx::query_as_unchecked!(
+            PresenceReminder,
+            "
+SELECT id, target_user, channel, content FROM presence_reminders WHERE target_user = ?
+            ",
+            target_user
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+    }
+
+    pub async fn from_channel(channel: u64, pool: &MySqlPool) -> Vec<Self> {
+        sqlx::query_as_unchecked!(
+            PresenceReminder,
+            "
+SELECT id, target_user, channel, content FROM presence_reminders WHERE channel = ? ORDER BY id
+            ",
+            channel
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Returns whether a reminder was deleted. Only reminders in the given channel can be deleted
+    pub async fn delete(id: u32, channel: u64, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+DELETE FROM presence_reminders WHERE id = ? AND channel = ?
+            ",
+            id,
+            channel
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .unwrap_or(false)
+    }
+
+    /// Remove the reminder ahead of sending it. Presence updates arrive once per shared guild, so
+    /// only the caller that actually deleted the row should send it.
+    pub async fn claim(&self, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+DELETE FROM presence_reminders WHERE id = ?
+            ",
+            self.id
+        )
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected() > 0)
+        .unwrap_or(false)
+    }
+}
@@ -1,28 +1,54 @@
 use serenity::{
+    builder::CreateMessage,
     client::Context,
-    model::id::{ChannelId, GuildId, UserId},
+    http::AttachmentType,
+    model::id::{ChannelId, GuildId, RoleId, UserId},
 };
 
-use chrono::NaiveDateTime;
+use chrono::{LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use sqlx::MySqlPool;
 
 use crate::{
-    consts::{DAY, HOUR, MINUTE, REGEX_CHANNEL},
+    consts::{DAY, HOUR, MINUTE, REGEX_CHANNEL, REGEX_ROLE, REGEX_USER},
+    language_manager::LanguageManager,
+    slow_query::TimedQuery,
     SQLPool,
 };
 
 use num_integer::Integer;
 
-fn longhand_displacement(seconds: u64) -> String {
+use std::collections::HashSet;
+
+/// Unit names for `longhand_displacement`, largest first. Used where no language is known
+pub const ENGLISH_UNITS: [&str; 4] = ["days", "hours", "minutes", "seconds"];
+
+/// Unit names for `longhand_displacement` in the given language, falling back to English for any
+/// that haven't been translated
+pub fn displacement_units<'a>(lm: &'a LanguageManager, language: &str) -> [&'a str; 4] {
+    let mut units = ENGLISH_UNITS;
+
+    for (unit, key) in units
+        .iter_mut()
+        .zip(["time/days", "time/hours", "time/minutes", "time/seconds"].iter())
+    {
+        if let Some(name) = lm.try_get(language, key) {
+            *unit = name;
+        }
+    }
+
+    units
+}
+
+pub fn longhand_displacement(seconds: u64, units: &[&str; 4]) -> String {
     let (days, seconds) = seconds.div_rem(&DAY);
     let (hours, seconds) = seconds.div_rem(&HOUR);
     let (minutes, seconds) = seconds.div_rem(&MINUTE);
 
     let mut sections = vec![];
 
-    for (var, name) in [days, hours, minutes, seconds]
-        .iter()
-        .zip(["days", "hours", "minutes", "seconds"].iter())
-    {
+    for (var, name) in [days, hours, minutes, seconds].iter().zip(units.iter()) {
         if *var > 0 {
             sections.push(format!("{} {}", var, name));
         }
@@ -92,6 +118,7 @@ WHERE
 
         let enabled = if flags.show_disabled { "0,1" } else { "1" };
         let channel_id = channel_id.into();
+        let set_by = flags.set_by.map(|user| *user.as_u64());
 
         sqlx::query_as_unchecked!(
             Self,
@@ -119,7 +146,8 @@ ON
     reminders.set_by = users.id
 WHERE
     channels.channel = ? AND
-    FIND_IN_SET(reminders.enabled, ?)
+    FIND_IN_SET(reminders.enabled, ?) AND
+    (? IS NULL OR users.user = ?)
 ORDER BY
     reminders.utc_time
 LIMIT
@@ -127,9 +155,12 @@ LIMIT
             ",
             channel_id.as_u64(),
             enabled,
+            set_by,
+            set_by,
             flags.limit
         )
         .fetch_all(&pool)
+        .timed("Reminder::from_channel")
         .await
         .unwrap()
     }
@@ -179,6 +210,7 @@ WHERE
                     channels
                 )
                 .fetch_all(&pool)
+                .timed("Reminder::from_guild channels")
                 .await
             } else {
                 sqlx::query_as_unchecked!(
@@ -211,6 +243,7 @@ WHERE
                     guild_id.as_u64()
                 )
                 .fetch_all(&pool)
+                .timed("Reminder::from_guild")
                 .await
             }
         } else {
@@ -244,11 +277,318 @@ WHERE
                 user.as_u64()
             )
             .fetch_all(&pool)
+            .timed("Reminder::from_guild DM")
+            .await
+        }
+        .unwrap()
+    }
+
+    /// The reminders `offset` moves: every reminder whose channel belongs to the guild with the
+    /// database id `guild_id`, or with no guild, every reminder in the DM channel `dm_channel`
+    pub async fn offset_targets(
+        pool: &MySqlPool,
+        guild_id: Option<u32>,
+        dm_channel: u32,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        if let Some(guild_id) = guild_id {
+            sqlx::query_as_unchecked!(
+                Self,
+                "
+SELECT
+    reminders.id,
+    reminders.uid,
+    channels.channel,
+    reminders.utc_time,
+    reminders.interval,
+    reminders.expires,
+    reminders.enabled,
+    reminders.content,
+    reminders.embed_description,
+    users.user AS set_by
+FROM
+    reminders
+INNER JOIN
+    channels
+ON
+    channels.id = reminders.channel_id
+LEFT JOIN
+    users
+ON
+    reminders.set_by = users.id
+WHERE
+    channels.guild_id = ?
+ORDER BY
+    reminders.utc_time
+                ",
+                guild_id
+            )
+            .fetch_all(pool)
+            .timed("Reminder::offset_targets")
+            .await
+        } else {
+            sqlx::query_as_unchecked!(
+                Self,
+                "
+SELECT
+    reminders.id,
+    reminders.uid,
+    channels.channel,
+    reminders.utc_time,
+    reminders.interval,
+    reminders.expires,
+    reminders.enabled,
+    reminders.content,
+    reminders.embed_description,
+    users.user AS set_by
+FROM
+    reminders
+INNER JOIN
+    channels
+ON
+    channels.id = reminders.channel_id
+LEFT JOIN
+    users
+ON
+    reminders.set_by = users.id
+WHERE
+    reminders.channel_id = ?
+ORDER BY
+    reminders.utc_time
+                ",
+                dm_channel
+            )
+            .fetch_all(pool)
+            .timed("Reminder::offset_targets DM")
             .await
         }
+    }
+
+    /// Reminders due from `start` to `end`, soonest first. In a guild, these are the reminders in
+    /// any of its channels, and otherwise the reminders in the user's DMs
+    pub async fn between(
+        ctx: &Context,
+        guild_id: Option<GuildId>,
+        user: UserId,
+        start: i64,
+        end: i64,
+    ) -> Vec<Self> {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        let guild_id = guild_id.map(|guild_id| *guild_id.as_u64());
+
+        sqlx::query_as_unchecked!(
+            Self,
+            "
+SELECT
+    reminders.id,
+    reminders.uid,
+    channels.channel,
+    reminders.utc_time,
+    reminders.interval,
+    reminders.expires,
+    reminders.enabled,
+    reminders.content,
+    reminders.embed_description,
+    users.user AS set_by
+FROM
+    reminders
+INNER JOIN
+    channels
+ON
+    channels.id = reminders.channel_id
+LEFT JOIN
+    users
+ON
+    reminders.set_by = users.id
+WHERE
+    IF(
+        ? IS NULL,
+        channels.id = (SELECT dm_channel FROM users WHERE user = ?),
+        channels.guild_id = (SELECT id FROM guilds WHERE guild = ?)
+    ) AND
+    reminders.utc_time BETWEEN
+        DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND) AND
+        DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND)
+ORDER BY
+    reminders.utc_time
+            ",
+            guild_id,
+            user.as_u64(),
+            guild_id,
+            start,
+            end
+        )
+        .fetch_all(&pool)
+        .timed("Reminder::between")
+        .await
         .unwrap()
     }
 
+    pub async fn next_in_channel<C: Into<ChannelId>>(
+        ctx: &Context,
+        channel_id: C,
+        after: Option<NaiveDateTime>,
+    ) -> Option<Self> {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        let channel_id = channel_id.into();
+
+        sqlx::query_as_unchecked!(
+            Self,
+            "
+SELECT
+    reminders.id,
+    reminders.uid,
+    channels.channel,
+    reminders.utc_time,
+    reminders.interval,
+    reminders.expires,
+    reminders.enabled,
+    reminders.content,
+    reminders.embed_description,
+    users.user AS set_by
+FROM
+    reminders
+INNER JOIN
+    channels
+ON
+    reminders.channel_id = channels.id
+LEFT JOIN
+    users
+ON
+    reminders.set_by = users.id
+WHERE
+    channels.channel = ? AND
+    reminders.enabled = 1 AND
+    reminders.utc_time >= IFNULL(?, reminders.utc_time)
+ORDER BY
+    reminders.utc_time
+LIMIT
+    1
+            ",
+            channel_id.as_u64(),
+            after
+        )
+        .fetch_one(&pool)
+        .await
+        .ok()
+    }
+
+    /// Send the reminder without pinging anyone until `until`, or ping as normal again with `None`
+    pub async fn mute_pings(&self, ctx: &Context, until: Option<NaiveDateTime>) {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        let _ = sqlx::query!(
+            "
+UPDATE reminders SET muted_until = ? WHERE id = ?
+            ",
+            until,
+            self.id
+        )
+        .execute(&pool)
+        .await;
+    }
+
+    /// Remember that the reminder's time was given as a local time in `timezone`
+    pub async fn set_local_timezone(&self, ctx: &Context, timezone: Tz) {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        let _ = sqlx::query!(
+            "
+UPDATE reminders SET local_timezone = ? WHERE id = ?
+            ",
+            timezone.to_string(),
+            self.id
+        )
+        .execute(&pool)
+        .await;
+    }
+
+    /// Move the user's upcoming reminders that were given as local times in `from` to the same
+    /// local times in `to`. A local time that doesn't exist in `to`, or exists twice, because of a
+    /// daylight saving change is left alone. The reminders are moved together or not at all.
+    /// Returns how many reminders were moved
+    pub async fn shift_timezone(
+        pool: &MySqlPool,
+        user_id: u32,
+        from: Tz,
+        to: Tz,
+    ) -> Result<u64, sqlx::Error> {
+        let rows = sqlx::query!(
+            "
+SELECT id, utc_time FROM reminders
+WHERE set_by = ? AND local_timezone = ? AND utc_time > UTC_TIMESTAMP()
+            ",
+            user_id,
+            from.to_string()
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut transaction = pool.begin().await?;
+        let mut shifted = 0;
+
+        for row in rows {
+            let local = Utc
+                .from_utc_datetime(&row.utc_time)
+                .with_timezone(&from)
+                .naive_local();
+
+            if let LocalResult::Single(time) = to.from_local_datetime(&local) {
+                sqlx::query!(
+                    "
+UPDATE reminders SET utc_time = ?, local_timezone = ? WHERE id = ?
+                    ",
+                    time.naive_utc(),
+                    to.to_string(),
+                    row.id
+                )
+                .execute(&mut transaction)
+                .await?;
+
+                shifted += 1;
+            }
+        }
+
+        transaction.commit().await?;
+
+        Ok(shifted)
+    }
+
+    /// Channels to try in order when the reminder can't be posted in its own, by their internal IDs
+    pub async fn set_fallback_channels(&self, ctx: &Context, channel_ids: &[u32]) {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        for (position, channel_id) in channel_ids.iter().enumerate() {
+            let _ = sqlx::query!(
+                "
+INSERT INTO reminder_fallback_channels (reminder_id, channel_id, position) VALUES (?, ?, ?)
+                ",
+                self.id,
+                channel_id,
+                position as u8
+            )
+            .execute(&pool)
+            .await;
+        }
+    }
+
+    /// Stop a reminder that was waiting to be acknowledged. Returns false if it was already
+    /// acknowledged or deleted
+    pub async fn acknowledge(ctx: &Context, uid: &str) -> bool {
+        let pool = ctx.data.read().await.get::<SQLPool>().cloned().unwrap();
+
+        sqlx::query!(
+            "
+DELETE FROM reminders WHERE uid = ? AND acknowledge = 1
+            ",
+            uid
+        )
+        .execute(&pool)
+        .await
+        .map_or(false, |res| res.rows_affected() > 0)
+    }
+
     pub fn display_content(&self) -> &str {
         if self.content.is_empty() {
             &self.embed_description
@@ -257,23 +597,51 @@ WHERE
         }
     }
 
-    pub fn display(&self, flags: &LookFlags, inter: &str) -> String {
+    /// Who set the reminder, as a mention. Creators in `departed` are no longer in the guild and
+    /// are shown by ID, since their mentions can't be resolved
+    pub fn creator_display(&self, departed: &HashSet<u64>) -> String {
+        match self.set_by {
+            Some(user) if departed.contains(&user) => format!("`{}`", user),
+
+            Some(user) => format!("<@{}>", user),
+
+            None => "unknown".to_string(),
+        }
+    }
+
+    pub fn display(
+        &self,
+        flags: &LookFlags,
+        inter: &str,
+        units: &[&str; 4],
+        departed: &HashSet<u64>,
+    ) -> String {
         let time_display = match flags.time_display {
-            TimeDisplayType::Absolute => format!("<t:{}>", self.utc_time.timestamp()),
+            TimeDisplayType::Absolute => match flags.display_timezone {
+                // a fixed timezone shows everyone the same wall-clock time
+                Some(timezone) => format!(
+                    "{} ({})",
+                    Utc.from_utc_datetime(&self.utc_time)
+                        .with_timezone(&timezone)
+                        .format("%Y-%m-%d %H:%M"),
+                    timezone
+                ),
+
+                None => format!("<t:{}>", self.utc_time.timestamp()),
+            },
 
             TimeDisplayType::Relative => format!("<t:{}:R>", self.utc_time.timestamp()),
         };
 
         if let Some(interval) = self.interval {
+            // marked so repeating reminders stand out in a long listing
             format!(
-                "'{}' *{}* **{}**, repeating every **{}** (set by {})",
+                "🔁 '{}' *{}* **{}**, repeating every **{}** (set by {})",
                 self.display_content(),
                 &inter,
                 time_display,
-                longhand_displacement(interval as u64),
-                self.set_by
-                    .map(|i| format!("<@{}>", i))
-                    .unwrap_or_else(|| "unknown".to_string())
+                longhand_displacement(interval as u64, units),
+                self.creator_display(departed)
             )
         } else {
             format!(
@@ -281,14 +649,173 @@ WHERE
                 self.display_content(),
                 &inter,
                 time_display,
-                self.set_by
-                    .map(|i| format!("<@{}>", i))
-                    .unwrap_or_else(|| "unknown".to_string())
+                self.creator_display(departed)
             )
         }
     }
 }
 
+/// A reminder's message as it will be sent, for previewing
+pub struct RenderedReminder {
+    content: String,
+    attachment: Option<Vec<u8>>,
+    attachment_name: Option<String>,
+    embed_title: String,
+    embed_description: String,
+    embed_image_url: Option<String>,
+    embed_thumbnail_url: Option<String>,
+    embed_footer: String,
+    embed_footer_url: Option<String>,
+    embed_author: String,
+    embed_author_url: Option<String>,
+    embed_color: u32,
+    fields: Vec<(String, String, bool)>,
+    muted: bool,
+}
+
+impl RenderedReminder {
+    fn has_embed(&self) -> bool {
+        !(self.embed_title.is_empty()
+            && self.embed_description.is_empty()
+            && self.embed_image_url.is_none()
+            && self.embed_thumbnail_url.is_none()
+            && self.embed_footer.is_empty()
+            && self.embed_author.is_empty()
+            && self.fields.is_empty())
+    }
+
+    /// Fill in a message with the reminder's content, embed and attachment
+    pub fn build<'a, 'b>(&'a self, m: &'b mut CreateMessage<'a>) -> &'b mut CreateMessage<'a> {
+        if !self.content.is_empty() {
+            m.content(&self.content);
+        }
+
+        if self.muted {
+            m.allowed_mentions(|am| am.empty_parse());
+        }
+
+        if let (Some(data), Some(filename)) = (&self.attachment, &self.attachment_name) {
+            m.add_file(AttachmentType::Bytes {
+                data: data.as_slice().into(),
+                filename: filename.clone(),
+            });
+        }
+
+        if self.has_embed() {
+            m.embed(|e| {
+                if !self.embed_title.is_empty() {
+                    e.title(&self.embed_title);
+                }
+
+                if !self.embed_description.is_empty() {
+                    e.description(&self.embed_description);
+                }
+
+                if let Some(url) = &self.embed_image_url {
+                    e.image(url);
+                }
+
+                if let Some(url) = &self.embed_thumbnail_url {
+                    e.thumbnail(url);
+                }
+
+                if !self.embed_footer.is_empty() {
+                    e.footer(|f| {
+                        f.text(&self.embed_footer);
+
+                        if let Some(url) = &self.embed_footer_url {
+                            f.icon_url(url);
+                        }
+
+                        f
+                    });
+                }
+
+                if !self.embed_author.is_empty() {
+                    e.author(|a| {
+                        a.name(&self.embed_author);
+
+                        if let Some(url) = &self.embed_author_url {
+                            a.icon_url(url);
+                        }
+
+                        a
+                    });
+                }
+
+                e.color(self.embed_color).fields(
+                    self.fields
+                        .iter()
+                        .map(|(title, value, inline)| (title.as_str(), value.as_str(), *inline)),
+                )
+            });
+        }
+
+        m
+    }
+}
+
+/// Load what a reminder will send, from its content, embed and embed fields
+pub async fn render_reminder(
+    pool: &MySqlPool,
+    reminder_id: u32,
+) -> Result<RenderedReminder, sqlx::Error> {
+    let row = sqlx::query!(
+        "
+SELECT
+    content,
+    attachment,
+    attachment_name,
+    embed_title,
+    embed_description,
+    embed_image_url,
+    embed_thumbnail_url,
+    embed_footer,
+    embed_footer_url,
+    embed_author,
+    embed_author_url,
+    embed_color,
+    muted_until
+FROM reminders
+WHERE id = ?
+        ",
+        reminder_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    let fields = sqlx::query!(
+        "
+SELECT title, value, inline FROM embed_fields WHERE reminder_id = ? ORDER BY id
+        ",
+        reminder_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|field| (field.title, field.value, field.inline != 0))
+    .collect();
+
+    Ok(RenderedReminder {
+        content: row.content,
+        attachment: row.attachment,
+        attachment_name: row.attachment_name,
+        embed_title: row.embed_title,
+        embed_description: row.embed_description,
+        embed_image_url: row.embed_image_url,
+        embed_thumbnail_url: row.embed_thumbnail_url,
+        embed_footer: row.embed_footer,
+        embed_footer_url: row.embed_footer_url,
+        embed_author: row.embed_author,
+        embed_author_url: row.embed_author_url,
+        embed_color: row.embed_color,
+        fields,
+        muted: row
+            .muted_until
+            .map_or(false, |until| until > Utc::now().naive_utc()),
+    })
+}
+
 enum TimeDisplayType {
     Absolute,
     Relative,
@@ -298,7 +825,10 @@ pub struct LookFlags {
     pub limit: u16,
     pub show_disabled: bool,
     pub channel_id: Option<ChannelId>,
+    pub role_id: Option<RoleId>,
+    pub set_by: Option<UserId>,
     time_display: TimeDisplayType,
+    pub display_timezone: Option<Tz>,
 }
 
 impl Default for LookFlags {
@@ -307,7 +837,63 @@ impl Default for LookFlags {
             limit: u16::MAX,
             show_disabled: true,
             channel_id: None,
+            role_id: None,
+            set_by: None,
             time_display: TimeDisplayType::Relative,
+            display_timezone: None,
+        }
+    }
+}
+
+enum LookToken {
+    Enabled,
+    Time,
+    Limit(u16),
+    Channel(ChannelId),
+    Role(RoleId),
+    SetBy(UserId),
+    Unknown,
+}
+
+impl LookToken {
+    fn classify(token: &str) -> Self {
+        match token.to_lowercase().as_str() {
+            "enabled" => Self::Enabled,
+
+            "time" => Self::Time,
+
+            token => {
+                if let Some(role) = token.strip_prefix("role:") {
+                    // accept both a role mention and a bare ID
+                    REGEX_ROLE
+                        .captures(role)
+                        .map(|cap| cap.get(1))
+                        .flatten()
+                        .map_or(role, |c| c.as_str())
+                        .parse::<u64>()
+                        .map_or(Self::Unknown, |role| Self::Role(RoleId(role)))
+                } else if let Some(user) = token.strip_prefix("by:") {
+                    REGEX_USER
+                        .captures(user)
+                        .map(|cap| cap.get(1))
+                        .flatten()
+                        .map_or(user, |c| c.as_str())
+                        .parse::<u64>()
+                        .map_or(Self::Unknown, |user| Self::SetBy(UserId(user)))
+                } else if let Ok(limit) = token.parse::<u16>() {
+                    Self::Limit(limit)
+                } else if let Some(channel) = REGEX_CHANNEL
+                    .captures(token)
+                    .map(|cap| cap.get(1))
+                    .flatten()
+                    .map(|c| c.as_str().parse::<u64>().ok())
+                    .flatten()
+                {
+                    Self::Channel(ChannelId(channel))
+                } else {
+                    Self::Unknown
+                }
+            }
         }
     }
 }
@@ -316,31 +902,94 @@ impl LookFlags {
     pub fn from_string(args: &str) -> Self {
         let mut new_flags: Self = Default::default();
 
-        for arg in args.split(' ') {
-            match arg {
-                "enabled" => {
+        for token in args.split_whitespace().map(LookToken::classify) {
+            match token {
+                LookToken::Enabled => {
                     new_flags.show_disabled = false;
                 }
 
-                "time" => {
+                LookToken::Time => {
                     new_flags.time_display = TimeDisplayType::Absolute;
                 }
 
-                param => {
-                    if let Ok(val) = param.parse::<u16>() {
-                        new_flags.limit = val;
-                    } else if let Some(channel) = REGEX_CHANNEL
-                        .captures(&arg)
-                        .map(|cap| cap.get(1))
-                        .flatten()
-                        .map(|c| c.as_str().parse::<u64>().unwrap())
-                    {
-                        new_flags.channel_id = Some(ChannelId(channel));
-                    }
+                LookToken::Limit(limit) => {
+                    new_flags.limit = limit;
                 }
+
+                LookToken::Channel(channel_id) => {
+                    new_flags.channel_id = Some(channel_id);
+                }
+
+                LookToken::Role(role_id) => {
+                    new_flags.role_id = Some(role_id);
+                }
+
+                LookToken::SetBy(user_id) => {
+                    new_flags.set_by = Some(user_id);
+                }
+
+                LookToken::Unknown => {}
             }
         }
 
         new_flags
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn look_flags_limit_and_time() {
+        let flags = LookFlags::from_string("all 5 time");
+
+        assert_eq!(flags.limit, 5);
+        assert!(matches!(flags.time_display, TimeDisplayType::Absolute));
+        assert!(flags.show_disabled);
+        assert_eq!(flags.channel_id, None);
+    }
+
+    #[test]
+    fn look_flags_channel_and_enabled() {
+        let flags = LookFlags::from_string("<#123> enabled");
+
+        assert_eq!(flags.channel_id, Some(ChannelId(123)));
+        assert!(!flags.show_disabled);
+        assert_eq!(flags.limit, u16::MAX);
+
+        // a bare `#123` isn't a channel, and doesn't affect the other flags
+        let flags = LookFlags::from_string("#123 enabled");
+
+        assert_eq!(flags.channel_id, None);
+        assert!(!flags.show_disabled);
+        assert_eq!(flags.limit, u16::MAX);
+    }
+
+    #[test]
+    fn look_flags_unknown_tokens() {
+        // a later unknown token doesn't undo an earlier channel
+        let flags = LookFlags::from_string("<#123>  ENABLED  foo 10");
+
+        assert_eq!(flags.channel_id, Some(ChannelId(123)));
+        assert!(!flags.show_disabled);
+        assert_eq!(flags.limit, 10);
+
+        let flags = LookFlags::from_string("<#99999999999999999999999>");
+
+        assert_eq!(flags.channel_id, None);
+    }
+
+    #[test]
+    fn look_flags_role_and_set_by() {
+        let flags = LookFlags::from_string("role:<@&5> by:<@!6>");
+
+        assert_eq!(flags.role_id, Some(RoleId(5)));
+        assert_eq!(flags.set_by, Some(UserId(6)));
+
+        let flags = LookFlags::from_string("role:5 by:6");
+
+        assert_eq!(flags.role_id, Some(RoleId(5)));
+        assert_eq!(flags.set_by, Some(UserId(6)));
+    }
+}
@@ -0,0 +1,331 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId, UserId},
+};
+
+use sqlx::MySqlPool;
+
+use crate::{
+    consts::MAX_CONTENT_LENGTH,
+    models::{channel_data::ChannelData, user_data::UserData, CtxGuildData},
+    slow_query::TimedQuery,
+};
+
+/// Reminders copied out of one instance's database, to be recreated in another's. Everything is
+/// referred to by Discord ID, since internal IDs differ between databases. Attachments are left
+/// out to keep dumps small enough to send through Discord
+#[derive(Serialize, Deserialize)]
+pub struct ReminderDump {
+    pub reminders: Vec<DumpedReminder>,
+    /// The embed fields of every reminder in `reminders`, matched to them by UID
+    #[serde(default)]
+    pub embed_fields: Vec<DumpedEmbedField>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DumpedReminder {
+    pub uid: String,
+    pub name: String,
+    pub channel: u64,
+    pub guild: Option<u64>,
+    /// The user whose DMs the reminder goes to, when it isn't in a guild
+    pub dm_user: Option<u64>,
+    pub set_by: Option<u64>,
+    pub utc_time: i64,
+    pub interval: Option<u32>,
+    pub expires: Option<i64>,
+    pub enabled: bool,
+    pub content: String,
+    pub tts: bool,
+    pub embed_title: String,
+    pub embed_description: String,
+    pub embed_color: u32,
+    // missing from dumps made before these were exported
+    #[serde(default)]
+    pub embed_image_url: Option<String>,
+    #[serde(default)]
+    pub embed_thumbnail_url: Option<String>,
+    #[serde(default)]
+    pub embed_footer: String,
+    #[serde(default)]
+    pub embed_footer_url: Option<String>,
+    #[serde(default)]
+    pub embed_author: String,
+    #[serde(default)]
+    pub embed_author_url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DumpedEmbedField {
+    /// The UID of the reminder the field belongs to
+    pub uid: String,
+    pub title: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+/// What happened to a single reminder during an import
+pub enum ImportOutcome {
+    Imported,
+    /// A reminder with the same UID exists already, as when an import is run again
+    Exists,
+    Invalid,
+    UnknownChannel,
+    UnknownUser,
+}
+
+impl ImportOutcome {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Imported => "imported",
+            Self::Exists => "already exists",
+            Self::Invalid => "invalid",
+            Self::UnknownChannel => "channel not found or not visible to the bot",
+            Self::UnknownUser => "DM user not found",
+        }
+    }
+}
+
+impl ReminderDump {
+    /// Every reminder in the database, or only those in one guild's channels
+    pub async fn export(guild_id: Option<GuildId>, pool: &MySqlPool) -> Result<Self, sqlx::Error> {
+        let guild_id = guild_id.map(|guild_id| *guild_id.as_u64());
+
+        let reminders = sqlx::query_as_unchecked!(
+            DumpedReminder,
+            "
+SELECT
+    reminders.uid,
+    reminders.name,
+    channels.channel,
+    guilds.guild,
+    dm_users.user AS dm_user,
+    set_by_users.user AS set_by,
+    TIMESTAMPDIFF(SECOND, FROM_UNIXTIME(0), reminders.`utc_time`) AS utc_time,
+    reminders.`interval`,
+    TIMESTAMPDIFF(SECOND, FROM_UNIXTIME(0), reminders.expires) AS expires,
+    reminders.enabled,
+    reminders.content,
+    reminders.tts,
+    reminders.embed_title,
+    reminders.embed_description,
+    reminders.embed_color,
+    reminders.embed_image_url,
+    reminders.embed_thumbnail_url,
+    reminders.embed_footer,
+    reminders.embed_footer_url,
+    reminders.embed_author,
+    reminders.embed_author_url,
+    reminders.username,
+    reminders.avatar
+FROM reminders
+INNER JOIN channels ON channels.id = reminders.channel_id
+LEFT JOIN guilds ON guilds.id = channels.guild_id
+LEFT JOIN users AS dm_users ON dm_users.dm_channel = channels.id
+LEFT JOIN users AS set_by_users ON set_by_users.id = reminders.set_by
+WHERE ? IS NULL OR guilds.guild = ?
+ORDER BY reminders.id
+            ",
+            guild_id,
+            guild_id
+        )
+        .fetch_all(pool)
+        .timed("ReminderDump::export")
+        .await?;
+
+        let embed_fields = sqlx::query_as_unchecked!(
+            DumpedEmbedField,
+            "
+SELECT
+    reminders.uid,
+    embed_fields.title,
+    embed_fields.value,
+    embed_fields.inline
+FROM embed_fields
+INNER JOIN reminders ON reminders.id = embed_fields.reminder_id
+INNER JOIN channels ON channels.id = reminders.channel_id
+LEFT JOIN guilds ON guilds.id = channels.guild_id
+WHERE ? IS NULL OR guilds.guild = ?
+ORDER BY embed_fields.id
+            ",
+            guild_id,
+            guild_id
+        )
+        .fetch_all(pool)
+        .timed("ReminderDump::export fields")
+        .await?;
+
+        Ok(Self {
+            reminders,
+            embed_fields,
+        })
+    }
+}
+
+impl DumpedReminder {
+    /// Recreate the reminder and those of `embed_fields` with its UID, adding its guild, channel
+    /// and DM user to the database as needed. Reminders are matched by UID, so an interrupted
+    /// import can be run again to carry on
+    pub async fn import(
+        &self,
+        ctx: &Context,
+        pool: &MySqlPool,
+        embed_fields: &[DumpedEmbedField],
+    ) -> Result<ImportOutcome, Box<dyn std::error::Error + Sync + Send>> {
+        if self.uid.is_empty()
+            || self.uid.len() > 64
+            || self.content.chars().count() > *MAX_CONTENT_LENGTH
+            || (self.guild.is_none() && self.dm_user.is_none())
+        {
+            return Ok(ImportOutcome::Invalid);
+        }
+
+        let exists = sqlx::query!(
+            "
+SELECT COUNT(1) AS count FROM reminders WHERE uid = ?
+            ",
+            self.uid
+        )
+        .fetch_one(pool)
+        .await?
+        .count
+            > 0;
+
+        if exists {
+            return Ok(ImportOutcome::Exists);
+        }
+
+        let channel_id = match (self.guild, self.dm_user) {
+            (Some(guild_id), _) => {
+                let channel = ChannelId(self.channel)
+                    .to_channel_cached(&ctx)
+                    .filter(|channel| {
+                        channel
+                            .clone()
+                            .guild()
+                            .map(|channel| *channel.guild_id.as_u64())
+                            == Some(guild_id)
+                    });
+
+                match channel {
+                    Some(channel) => {
+                        // the guild has to be stored before its channels
+                        ctx.guild_data(GuildId(guild_id)).await?;
+
+                        ChannelData::from_channel(channel, pool).await?.id
+                    }
+
+                    None => return Ok(ImportOutcome::UnknownChannel),
+                }
+            }
+
+            (None, Some(user_id)) => match UserId(user_id).to_user(&ctx).await {
+                Ok(user) => UserData::from_user(&user, &ctx, pool).await?.dm_channel,
+
+                Err(_) => return Ok(ImportOutcome::UnknownUser),
+            },
+
+            (None, None) => return Ok(ImportOutcome::Invalid),
+        };
+
+        // a reminder is only imported with all of its fields
+        let mut transaction = pool.begin().await?;
+
+        let reminder_id = sqlx::query!(
+            "
+INSERT INTO reminders (
+    uid,
+    name,
+    channel_id,
+    `utc_time`,
+    `interval`,
+    expires,
+    enabled,
+    content,
+    tts,
+    embed_title,
+    embed_description,
+    embed_color,
+    embed_image_url,
+    embed_thumbnail_url,
+    embed_footer,
+    embed_footer_url,
+    embed_author,
+    embed_author_url,
+    username,
+    avatar,
+    set_by
+) VALUES (
+    ?,
+    ?,
+    ?,
+    DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND),
+    ?,
+    DATE_ADD(FROM_UNIXTIME(0), INTERVAL ? SECOND),
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    (SELECT id FROM users WHERE user = ?)
+)
+            ",
+            self.uid,
+            self.name,
+            channel_id,
+            self.utc_time,
+            self.interval,
+            self.expires,
+            self.enabled,
+            self.content,
+            self.tts,
+            self.embed_title,
+            self.embed_description,
+            self.embed_color,
+            self.embed_image_url,
+            self.embed_thumbnail_url,
+            self.embed_footer,
+            self.embed_footer_url,
+            self.embed_author,
+            self.embed_author_url,
+            self.username,
+            self.avatar,
+            self.set_by
+        )
+        .execute(&mut transaction)
+        .await?
+        .last_insert_id();
+
+        for field in embed_fields.iter().filter(|field| field.uid == self.uid) {
+            sqlx::query!(
+                "
+INSERT INTO embed_fields (title, value, inline, reminder_id) VALUES (?, ?, ?, ?)
+                ",
+                field.title,
+                field.value,
+                field.inline,
+                reminder_id
+            )
+            .execute(&mut transaction)
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        Ok(ImportOutcome::Imported)
+    }
+}
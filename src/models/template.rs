@@ -0,0 +1,165 @@
+use sqlx::MySqlPool;
+
+// the lengths of the reminder_templates columns, in characters
+pub const TEMPLATE_CONTENT_LIMIT: usize = 2048;
+pub const TEMPLATE_TITLE_LIMIT: usize = 256;
+pub const TEMPLATE_DESCRIPTION_LIMIT: usize = 2048;
+
+pub struct Template {
+    pub id: u32,
+    pub name: String,
+    pub content: String,
+    pub interval: Option<u32>,
+    pub embed_title: String,
+    pub embed_description: String,
+    pub embed_color: u32,
+}
+
+impl Template {
+    pub fn valid_name(name: &str) -> bool {
+        !name.is_empty()
+            && name.len() <= 24
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    pub async fn from_name(guild_id: u64, name: &str, pool: &MySqlPool) -> Option<Self> {
+        sqlx::query_as_unchecked!(
+            Template,
+            "
+SELECT
+    id, name, content, `interval`, embed_title, embed_description, embed_color
+FROM
+    reminder_templates
+WHERE
+    guild_id = (SELECT id FROM guilds WHERE guild = ?) AND name = ?
+            ",
+            guild_id,
+            name
+        )
+        .fetch_one(pool)
+        .await
+        .ok()
+    }
+
+    pub async fn from_guild(guild_id: u64, pool: &MySqlPool) -> Vec<Self> {
+        sqlx::query_as_unchecked!(
+            Template,
+            "
+SELECT
+    id, name, content, `interval`, embed_title, embed_description, embed_color
+FROM
+    reminder_templates
+WHERE
+    guild_id = (SELECT id FROM guilds WHERE guild = ?)
+ORDER BY
+    name
+            ",
+            guild_id
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    pub async fn count_from_guild(guild_id: u64, pool: &MySqlPool) -> u32 {
+        sqlx::query!(
+            "
+SELECT COUNT(1) AS count FROM reminder_templates WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?)
+            ",
+            guild_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .count as u32
+    }
+
+    /// Create or overwrite the guild's template with this name. `content` must fit
+    /// `TEMPLATE_CONTENT_LIMIT`.
+    pub async fn create(
+        guild_id: u64,
+        name: &str,
+        interval: Option<i64>,
+        content: &str,
+        pool: &MySqlPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "
+INSERT INTO reminder_templates (guild_id, name, `interval`, content)
+    VALUES ((SELECT id FROM guilds WHERE guild = ?), ?, ?, ?)
+    ON DUPLICATE KEY UPDATE `interval` = ?, content = ?
+            ",
+            guild_id,
+            name,
+            interval,
+            content,
+            interval,
+            content
+        )
+        .execute(pool)
+        .await
+        .map(|_| ())
+    }
+
+    /// Set the embed that reminders created from the template are sent with. Empty `title` and
+    /// `description` remove it. Both must fit their `TEMPLATE_*_LIMIT`.
+    pub async fn set_embed(
+        &self,
+        title: &str,
+        description: &str,
+        color: u32,
+        pool: &MySqlPool,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "
+UPDATE reminder_templates SET embed_title = ?, embed_description = ?, embed_color = ? WHERE id = ?
+            ",
+            title,
+            description,
+            color,
+            self.id
+        )
+        .execute(pool)
+        .await
+        .map(|_| ())
+    }
+
+    /// Returns whether a template was deleted.
+    pub async fn delete(guild_id: u64, name: &str, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+DELETE FROM reminder_templates WHERE guild_id = (SELECT id FROM guilds WHERE guild = ?) AND name = ?
+            ",
+            guild_id,
+            name
+        )
+        .execute(pool)
+        .await
+        .unwrap()
+        .rows_affected()
+            > 0
+    }
+
+    /// Copy the template's embed settings onto a reminder created from it.
+    pub async fn apply_embed(&self, reminder_id: u32, pool: &MySqlPool) {
+        // leave the reminder's own embed alone (e.g. long content) if the template has none
+        if self.embed_title.is_empty() && self.embed_description.is_empty() {
+            return;
+        }
+
+        sqlx::query!(
+            "
+UPDATE reminders SET embed_title = ?, embed_description = ?, embed_color = ? WHERE id = ?
+            ",
+            self.embed_title,
+            self.embed_description,
+            self.embed_color,
+            reminder_id
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
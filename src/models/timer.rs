@@ -1,11 +1,14 @@
 use sqlx::MySqlPool;
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
 
 pub struct Timer {
     pub name: String,
     pub start_time: NaiveDateTime,
     pub owner: u64,
+    /// The user who started the timer. Unknown for guild timers started before this was recorded
+    pub created_by: Option<u64>,
 }
 
 impl Timer {
@@ -13,7 +16,7 @@ impl Timer {
         sqlx::query_as_unchecked!(
             Timer,
             "
-SELECT name, start_time, owner FROM timers WHERE owner = ?
+SELECT name, start_time, owner, created_by FROM timers WHERE owner = ?
             ",
             owner
         )
@@ -22,6 +25,32 @@ SELECT name, start_time, owner FROM timers WHERE owner = ?
         .unwrap()
     }
 
+    /// All timers started by a user, whether in a server or in DMs.
+    pub async fn from_creator(creator: u64, pool: &MySqlPool) -> Vec<Self> {
+        sqlx::query_as_unchecked!(
+            Timer,
+            "
+SELECT name, start_time, owner, created_by FROM timers WHERE created_by = ? OR owner = ?
+            ",
+            creator,
+            creator
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    /// When the timer was started, as a wall-clock time in `timezone`
+    pub fn display_start(&self, timezone: Tz) -> String {
+        format!(
+            "{} ({})",
+            Utc.from_utc_datetime(&self.start_time)
+                .with_timezone(&timezone)
+                .format("%Y-%m-%d %H:%M"),
+            timezone
+        )
+    }
+
     pub async fn count_from_owner(owner: u64, pool: &MySqlPool) -> u32 {
         sqlx::query!(
             "
@@ -35,13 +64,62 @@ SELECT COUNT(1) as count FROM timers WHERE owner = ?
         .count as u32
     }
 
-    pub async fn create(name: &str, owner: u64, pool: &MySqlPool) {
+    /// Timer names are unique per owner. The table doesn't enforce it, so this is checked before
+    /// a name is used
+    pub async fn exists(owner: u64, name: &str, pool: &MySqlPool) -> bool {
         sqlx::query!(
             "
-INSERT INTO timers (name, owner) VALUES (?, ?)
+SELECT COUNT(1) AS count FROM timers WHERE owner = ? AND name = ?
+            ",
+            owner,
+            name
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .count
+            > 0
+    }
+
+    /// Delete the owner's timer with this name. Returns false if there wasn't one. Duplicates from
+    /// before names were unique are removed one at a time, oldest first
+    pub async fn delete(owner: u64, name: &str, pool: &MySqlPool) -> bool {
+        sqlx::query!(
+            "
+DELETE FROM timers WHERE owner = ? AND name = ? ORDER BY start_time LIMIT 1
+            ",
+            owner,
+            name
+        )
+        .execute(pool)
+        .await
+        .unwrap()
+        .rows_affected()
+            > 0
+    }
+
+    pub async fn rename(owner: u64, name: &str, new_name: &str, pool: &MySqlPool) {
+        sqlx::query!(
+            "
+UPDATE timers SET name = ? WHERE owner = ? AND name = ? ORDER BY start_time LIMIT 1
+            ",
+            new_name,
+            owner,
+            name
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    pub async fn create(name: &str, owner: u64, created_by: u64, pool: &MySqlPool) {
+        sqlx::query!(
+            "
+INSERT INTO timers (name, owner, created_by) VALUES (?, ?, ?)
             ",
             name,
-            owner
+            owner,
+            created_by
         )
         .execute(pool)
         .await
@@ -5,11 +5,15 @@ use serenity::{
 
 use sqlx::MySqlPool;
 
+use chrono::{DateTime, NaiveTime, Utc};
 use chrono_tz::Tz;
 
 use log::error;
 
-use crate::consts::{LOCAL_LANGUAGE, LOCAL_TIMEZONE};
+use crate::{
+    consts::{LOCAL_LANGUAGE, LOCAL_TIMEZONE},
+    slow_query::TimedQuery,
+};
 
 pub struct UserData {
     pub id: u32,
@@ -18,6 +22,11 @@ pub struct UserData {
     pub dm_channel: u32,
     pub language: String,
     pub timezone: String,
+    pub dm_opt_out: bool,
+    pub quiet_start: Option<NaiveTime>,
+    pub quiet_end: Option<NaiveTime>,
+    pub quiet_silent: bool,
+    pub default_channel: Option<u64>,
 }
 
 impl UserData {
@@ -34,6 +43,7 @@ SELECT language FROM users WHERE user = ?
             user_id
         )
         .fetch_one(pool)
+        .timed("UserData::language_of")
         .await
         {
             Ok(r) => r.language,
@@ -55,6 +65,7 @@ SELECT timezone FROM users WHERE user = ?
             user_id
         )
         .fetch_one(pool)
+        .timed("UserData::timezone_of")
         .await
         {
             Ok(r) => r.timezone,
@@ -65,6 +76,26 @@ SELECT timezone FROM users WHERE user = ?
         .unwrap()
     }
 
+    pub async fn default_channel_of<U>(user: U, pool: &MySqlPool) -> Option<u64>
+    where
+        U: Into<UserId>,
+    {
+        let user_id = user.into().as_u64().to_owned();
+
+        sqlx::query!(
+            "
+SELECT default_channel FROM users WHERE user = ?
+            ",
+            user_id
+        )
+        .fetch_one(pool)
+        .timed("UserData::default_channel_of")
+        .await
+        .ok()
+        .map(|r| r.default_channel)
+        .flatten()
+    }
+
     pub async fn from_user(
         user: &User,
         ctx: impl CacheHttp,
@@ -75,11 +106,12 @@ SELECT timezone FROM users WHERE user = ?
         match sqlx::query_as_unchecked!(
             Self,
             "
-SELECT id, user, name, dm_channel, IF(language IS NULL, ?, language) AS language, IF(timezone IS NULL, ?, timezone) AS timezone FROM users WHERE user = ?
+SELECT id, user, name, dm_channel, IF(language IS NULL, ?, language) AS language, IF(timezone IS NULL, ?, timezone) AS timezone, dm_opt_out, quiet_start, quiet_end, quiet_silent, default_channel FROM users WHERE user = ?
             ",
             *LOCAL_LANGUAGE, *LOCAL_TIMEZONE, user_id
         )
         .fetch_one(pool)
+        .timed("UserData::from_user")
         .await
         {
             Ok(c) => Ok(c),
@@ -109,7 +141,7 @@ INSERT INTO users (user, name, dm_channel, language, timezone) VALUES (?, ?, (SE
                 Ok(sqlx::query_as_unchecked!(
                     Self,
                     "
-SELECT id, user, name, dm_channel, language, timezone FROM users WHERE user = ?
+SELECT id, user, name, dm_channel, language, timezone, dm_opt_out, quiet_start, quiet_end, quiet_silent, default_channel FROM users WHERE user = ?
                     ",
                     user_id
                 )
@@ -128,11 +160,16 @@ SELECT id, user, name, dm_channel, language, timezone FROM users WHERE user = ?
     pub async fn commit_changes(&self, pool: &MySqlPool) {
         sqlx::query!(
             "
-UPDATE users SET name = ?, language = ?, timezone = ? WHERE id = ?
+UPDATE users SET name = ?, language = ?, timezone = ?, dm_opt_out = ?, quiet_start = ?, quiet_end = ?, quiet_silent = ?, default_channel = ? WHERE id = ?
             ",
             self.name,
             self.language,
             self.timezone,
+            self.dm_opt_out,
+            self.quiet_start,
+            self.quiet_end,
+            self.quiet_silent,
+            self.default_channel,
             self.id
         )
         .execute(pool)
@@ -143,4 +180,22 @@ UPDATE users SET name = ?, language = ?, timezone = ? WHERE id = ?
     pub fn timezone(&self) -> Tz {
         self.timezone.parse().unwrap()
     }
+
+    /// Whether `time` is in the user's quiet hours, read in their timezone. The window includes
+    /// its start but not its end, and wraps past midnight when the end is earlier than the start
+    pub fn in_quiet_hours(&self, time: DateTime<Utc>) -> bool {
+        match (self.quiet_start, self.quiet_end) {
+            (Some(start), Some(end)) => {
+                let local = time.with_timezone(&self.timezone()).time();
+
+                if start <= end {
+                    local >= start && local < end
+                } else {
+                    local >= start || local < end
+                }
+            }
+
+            _ => false,
+        }
+    }
 }
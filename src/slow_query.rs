@@ -0,0 +1,38 @@
+use serenity::async_trait;
+
+use log::warn;
+
+use std::{future::Future, time::Instant};
+
+use crate::consts::SLOW_QUERY_THRESHOLD;
+
+/// Logs database queries that take longer than `SLOW_QUERY_THRESHOLD` milliseconds, so that
+/// self-hosters can see which queries need indexes
+#[async_trait]
+pub trait TimedQuery: Future + Sized + Send {
+    async fn timed(self, label: &'static str) -> Self::Output;
+}
+
+#[async_trait]
+impl<F> TimedQuery for F
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    async fn timed(self, label: &'static str) -> Self::Output {
+        // a threshold of 0 turns the logging off, and the query is awaited as normal
+        if *SLOW_QUERY_THRESHOLD == 0 {
+            return self.await;
+        }
+
+        let start = Instant::now();
+        let output = self.await;
+        let elapsed = start.elapsed().as_millis();
+
+        if elapsed >= *SLOW_QUERY_THRESHOLD as u128 {
+            warn!("Slow query {}: took {}ms", label, elapsed);
+        }
+
+        output
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt::{Display, Formatter, Result as FmtResult};
 
 use crate::consts::{LOCAL_TIMEZONE, PYTHON_LOCATION};
 
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc, Weekday};
 use chrono_tz::Tz;
 use std::convert::TryFrom;
 use std::str::from_utf8;
@@ -29,6 +29,75 @@ impl std::error::Error for InvalidTime {}
 enum ParseType {
     Explicit,
     Displacement,
+    Timestamp(i64),
+    Relative(RelativeDate),
+}
+
+/// Dates named relative to the current calendar, e.g. `end of month`. Ends are at 23:59 and starts
+/// at 00:00 in the parser's timezone, always in the future
+#[derive(Clone, Copy)]
+enum RelativeDate {
+    EndOfMonth,
+    StartOfMonth,
+    EndOfWeek,
+    StartOfWeek,
+}
+
+impl RelativeDate {
+    fn from_input(input: &str) -> Option<Self> {
+        let words = input
+            .to_lowercase()
+            .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        match words.as_str() {
+            "end of month" => Some(Self::EndOfMonth),
+            "start of month" => Some(Self::StartOfMonth),
+            "end of week" => Some(Self::EndOfWeek),
+            "start of week" => Some(Self::StartOfWeek),
+            _ => None,
+        }
+    }
+}
+
+// the last second of 9999-12-31, the latest time a Discord timestamp token can display
+const MAX_TIMESTAMP_TOKEN: i64 = 253_402_300_799;
+
+/// The unix time in a Discord timestamp token, e.g. `<t:1618953630>` or `<t:1618953630:R>`. The
+/// style suffix only changes how Discord displays the token, so it is checked but ignored
+fn timestamp_token(input: &str) -> Option<i64> {
+    let inner = input.strip_prefix("<t:")?.strip_suffix('>')?;
+
+    let seconds = match inner.split_once(':') {
+        Some((seconds, style)) => {
+            if style.len() == 1 && "tTdDfFR".contains(style) {
+                seconds
+            } else {
+                return None;
+            }
+        }
+
+        None => inner,
+    };
+
+    if !seconds.is_empty() && seconds.chars().all(|c| c.is_ascii_digit()) {
+        seconds
+            .parse()
+            .ok()
+            .filter(|seconds| *seconds <= MAX_TIMESTAMP_TOKEN)
+    } else {
+        None
+    }
+}
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    if date.month() == 12 {
+        NaiveDate::from_ymd(date.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd(date.year(), date.month() + 1, 1)
+    }
 }
 
 pub struct TimeParser {
@@ -50,7 +119,11 @@ impl TimeParser {
     pub fn new(input: &str, timezone: Tz) -> Self {
         let inverted = input.starts_with('-');
 
-        let parse_type = if input.contains('/') || input.contains(':') {
+        let parse_type = if let Some(timestamp) = timestamp_token(input) {
+            ParseType::Timestamp(timestamp)
+        } else if let Some(relative) = RelativeDate::from_input(input) {
+            ParseType::Relative(relative)
+        } else if input.contains('/') || input.contains(':') {
             ParseType::Explicit
         } else {
             ParseType::Displacement
@@ -64,6 +137,15 @@ impl TimeParser {
         }
     }
 
+    /// Whether the input names a time of day or date in the parser's timezone, rather than a
+    /// time relative to now or a fixed instant
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self.parse_type,
+            ParseType::Explicit | ParseType::Relative(_)
+        )
+    }
+
     pub fn timestamp(&self) -> Result<i64, InvalidTime> {
         match self.parse_type {
             ParseType::Explicit => Ok(self.process_explicit()?),
@@ -76,6 +158,10 @@ impl TimeParser {
 
                 Ok(since_epoch.as_secs() as i64 + self.process_displacement()?)
             }
+
+            ParseType::Relative(relative) => self.process_relative(relative),
+
+            ParseType::Timestamp(timestamp) => Ok(timestamp),
         }
     }
 
@@ -91,9 +177,91 @@ impl TimeParser {
             }
 
             ParseType::Displacement => Ok(self.process_displacement()?),
+
+            ParseType::Relative(relative) => {
+                let now = SystemTime::now();
+                let since_epoch = now
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time calculated as going backwards. Very bad");
+
+                Ok(self.process_relative(relative)? - since_epoch.as_secs() as i64)
+            }
+
+            ParseType::Timestamp(timestamp) => {
+                let now = SystemTime::now();
+                let since_epoch = now
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Time calculated as going backwards. Very bad");
+
+                Ok(timestamp - since_epoch.as_secs() as i64)
+            }
         }
     }
 
+    fn process_relative(&self, relative: RelativeDate) -> Result<i64, InvalidTime> {
+        self.relative_timestamp(relative, Utc::now())
+    }
+
+    fn relative_timestamp(
+        &self,
+        relative: RelativeDate,
+        now: DateTime<Utc>,
+    ) -> Result<i64, InvalidTime> {
+        let today = now.with_timezone(&self.timezone).date().naive_local();
+        let now = now.timestamp();
+        let from_monday = today.weekday().num_days_from_monday() as i64;
+
+        match relative {
+            RelativeDate::EndOfMonth => {
+                let end = self.local_timestamp(first_of_next_month(today).pred(), 23, 59)?;
+
+                // only in the month's final minute, when the next month's end is meant
+                if end <= now {
+                    self.local_timestamp(
+                        first_of_next_month(first_of_next_month(today)).pred(),
+                        23,
+                        59,
+                    )
+                } else {
+                    Ok(end)
+                }
+            }
+
+            RelativeDate::StartOfMonth => self.local_timestamp(first_of_next_month(today), 0, 0),
+
+            RelativeDate::EndOfWeek => {
+                let end = self.local_timestamp(today + Duration::days(6 - from_monday), 23, 59)?;
+
+                if end <= now {
+                    self.local_timestamp(today + Duration::days(13 - from_monday), 23, 59)
+                } else {
+                    Ok(end)
+                }
+            }
+
+            RelativeDate::StartOfWeek => {
+                self.local_timestamp(today + Duration::days(7 - from_monday), 0, 0)
+            }
+        }
+    }
+
+    /// The timestamp of a wall-clock time in the parser's timezone. A time skipped by a DST change
+    /// is moved an hour later, past the gap, and a repeated time takes its first occurrence
+    fn local_timestamp(&self, date: NaiveDate, hour: u32, minute: u32) -> Result<i64, InvalidTime> {
+        let local = date.and_hms(hour, minute, 0);
+
+        self.timezone
+            .from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                self.timezone
+                    .from_local_datetime(&(local + Duration::hours(1)))
+                    .earliest()
+            })
+            .map(|time| time.timestamp())
+            .ok_or(InvalidTime::ParseErrorChrono)
+    }
+
     fn process_explicit(&self) -> Result<i64, InvalidTime> {
         let mut time = Utc::now()
             .with_timezone(&self.timezone)
@@ -205,6 +373,143 @@ impl TimeParser {
     }
 }
 
+/// How a natural reminder repeats
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NaturalInterval {
+    /// Every fixed number of seconds
+    Fixed(i64),
+    /// Every `weeks` weeks on `weekday`
+    Weekly { weekday: Weekday, weeks: i64 },
+    /// On the `nth` `weekday` of every month
+    Monthly { weekday: Weekday, nth: u32 },
+}
+
+impl NaturalInterval {
+    /// The interval stored with the reminder. Monthly intervals store four weeks, which is only
+    /// used to check the interval limits; occurrences come from the weekday and ordinal
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::Fixed(seconds) => *seconds,
+
+            Self::Weekly { weeks, .. } => weeks * 7 * 24 * 60 * 60,
+
+            Self::Monthly { .. } => 4 * 7 * 24 * 60 * 60,
+        }
+    }
+
+    /// Move a parsed time onto the first day the interval lands on, keeping its time of day in
+    /// `timezone`. Fixed intervals start whenever the time says
+    pub fn first_occurrence(&self, time: i64, timezone: Tz) -> i64 {
+        let local = timezone.timestamp(time, 0).naive_local();
+
+        let date = match self {
+            Self::Fixed(_) => return time,
+
+            Self::Weekly { weekday, .. } => {
+                let days_ahead = (7 + weekday.num_days_from_monday()
+                    - local.weekday().num_days_from_monday())
+                    % 7;
+
+                local.date() + Duration::days(days_ahead as i64)
+            }
+
+            Self::Monthly { weekday, nth } => {
+                let mut month = local.date().with_day(1).unwrap();
+
+                // a 5th weekday is at most a few months away
+                loop {
+                    match NaiveDate::from_weekday_of_month_opt(
+                        month.year(),
+                        month.month(),
+                        *weekday,
+                        *nth as u8,
+                    ) {
+                        Some(date) if date >= local.date() => break date,
+
+                        _ => month = first_of_next_month(month),
+                    }
+                }
+            }
+        };
+
+        let aligned = date.and_time(local.time());
+
+        timezone
+            .from_local_datetime(&aligned)
+            .earliest()
+            .or_else(|| {
+                timezone
+                    .from_local_datetime(&(aligned + Duration::hours(1)))
+                    .earliest()
+            })
+            .map_or(time, |time| time.timestamp())
+    }
+}
+
+/// Parse the interval part of a natural reminder, e.g. `2 hours`, `day`, `other week`, `friday`
+/// or `3rd monday`. "other" repeats every 2 units and weekdays repeat weekly. An ordinal with a
+/// weekday means that weekday of each month, so it is rejected beyond the 5th; with other units
+/// it repeats every N units.
+pub fn natural_interval(interval: &str) -> Option<NaturalInterval> {
+    let interval = interval.trim().to_lowercase();
+
+    let (multiplier, ordinal, unit) = match interval.split_once(' ') {
+        Some(("other", unit)) => (2, false, unit.trim()),
+
+        Some((ordinal, unit))
+            if ["st", "nd", "rd", "th"]
+                .iter()
+                .any(|suffix| ordinal.ends_with(suffix))
+                && ordinal.starts_with(|c: char| c.is_ascii_digit()) =>
+        {
+            match ordinal[..ordinal.len() - 2].parse::<i64>() {
+                Ok(n) if n >= 1 => (n, true, unit.trim()),
+                _ => return None,
+            }
+        }
+
+        _ => (1, false, interval.as_str()),
+    };
+
+    let weekdays = [
+        ("monday", Weekday::Mon),
+        ("tuesday", Weekday::Tue),
+        ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu),
+        ("friday", Weekday::Fri),
+        ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ];
+
+    let weekday = weekdays
+        .iter()
+        .find(|(name, _)| *name == unit.trim_end_matches('s'))
+        .map(|(_, weekday)| *weekday);
+
+    match weekday {
+        Some(weekday) if ordinal => {
+            if (1..=5).contains(&multiplier) {
+                Some(NaturalInterval::Monthly {
+                    weekday,
+                    nth: multiplier as u32,
+                })
+            } else {
+                None
+            }
+        }
+
+        Some(weekday) => Some(NaturalInterval::Weekly {
+            weekday,
+            weeks: multiplier,
+        }),
+
+        None => humantime::parse_duration(unit)
+            .or_else(|_| humantime::parse_duration(&format!("1 {}", unit)))
+            .map(|duration| NaturalInterval::Fixed(duration.as_secs() as i64 * multiplier))
+            .ok(),
+    }
+}
+
 pub async fn natural_parser(time: &str, timezone: &str) -> Option<i64> {
     Command::new(&*PYTHON_LOCATION)
         .arg("-c")
@@ -226,3 +531,271 @@ pub async fn natural_parser(time: &str, timezone: &str) -> Option<i64> {
         .map(|inner| if inner < 0 { None } else { Some(inner) })
         .flatten()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_token_bare() {
+        assert_eq!(timestamp_token("<t:1618953630>"), Some(1618953630));
+    }
+
+    #[test]
+    fn timestamp_token_styles() {
+        for style in &["t", "T", "d", "D", "f", "F", "R"] {
+            assert_eq!(
+                timestamp_token(&format!("<t:1618953630:{}>", style)),
+                Some(1618953630)
+            );
+        }
+    }
+
+    #[test]
+    fn timestamp_token_invalid() {
+        assert_eq!(timestamp_token("<t:1618953630:x>"), None);
+        assert_eq!(timestamp_token("<t:1618953630:RR>"), None);
+        assert_eq!(timestamp_token("<t:>"), None);
+        assert_eq!(timestamp_token("<t:-5>"), None);
+        assert_eq!(timestamp_token("<t:1618953630"), None);
+        assert_eq!(timestamp_token("1618953630"), None);
+    }
+
+    #[test]
+    fn timestamp_token_range() {
+        assert_eq!(timestamp_token("<t:253402300799>"), Some(253402300799));
+        assert_eq!(timestamp_token("<t:253402300800>"), None);
+        assert_eq!(timestamp_token("<t:99999999999999999999>"), None);
+    }
+
+    #[test]
+    fn timestamp_token_parser() {
+        let parser = TimeParser::new("<t:1618953630:R>", Tz::UTC);
+
+        assert!(!parser.is_local());
+        assert_eq!(parser.timestamp().unwrap(), 1618953630);
+    }
+
+    fn relative(input: &str, timezone: Tz, now: DateTime<Utc>) -> i64 {
+        let parser = TimeParser::new(input, timezone);
+
+        match parser.parse_type {
+            ParseType::Relative(relative) => parser.relative_timestamp(relative, now).unwrap(),
+            _ => panic!("{} wasn't parsed as a relative date", input),
+        }
+    }
+
+    #[test]
+    fn relative_date_input() {
+        for input in &["end of month", "End-Of-Month", "end_of  month"] {
+            assert!(matches!(
+                TimeParser::new(input, Tz::UTC).parse_type,
+                ParseType::Relative(RelativeDate::EndOfMonth)
+            ));
+        }
+    }
+
+    #[test]
+    fn end_of_month_lengths() {
+        // january 31st rolls over to the end of february, not past it
+        let now = Utc.ymd(2021, 1, 31).and_hms(23, 59, 30);
+        assert_eq!(
+            relative("end of month", Tz::UTC, now),
+            Utc.ymd(2021, 2, 28).and_hms(23, 59, 0).timestamp()
+        );
+
+        let now = Utc.ymd(2024, 1, 31).and_hms(23, 59, 30);
+        assert_eq!(
+            relative("end of month", Tz::UTC, now),
+            Utc.ymd(2024, 2, 29).and_hms(23, 59, 0).timestamp()
+        );
+
+        let now = Utc.ymd(2021, 1, 31).and_hms(12, 0, 0);
+        assert_eq!(
+            relative("end of month", Tz::UTC, now),
+            Utc.ymd(2021, 1, 31).and_hms(23, 59, 0).timestamp()
+        );
+
+        let now = Utc.ymd(2021, 4, 10).and_hms(12, 0, 0);
+        assert_eq!(
+            relative("end of month", Tz::UTC, now),
+            Utc.ymd(2021, 4, 30).and_hms(23, 59, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn start_of_month_year_end() {
+        let now = Utc.ymd(2021, 12, 31).and_hms(12, 0, 0);
+        assert_eq!(
+            relative("start of month", Tz::UTC, now),
+            Utc.ymd(2022, 1, 1).and_hms(0, 0, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn week_boundaries() {
+        // a wednesday
+        let now = Utc.ymd(2021, 4, 21).and_hms(12, 0, 0);
+        assert_eq!(
+            relative("end of week", Tz::UTC, now),
+            Utc.ymd(2021, 4, 25).and_hms(23, 59, 0).timestamp()
+        );
+        assert_eq!(
+            relative("start of week", Tz::UTC, now),
+            Utc.ymd(2021, 4, 26).and_hms(0, 0, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn relative_date_timezone() {
+        // still the 31st in new york, but already april in utc
+        let now = Utc.ymd(2021, 4, 1).and_hms(2, 0, 0);
+        assert_eq!(
+            relative("end of month", Tz::America__New_York, now),
+            Utc.ymd(2021, 4, 1).and_hms(3, 59, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn relative_date_dst() {
+        // london moves to BST on the 28th, so the end of march is an hour earlier in utc
+        let now = Utc.ymd(2021, 3, 10).and_hms(12, 0, 0);
+        assert_eq!(
+            relative("end of month", Tz::Europe__London, now),
+            Utc.ymd(2021, 3, 31).and_hms(22, 59, 0).timestamp()
+        );
+
+        // são paulo skipped midnight on the 4th of november 2018, so 00:00 moves past the gap
+        let parser = TimeParser::new("start of week", Tz::America__Sao_Paulo);
+        assert_eq!(
+            parser
+                .local_timestamp(NaiveDate::from_ymd(2018, 11, 4), 0, 0)
+                .unwrap(),
+            Utc.ymd(2018, 11, 4).and_hms(3, 0, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn natural_interval_other() {
+        assert_eq!(
+            natural_interval("other day"),
+            Some(NaturalInterval::Fixed(2 * 24 * 60 * 60))
+        );
+        assert_eq!(
+            natural_interval("other friday"),
+            Some(NaturalInterval::Weekly {
+                weekday: Weekday::Fri,
+                weeks: 2
+            })
+        );
+    }
+
+    #[test]
+    fn natural_interval_weekday() {
+        for interval in &["friday", "Fridays"] {
+            assert_eq!(
+                natural_interval(interval),
+                Some(NaturalInterval::Weekly {
+                    weekday: Weekday::Fri,
+                    weeks: 1
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn natural_interval_ordinal() {
+        assert_eq!(
+            natural_interval("2nd friday"),
+            Some(NaturalInterval::Monthly {
+                weekday: Weekday::Fri,
+                nth: 2
+            })
+        );
+        assert_eq!(
+            natural_interval("5th monday"),
+            Some(NaturalInterval::Monthly {
+                weekday: Weekday::Mon,
+                nth: 5
+            })
+        );
+        assert_eq!(natural_interval("6th friday"), None);
+        assert_eq!(natural_interval("0th friday"), None);
+
+        // only weekdays are limited to the weeks of a month
+        assert_eq!(
+            natural_interval("7th day"),
+            Some(NaturalInterval::Fixed(7 * 24 * 60 * 60))
+        );
+        assert_eq!(natural_interval("0th day"), None);
+    }
+
+    #[test]
+    fn first_occurrence_weekly() {
+        let every_friday = NaturalInterval::Weekly {
+            weekday: Weekday::Fri,
+            weeks: 1,
+        };
+
+        // tuesday
+        let time = Tz::UTC.ymd(2021, 4, 20).and_hms(10, 0, 0).timestamp();
+        assert_eq!(
+            every_friday.first_occurrence(time, Tz::UTC),
+            Tz::UTC.ymd(2021, 4, 23).and_hms(10, 0, 0).timestamp()
+        );
+
+        // already a friday
+        let time = Tz::UTC.ymd(2021, 4, 23).and_hms(10, 0, 0).timestamp();
+        assert_eq!(every_friday.first_occurrence(time, Tz::UTC), time);
+    }
+
+    #[test]
+    fn first_occurrence_keeps_local_time() {
+        let tz = Tz::Europe__London;
+        let every_sunday = NaturalInterval::Weekly {
+            weekday: Weekday::Sun,
+            weeks: 1,
+        };
+
+        // the clocks go forward on sunday 28th
+        let time = tz.ymd(2021, 3, 23).and_hms(10, 0, 0).timestamp();
+        assert_eq!(
+            every_sunday.first_occurrence(time, tz),
+            Tz::UTC.ymd(2021, 3, 28).and_hms(9, 0, 0).timestamp()
+        );
+    }
+
+    #[test]
+    fn first_occurrence_monthly() {
+        let time = Tz::UTC.ymd(2021, 4, 20).and_hms(10, 0, 0).timestamp();
+
+        // april's 2nd friday has passed
+        let second_friday = NaturalInterval::Monthly {
+            weekday: Weekday::Fri,
+            nth: 2,
+        };
+        assert_eq!(
+            second_friday.first_occurrence(time, Tz::UTC),
+            Tz::UTC.ymd(2021, 5, 14).and_hms(10, 0, 0).timestamp()
+        );
+
+        let fifth_friday = NaturalInterval::Monthly {
+            weekday: Weekday::Fri,
+            nth: 5,
+        };
+        assert_eq!(
+            fifth_friday.first_occurrence(time, Tz::UTC),
+            Tz::UTC.ymd(2021, 4, 30).and_hms(10, 0, 0).timestamp()
+        );
+
+        // april has no 5th monday
+        let fifth_monday = NaturalInterval::Monthly {
+            weekday: Weekday::Mon,
+            nth: 5,
+        };
+        assert_eq!(
+            fifth_monday.first_occurrence(time, Tz::UTC),
+            Tz::UTC.ymd(2021, 5, 31).and_hms(10, 0, 0).timestamp()
+        );
+    }
+}